@@ -0,0 +1,49 @@
+#[macro_use] extern crate bencher;
+extern crate rand;
+extern crate rand_xoshiro;
+
+extern crate average;
+
+use bencher::Bencher;
+
+/// Create two random vectors by sampling from a normal distribution.
+fn initialize_vecs() -> (Vec<f64>, Vec<f64>) {
+    use rand::distributions::{Normal, Distribution};
+    use rand::SeedableRng;
+    let normal = Normal::new(2.0, 3.0);
+    let n = 1_000_000;
+    let mut xs = Vec::with_capacity(n);
+    let mut ys = Vec::with_capacity(n);
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+         17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32]);
+    for _ in 0..n {
+        let x = normal.sample(&mut rng);
+        xs.push(x);
+        ys.push(0.5 * x + normal.sample(&mut rng));
+    }
+    (xs, ys)
+}
+
+fn bench_covariance(b: &mut Bencher) {
+    let (xs, ys) = initialize_vecs();
+    b.iter(|| {
+        let mut c = average::CoVariance::new();
+        for (&x, &y) in xs.iter().zip(&ys) {
+            c.add(x, y);
+        }
+        c
+    });
+}
+
+fn bench_covariance_slice(b: &mut Bencher) {
+    let (xs, ys) = initialize_vecs();
+    b.iter(|| {
+        let mut c = average::CoVariance::new();
+        c.add_slice(&xs, &ys);
+        c
+    });
+}
+
+benchmark_group!(benches, bench_covariance, bench_covariance_slice);
+benchmark_main!(benches);
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate bencher;
 extern crate rand;
@@ -8,6 +8,8 @@ extern crate average;
 
 use bencher::Bencher;
 
+use average::Estimate;
+
 /// Create a random vector by sampling from a normal distribution.
 fn initialize_vec() -> Vec<f64> {
     use rand::distributions::{Normal, Distribution};
@@ -40,5 +42,22 @@ fn bench_moments(b: &mut Bencher) {
     });
 }
 
-benchmark_group!(benches, bench_kurtosis, bench_moments);
+fn bench_separate(b: &mut Bencher) {
+    let values = initialize_vec();
+    b.iter(|| {
+        let mut mean = average::Mean::new();
+        let mut variance = average::Variance::new();
+        let mut skewness = average::Skewness::new();
+        let mut kurtosis = average::Kurtosis::new();
+        for &x in &values {
+            mean.add(x);
+            variance.add(x);
+            skewness.add(x);
+            kurtosis.add(x);
+        }
+        (mean, variance, skewness, kurtosis)
+    });
+}
+
+benchmark_group!(benches, bench_kurtosis, bench_moments, bench_separate);
 benchmark_main!(benches);
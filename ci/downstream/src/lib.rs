@@ -0,0 +1,24 @@
+//! Check that the histograms defined by `define_histogram!` in another crate
+//! get the methods enabled by the features of `average`.
+//!
+//! This crate does not define any features, so the `std` feature of `average`
+//! has to be checked in `average` itself:
+//!
+//! ```text
+//! cargo build
+//! ```
+
+extern crate core;
+#[macro_use] extern crate average;
+
+define_histogram!(hist, 4);
+
+pub fn csv(xs: &[f64]) -> String {
+    let mut h = hist::Histogram::with_const_width(0., 4.);
+    for &x in xs {
+        h.add(x).ok();
+    }
+    let mut csv = Vec::new();
+    h.write_csv(&mut csv).unwrap();
+    String::from_utf8(csv).unwrap()
+}
@@ -0,0 +1,60 @@
+//! Check that the arithmetic estimators can be used with just `core`, and the
+//! allocating estimators with just `core` and `alloc`.
+//!
+//! This crate has to be built for a target without `std`, for example:
+//!
+//! ```text
+//! cargo build --target thumbv7m-none-eabi
+//! ```
+
+#![no_std]
+
+extern crate average;
+
+use average::{CoVariance, Estimate, Kurtosis, Mean, Merge, Quantile, Variance};
+
+pub fn mean(xs: &[f64]) -> f64 {
+    let a: Mean = xs.iter().collect();
+    a.mean()
+}
+
+pub fn mean_f32(xs: &[f32]) -> f32 {
+    let a: Mean<f32> = xs.iter().collect();
+    a.mean()
+}
+
+pub fn error(xs: &[f64]) -> f64 {
+    let (left, right) = xs.split_at(xs.len() / 2);
+    let mut a: Variance = left.iter().collect();
+    a.merge(&right.iter().collect());
+    a.error()
+}
+
+pub fn pearson(xs: &[f64], ys: &[f64]) -> f64 {
+    let mut a = CoVariance::new();
+    for (&x, &y) in xs.iter().zip(ys) {
+        a.add(x, y);
+    }
+    a.pearson()
+}
+
+pub fn kurtosis(xs: &[f64]) -> f64 {
+    let a: Kurtosis = xs.iter().collect();
+    a.kurtosis()
+}
+
+pub fn median(xs: &[f64]) -> f64 {
+    let mut a = Quantile::new(0.5);
+    for &x in xs {
+        a.add(x);
+    }
+    a.estimate()
+}
+
+pub fn correlation(rows: &[[f64; 3]]) -> f64 {
+    let mut a = average::CovarianceMatrix::new(3);
+    for row in rows {
+        a.add(row);
+    }
+    a.correlation(0, 2)
+}
@@ -0,0 +1,74 @@
+use super::{Estimate, GeometricMean, HarmonicMean, Mean};
+
+concatenate!(
+    /// Estimate the arithmetic, the geometric and the harmonic mean of a
+    /// sequence of numbers ("population") in one pass.
+    ///
+    /// This combines `Mean`, `GeometricMean` and `HarmonicMean` with
+    /// `concatenate!`. For positive observations, the means satisfy the
+    /// inequality `mean >= geometric_mean >= harmonic_mean`, with equality if
+    /// and only if all observations are equal, so `satisfies_inequality` can
+    /// be used as a sanity check.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::AllMeans;
+    ///
+    /// let a: AllMeans = [1., 2., 4.].iter().collect();
+    /// assert!((a.geometric_mean() - 2.).abs() < 1e-15);
+    /// assert!(a.satisfies_inequality());
+    /// ```
+    #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+    pub AllMeans,
+    [Mean, mean],
+    [GeometricMean, geometric_mean],
+    [HarmonicMean, harmonic_mean]);
+
+impl AllMeans {
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = AllMeans::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mean.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.mean.len()
+    }
+
+    /// Determine whether `mean >= geometric_mean >= harmonic_mean` holds.
+    ///
+    /// To allow for rounding errors, a relative tolerance of `1e-12` is used.
+    /// This is always true for positive observations, so `false` indicates
+    /// that some observations were not positive. It is true for an empty
+    /// sample.
+    #[inline]
+    pub fn satisfies_inequality(&self) -> bool {
+        let (am, gm, hm) = (self.mean(), self.geometric_mean(), self.harmonic_mean());
+        let tol = 1e-12;
+        am >= gm - tol * gm.abs() && gm >= hm - tol * hm.abs()
+    }
+}
+
+impl Estimate for AllMeans {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    /// Return the arithmetic mean.
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+
+use ndarray::ArrayView2;
+
+use super::Estimate;
+
+/// Estimate a statistic for each column of a two-dimensional array.
+///
+/// Each column is treated as a separate sample, so the result contains one
+/// estimator per column. The array is traversed row by row, so this is
+/// efficient for arrays in the default (row-major) memory order.
+///
+/// This requires the `ndarray` and `alloc` features.
+///
+///
+/// ## Example
+///
+/// ```
+/// # extern crate ndarray;
+/// # extern crate average;
+/// # fn main() {
+/// use ndarray::arr2;
+/// use average::{Variance, estimate_columns};
+///
+/// let a = arr2(&[[1., 10.],
+///                [2., 20.],
+///                [3., 30.]]);
+/// let v: Vec<Variance> = estimate_columns(a.view());
+/// assert_eq!(v[0].mean(), 2.);
+/// assert_eq!(v[1].mean(), 20.);
+/// # }
+/// ```
+pub fn estimate_columns<E>(a: ArrayView2<f64>) -> Vec<E>
+    where E: Estimate + Default
+{
+    let mut estimators: Vec<E> = (0..a.ncols()).map(|_| E::default()).collect();
+    for row in a.outer_iter() {
+        for (e, &x) in estimators.iter_mut().zip(row.iter()) {
+            e.add(x);
+        }
+    }
+    estimators
+}
+
+/// Estimate a statistic for each row of a two-dimensional array.
+///
+/// Each row is treated as a separate sample, so the result contains one
+/// estimator per row.
+///
+/// This requires the `ndarray` and `alloc` features.
+pub fn estimate_rows<E>(a: ArrayView2<f64>) -> Vec<E>
+    where E: Estimate + Default
+{
+    a.outer_iter()
+        .map(|row| {
+            let mut e = E::default();
+            for &x in row.iter() {
+                e.add(x);
+            }
+            e
+        })
+        .collect()
+}
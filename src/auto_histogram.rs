@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+use core::iter;
+
+use super::{Histogram, IterDynHistogram};
+
+/// A histogram with a fixed number of bins of constant width, whose range
+/// grows automatically to include all samples.
+///
+/// The histogram starts with a guessed range. When a sample falls outside of
+/// it, the range is doubled towards the sample, and each pair of adjacent bins
+/// is merged into one bin, so the number of bins and the total count are
+/// preserved. This is repeated until the sample is within the range. Each
+/// doubling takes O(n) operations for `n` bins, and the number of doublings
+/// only grows logarithmically with the span of the samples, so this is cheap
+/// after the range has settled.
+///
+/// Because of the rebinning, the bin boundaries shift over time and the
+/// resolution gets coarser with every expansion. Samples added before an
+/// expansion are only known up to the merged bins. If the range is known in
+/// advance, `DynHistogram` or `define_histogram!` should be used instead.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{AutoHistogram, Histogram};
+///
+/// let mut h = AutoHistogram::new(0., 4., 4);
+/// for &x in &[0.5, 1.5, 2.5, 3.5, 7.] {
+///     h.add(x).unwrap();
+/// }
+/// assert_eq!(h.range_max(), 8.);
+/// assert_eq!(h.bins(), &[2, 2, 0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AutoHistogram {
+    /// The ranges defining the bins of the histogram.
+    range: Vec<f64>,
+    /// The bins of the histogram.
+    bin: Vec<u64>,
+    /// Number of range expansions.
+    expansions: u32,
+}
+
+impl AutoHistogram {
+    /// Construct a histogram with `n` bins of constant width, initially
+    /// spanning from `start` to `end`.
+    ///
+    /// Panics if `n` is zero or odd, or if `start < end` does not hold for
+    /// finite `start` and `end` with a finite width `end - start`.
+    #[inline]
+    pub fn new(start: f64, end: f64, n: usize) -> AutoHistogram {
+        assert!(n > 0 && n % 2 == 0, "number of bins must be positive and even");
+        assert!(start.is_finite() && end.is_finite() && start < end
+                && (end - start).is_finite(),
+                "range must be finite and nonempty");
+        AutoHistogram {
+            range: uniform_range(start, end, n),
+            bin: iter::repeat(0).take(n).collect(),
+            expansions: 0,
+        }
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bin.len()
+    }
+
+    /// Determine whether no sample was added to the histogram.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bin.iter().all(|&b| b == 0)
+    }
+
+    /// Return the number of times the range was doubled.
+    #[inline]
+    pub fn expansions(&self) -> u32 {
+        self.expansions
+    }
+
+    /// Find the index of the bin corresponding to the given sample.
+    ///
+    /// Fails if the sample is out of the current range of the histogram.
+    #[inline]
+    pub fn find(&self, x: f64) -> Result<usize, ()> {
+        if !(self.range_min() <= x && x < self.range_max()) {
+            return Err(());
+        }
+        let len = self.bin.len();
+        match self.range.binary_search_by(|p| p.partial_cmp(&x).unwrap()) {
+            Ok(i) if i < len => Ok(i),
+            Err(i) if i > 0 && i < len + 1 => Ok(i - 1),
+            _ => Err(()),
+        }
+    }
+
+    /// Add a sample to the histogram, expanding the range if necessary.
+    ///
+    /// Fails if the sample is infinite or `nan`, or if the range would grow
+    /// beyond the finite numbers. Such samples are not counted, and the
+    /// histogram is left unchanged.
+    #[inline]
+    pub fn add(&mut self, x: f64) -> Result<(), ()> {
+        if !x.is_finite() {
+            return Err(());
+        }
+        // Determine the required expansions before changing anything, so
+        // that the histogram stays intact if the range would overflow.
+        let (mut start, mut end) = (self.range_min(), self.range_max());
+        let mut down = 0;
+        while x < start {
+            start -= end - start;
+            if !start.is_finite() || !(end - start).is_finite() {
+                return Err(());
+            }
+            down += 1;
+        }
+        let mut up = 0;
+        while x >= end {
+            end += end - start;
+            if !end.is_finite() || !(end - start).is_finite() {
+                return Err(());
+            }
+            up += 1;
+        }
+        for _ in 0..down {
+            self.expand_down();
+        }
+        for _ in 0..up {
+            self.expand_up();
+        }
+        let i = self.find(x)?;
+        self.bin[i] += 1;
+        Ok(())
+    }
+
+    /// Double the range towards larger values.
+    fn expand_up(&mut self) {
+        let (start, end) = (self.range_min(), self.range_max());
+        let n = self.bin.len();
+        for i in 0..n / 2 {
+            self.bin[i] = self.bin[2 * i] + self.bin[2 * i + 1];
+        }
+        for b in &mut self.bin[n / 2..] {
+            *b = 0;
+        }
+        self.range = uniform_range(start, end + (end - start), n);
+        self.expansions += 1;
+    }
+
+    /// Double the range towards smaller values.
+    fn expand_down(&mut self) {
+        let (start, end) = (self.range_min(), self.range_max());
+        let n = self.bin.len();
+        for i in (0..n / 2).rev() {
+            self.bin[n / 2 + i] = self.bin[2 * i] + self.bin[2 * i + 1];
+        }
+        for b in &mut self.bin[..n / 2] {
+            *b = 0;
+        }
+        self.range = uniform_range(start - (end - start), end, n);
+        self.expansions += 1;
+    }
+
+    /// Return the ranges of the histogram.
+    #[inline]
+    pub fn ranges(&self) -> &[f64] {
+        &self.range[..]
+    }
+
+    /// Return an iterator over the bins and corresponding ranges:
+    /// `((lower, upper), count)`
+    #[inline]
+    pub fn iter(&self) -> IterDynHistogram<'_> {
+        self.into_iter()
+    }
+
+    /// Reset all bins to zero.
+    ///
+    /// The current range is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        for b in &mut self.bin {
+            *b = 0;
+        }
+    }
+
+    /// Return the current lower range limit.
+    #[inline]
+    pub fn range_min(&self) -> f64 {
+        self.range[0]
+    }
+
+    /// Return the current upper range limit.
+    #[inline]
+    pub fn range_max(&self) -> f64 {
+        self.range[self.range.len() - 1]
+    }
+}
+
+/// Calculate the edges of `n` bins of constant width from `start` to `end`.
+fn uniform_range(start: f64, end: f64, n: usize) -> Vec<f64> {
+    let step = (end - start) / (n as f64);
+    let mut range: Vec<f64> = (0..n).map(|i| start + step * (i as f64)).collect();
+    // Make sure the upper limit is exact, so that doubling does not drift.
+    range.push(end);
+    range
+}
+
+impl<'a> IntoIterator for &'a AutoHistogram {
+    type Item = ((f64, f64), u64);
+    type IntoIter = IterDynHistogram<'a>;
+
+    #[inline]
+    fn into_iter(self) -> IterDynHistogram<'a> {
+        IterDynHistogram::new(&self.bin[..], &self.range[..])
+    }
+}
+
+impl Histogram for AutoHistogram {
+    #[inline]
+    fn bins(&self) -> &[u64] {
+        &self.bin[..]
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use num_traits::ToPrimitive;
+
+use super::{CoVariance, Variance};
+
+/// Estimate the autocovariance and autocorrelation of a time series
+/// ("population") for all lags up to a maximum lag.
+///
+/// The last `max_lag` samples are kept in a ring buffer, and for each lag `k`
+/// the pairs `(x_t, x_{t-k})` are fed into a covariance estimator. This
+/// requires O(`max_lag`) memory and each `add` takes O(`max_lag`) operations.
+///
+/// During the warm-up period, there are no pairs for lag `k` before `k + 1`
+/// samples were added, and the estimates for lag `k` are only meaningful after
+/// a lot more samples than `k` were added.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::AutoCovariance;
+///
+/// let mut a = AutoCovariance::new(2);
+/// for &x in &[1., 3., 2., 4., 3., 5.] {
+///     a.add(x);
+/// }
+/// println!("The lag-1 autocorrelation is {}.", a.autocorrelation(1));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AutoCovariance {
+    /// Ring buffer of the last `max_lag` samples.
+    buf: Vec<f64>,
+    /// Estimator of the variance of all samples, i.e. for lag 0.
+    variance: Variance,
+    /// Estimators of the covariance of the pairs for the lags `1..=max_lag`.
+    lags: Vec<CoVariance>,
+}
+
+impl AutoCovariance {
+    /// Create a new autocovariance estimator for lags up to `max_lag`.
+    ///
+    /// Panics if `max_lag` is zero.
+    #[inline]
+    pub fn new(max_lag: usize) -> AutoCovariance {
+        assert!(max_lag > 0, "maximal lag must be positive");
+        AutoCovariance {
+            buf: Vec::with_capacity(max_lag),
+            variance: Variance::new(),
+            lags: (0..max_lag).map(|_| CoVariance::new()).collect(),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The maximal lag is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = AutoCovariance::new(self.max_lag());
+    }
+
+    /// Return the maximal lag.
+    #[inline]
+    pub fn max_lag(&self) -> usize {
+        self.lags.len()
+    }
+
+    /// Add the next observation of the time series.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        let max_lag = self.max_lag();
+        let t = self.variance.len().to_usize().unwrap();
+        for (i, cov) in self.lags.iter_mut().take(self.buf.len()).enumerate() {
+            let lag = i + 1;
+            cov.add(x, self.buf[(t - lag) % max_lag]);
+        }
+        if self.buf.len() < max_lag {
+            self.buf.push(x);
+        } else {
+            self.buf[t % max_lag] = x;
+        }
+        self.variance.add(x);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.variance.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.variance.len()
+    }
+
+    /// Estimate the mean of the time series.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.variance.mean()
+    }
+
+    /// Estimate the autocovariance for the given lag.
+    ///
+    /// This is the sample covariance of the pairs `(x_t, x_{t-lag})`, for lag
+    /// 0 it is the sample variance. Returns 0 if there are less than two pairs.
+    ///
+    /// Panics if `lag` is larger than the maximal lag.
+    #[inline]
+    pub fn autocovariance(&self, lag: usize) -> f64 {
+        assert!(lag <= self.max_lag(), "lag is larger than the maximal lag");
+        if lag == 0 {
+            return self.variance.sample_variance();
+        }
+        self.lags[lag - 1].sample_covariance()
+    }
+
+    /// Estimate the autocorrelation for the given lag.
+    ///
+    /// This is the Pearson correlation coefficient of the pairs
+    /// `(x_t, x_{t-lag})`, for lag 0 it is 1. Returns 0 if there are less than
+    /// two pairs or if one of their variances is zero.
+    ///
+    /// Panics if `lag` is larger than the maximal lag.
+    #[inline]
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        assert!(lag <= self.max_lag(), "lag is larger than the maximal lag");
+        if lag == 0 {
+            return 1.;
+        }
+        self.lags[lag - 1].pearson()
+    }
+}
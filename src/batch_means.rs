@@ -0,0 +1,139 @@
+use super::{Estimate, Mean, MeanWithError};
+
+/// Estimate the arithmetic mean of a correlated sequence of numbers
+/// ("population") and its standard error using the method of batch means.
+///
+/// For autocorrelated data, like time series or Markov chain Monte Carlo
+/// samples, the standard error estimated by `MeanWithError` is too small,
+/// because it assumes independent samples. Here, the sequence is partitioned
+/// into consecutive batches of a fixed size, and the standard error is
+/// estimated from the variance of the batch means. If the batches are much
+/// longer than the autocorrelation time, the batch means are approximately
+/// independent, and the estimate is consistent.
+///
+/// Only completed batches contribute to the standard error, while all
+/// samples contribute to the mean.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::BatchMeansMean;
+///
+/// let mut a = BatchMeansMean::new(2);
+/// for &x in &[1., 2., 3., 4., 5., 6.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.mean(), 3.5);
+/// assert_eq!(a.num_batches(), 3);
+/// println!("The mean is {} ± {}.", a.mean(), a.standard_error());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BatchMeansMean {
+    /// Number of samples per batch.
+    batch_size: u64,
+    /// Estimator of the mean of all samples.
+    avg: Mean,
+    /// Estimator of the mean of the current, incomplete batch.
+    batch: Mean,
+    /// Estimator of the mean and the variance of the completed batch means.
+    batches: MeanWithError,
+}
+
+impl BatchMeansMean {
+    /// Create a new batch means estimator with the given number of samples
+    /// per batch.
+    ///
+    /// Panics if `batch_size` is zero.
+    #[inline]
+    pub fn new(batch_size: u64) -> BatchMeansMean {
+        assert!(batch_size > 0, "batch size must be positive");
+        BatchMeansMean {
+            batch_size,
+            avg: Mean::new(),
+            batch: Mean::new(),
+            batches: MeanWithError::new(),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The batch size is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = BatchMeansMean::new(self.batch_size);
+    }
+
+    /// Return the number of samples per batch.
+    #[inline]
+    pub fn batch_size(&self) -> u64 {
+        self.batch_size
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.avg.add(x);
+        self.batch.add(x);
+        if self.batch.len() == self.batch_size {
+            self.batches.add(self.batch.mean());
+            self.batch.reset();
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the number of completed batches.
+    #[inline]
+    pub fn num_batches(&self) -> u64 {
+        self.batches.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    ///
+    /// This is `sqrt(s² / k)`, where `s²` is the sample variance of the means
+    /// of the `k` completed batches. Returns 0 if less than two batches were
+    /// completed.
+    #[inline]
+    pub fn standard_error(&self) -> f64 {
+        self.batches.error()
+    }
+
+    /// Return the estimator of the mean and the variance of the completed
+    /// batch means.
+    #[inline]
+    pub fn batch_means(&self) -> &MeanWithError {
+        &self.batches
+    }
+}
+
+impl Estimate for BatchMeansMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
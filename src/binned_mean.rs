@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+use num_traits::ToPrimitive;
+
+use super::{MeanWithError, Merge};
+
+/// Estimate the mean of `y` for each bin of `x` of a sequence of pairs
+/// ("population").
+///
+/// This is sometimes called a profile histogram: The `x` values are sorted
+/// into bins like by a histogram, but instead of only counting them, the
+/// mean of the associated `y` values and its standard error are estimated
+/// for each bin, like by `TProfile` in ROOT. This summarizes how `y` depends
+/// on `x`.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::BinnedMean;
+///
+/// let mut a = BinnedMean::with_uniform_bins(0., 2., 2);
+/// for &(x, y) in &[(0.2, 1.), (0.7, 3.), (1.5, 10.)] {
+///     a.add(x, y).unwrap();
+/// }
+/// assert_eq!(a.bin_mean(0), 2.);
+/// assert_eq!(a.bin_count(0), 2);
+/// assert_eq!(a.bin_mean(1), 10.);
+/// println!("The mean of the first bin is {} ± {}.", a.bin_mean(0), a.bin_error(0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BinnedMean {
+    /// The ranges defining the bins of `x`.
+    range: Vec<f64>,
+    /// The estimators of the mean of `y` and its error for each bin.
+    bin: Vec<MeanWithError>,
+}
+
+impl BinnedMean {
+    /// Construct an estimator with `n` bins of constant width.
+    ///
+    /// Panics if `n` is zero, or if `start < end` does not hold for finite
+    /// `start` and `end`.
+    #[inline]
+    pub fn with_uniform_bins(start: f64, end: f64, n: usize) -> BinnedMean {
+        assert!(n > 0, "number of bins must be positive");
+        assert!(start.is_finite() && end.is_finite() && start < end,
+                "range must be finite and nonempty");
+        let step = (end - start) / n.to_f64().unwrap();
+        let range = (0..n + 1).map(|i| start + step * i.to_f64().unwrap()).collect();
+        BinnedMean::with_range(range)
+    }
+
+    /// Construct an estimator from a slice of `n + 1` sorted bin edges
+    /// defining `n` bins.
+    ///
+    /// Fails if there are less than two edges, if they are not sorted or if
+    /// they contain `nan`.
+    #[inline]
+    pub fn with_edges(edges: &[f64]) -> Result<BinnedMean, ()> {
+        if edges.len() < 2 || edges.iter().any(|e| e.is_nan())
+            || edges.windows(2).any(|w| w[0] > w[1])
+        {
+            return Err(());
+        }
+        Ok(BinnedMean::with_range(edges.to_vec()))
+    }
+
+    /// Construct an empty estimator from valid ranges.
+    #[inline]
+    fn with_range(range: Vec<f64>) -> BinnedMean {
+        let bin = (1..range.len()).map(|_| MeanWithError::new()).collect();
+        BinnedMean { range, bin }
+    }
+
+    /// Reset all bins to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        for b in &mut self.bin {
+            b.reset();
+        }
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bin.len()
+    }
+
+    /// Determine whether no pair was added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bin.iter().all(|b| b.is_empty())
+    }
+
+    /// Return the ranges of the bins.
+    #[inline]
+    pub fn ranges(&self) -> &[f64] {
+        &self.range[..]
+    }
+
+    /// Find the index of the bin corresponding to the given `x`.
+    ///
+    /// Fails if `x` is out of range.
+    #[inline]
+    pub fn find(&self, x: f64) -> Result<usize, ()> {
+        if x.is_nan() {
+            return Err(());
+        }
+        let len = self.bin.len();
+        match self.range.binary_search_by(|p| p.partial_cmp(&x).unwrap()) {
+            Ok(i) if i < len => Ok(i),
+            Err(i) if i > 0 && i < len + 1 => Ok(i - 1),
+            _ => Err(()),
+        }
+    }
+
+    /// Add an observed pair `(x, y)` sampled from the population.
+    ///
+    /// Fails if `x` is out of range. Such pairs are not counted.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) -> Result<(), ()> {
+        let i = self.find(x)?;
+        self.bin[i].add(y);
+        Ok(())
+    }
+
+    /// Estimate the mean of `y` for the bin with the given index.
+    ///
+    /// Returns 0 for an empty bin. Panics if the index is out of bounds.
+    #[inline]
+    pub fn bin_mean(&self, i: usize) -> f64 {
+        self.bin[i].mean()
+    }
+
+    /// Estimate the standard error of the mean of `y` for the bin with the
+    /// given index.
+    ///
+    /// Returns 0 if there are less than two pairs in the bin. Panics if the
+    /// index is out of bounds.
+    #[inline]
+    pub fn bin_error(&self, i: usize) -> f64 {
+        self.bin[i].error()
+    }
+
+    /// Return the number of pairs in the bin with the given index.
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn bin_count(&self, i: usize) -> u64 {
+        self.bin[i].len()
+    }
+
+    /// Return the estimator of the mean of `y` and its error for the bin with
+    /// the given index.
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn bin(&self, i: usize) -> &MeanWithError {
+        &self.bin[i]
+    }
+}
+
+impl Merge for BinnedMean {
+    /// Merge another sample into this one.
+    ///
+    /// Panics if the bin edges differ.
+    #[inline]
+    fn merge(&mut self, other: &BinnedMean) {
+        assert_eq!(self.range, other.range, "Both estimators must have the same ranges");
+        for (a, b) in self.bin.iter_mut().zip(&other.bin) {
+            a.merge(b);
+        }
+    }
+}
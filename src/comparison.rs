@@ -0,0 +1,111 @@
+//! Statistics comparing two independently estimated samples.
+
+use core;
+
+use num_traits::ToPrimitive;
+
+use super::Variance;
+
+/// Calculate Cohen's d, the standardized difference between the means of two
+/// samples.
+///
+/// This is `(a.mean() - b.mean()) / s`, where `s` is the pooled standard
+/// deviation
+///
+/// ```text
+/// s = sqrt(((n_a - 1) s_a² + (n_b - 1) s_b²) / (n_a + n_b - 2))
+/// ```
+///
+/// with the sample sizes `n_a`, `n_b` and the sample variances `s_a²`, `s_b²`.
+/// This assumes that both populations have the same variance.
+///
+/// If the pooled variance is zero (for example because all samples are equal
+/// or there are less than three samples in total), the result is 0 if the
+/// means are equal, and an infinity with the sign of the difference
+/// otherwise.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, cohens_d};
+///
+/// let a: Variance = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+/// let b: Variance = [1., 2., 3., 4., 5.].iter().collect();
+/// assert!((cohens_d(&a, &b) - 1.0235).abs() < 1e-4);
+/// ```
+pub fn cohens_d(a: &Variance, b: &Variance) -> f64 {
+    #[allow(unused_imports)]
+    use num_traits::Float;
+    let difference = a.mean() - b.mean();
+    let n = a.len() + b.len();
+    let sum_2 = a.sum_of_squares_centered() + b.sum_of_squares_centered();
+    let pooled_variance = if n > 2 && sum_2 > 0. {
+        sum_2 / (n - 2).to_f64().unwrap()
+    } else {
+        0.
+    };
+    if pooled_variance == 0. {
+        if difference == 0. {
+            return 0.;
+        }
+        return difference.signum() * core::f64::INFINITY;
+    }
+    difference / pooled_variance.sqrt()
+}
+
+/// Calculate Welch's t-statistic and the Welch–Satterthwaite degrees of
+/// freedom `(t, df)` for the difference between the means of two samples.
+///
+/// Unlike Student's t-test, this does not assume that both populations have
+/// the same variance. With the sample sizes `n_a`, `n_b` and the squared
+/// standard errors of the means `v_a = s_a² / n_a`, `v_b = s_b² / n_b`, the
+/// statistic and the degrees of freedom are
+///
+/// ```text
+/// t = (a.mean() - b.mean()) / sqrt(v_a + v_b)
+/// df = (v_a + v_b)² / (v_a² / (n_a - 1) + v_b² / (n_b - 1))
+/// ```
+///
+/// Under the hypothesis that both populations have the same mean, `t`
+/// approximately follows Student's t-distribution with `df` degrees of
+/// freedom, assuming the populations are normally distributed.
+///
+/// Returns `(0, 0)` if one of the samples has less than two elements. If both
+/// sample variances are zero, `t` is handled like in `cohens_d` and `df` is
+/// `n_a + n_b - 2`.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, welch_t};
+///
+/// let a: Variance = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+/// let b: Variance = [1., 2., 3., 4., 5.].iter().collect();
+/// let (t, df) = welch_t(&a, &b);
+/// assert!((t - 1.932).abs() < 1e-3);
+/// assert!((df - 10.52).abs() < 1e-2);
+/// ```
+pub fn welch_t(a: &Variance, b: &Variance) -> (f64, f64) {
+    #[allow(unused_imports)]
+    use num_traits::Float;
+    let (n_a, n_b) = (a.len(), b.len());
+    if n_a < 2 || n_b < 2 {
+        return (0., 0.);
+    }
+    let difference = a.mean() - b.mean();
+    let v_a = a.sample_variance() / n_a.to_f64().unwrap();
+    let v_b = b.sample_variance() / n_b.to_f64().unwrap();
+    let v = v_a + v_b;
+    if v == 0. {
+        let df = (n_a + n_b - 2).to_f64().unwrap();
+        if difference == 0. {
+            return (0., df);
+        }
+        return (difference.signum() * core::f64::INFINITY, df);
+    }
+    let df = v * v / (v_a * v_a / (n_a - 1).to_f64().unwrap()
+                      + v_b * v_b / (n_b - 1).to_f64().unwrap());
+    (difference / v.sqrt(), df)
+}
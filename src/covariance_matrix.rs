@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+
+use num_traits::ToPrimitive;
+
+use super::Merge;
+
+/// Estimate the arithmetic means and the covariance matrix of a sequence of
+/// vectors ("population").
+///
+/// The dimension `d` of the vectors is given at runtime. Only the upper
+/// triangle of the symmetric matrix is stored, so this needs about d²/2
+/// values of memory and each `add` takes O(d²) operations. Looking up a
+/// covariance or correlation takes O(1) operations, so the pairwise
+/// correlations of several series can be tracked after every step.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::CovarianceMatrix;
+///
+/// let mut a = CovarianceMatrix::new(3);
+/// a.add(&[1., 2., 3.]);
+/// a.add(&[2., 4., 1.]);
+/// a.add(&[3., 5., 2.]);
+/// println!("The covariance of the first two variables is {}.", a.covariance(0, 1));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CovarianceMatrix {
+    /// Dimension of the vectors.
+    dim: usize,
+    /// Sample size.
+    n: u64,
+    /// Mean values.
+    avg: Vec<f64>,
+    /// Intermediate sums of products for calculating the covariances, stored
+    /// as the packed upper triangle.
+    sum_2: Vec<f64>,
+}
+
+impl CovarianceMatrix {
+    /// Create a new covariance matrix estimator for vectors of the given
+    /// dimension.
+    ///
+    /// Panics if `dim` is zero.
+    #[inline]
+    pub fn new(dim: usize) -> CovarianceMatrix {
+        assert!(dim > 0, "dimension must be positive");
+        let mut avg = Vec::new();
+        avg.resize(dim, 0.);
+        let mut sum_2 = Vec::new();
+        sum_2.resize(dim * (dim + 1) / 2, 0.);
+        CovarianceMatrix { dim, n: 0, avg, sum_2 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The dimension is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.n = 0;
+        for x in &mut self.avg {
+            *x = 0.;
+        }
+        for x in &mut self.sum_2 {
+            *x = 0.;
+        }
+    }
+
+    /// Calculate the index into the packed upper triangle.
+    #[inline]
+    fn index(&self, i: usize, j: usize) -> usize {
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+        assert!(j < self.dim, "index out of bounds");
+        i * self.dim - i * (i + 1) / 2 + j
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// Panics if the length of `row` does not match the dimension.
+    #[inline]
+    pub fn add(&mut self, row: &[f64]) {
+        // This generalizes the algorithm introduced by Welford in 1962.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        assert_eq!(row.len(), self.dim, "row length must match the dimension");
+        self.n += 1;
+        let n = self.n.to_f64().unwrap();
+        let factor = (n - 1.) / n;
+        let mut k = 0;
+        for i in 0..self.dim {
+            let delta_i = row[i] - self.avg[i];
+            for (&x, &avg) in row[i..].iter().zip(&self.avg[i..]) {
+                self.sum_2[k] += delta_i * (x - avg) * factor;
+                k += 1;
+            }
+        }
+        for (avg, &x) in self.avg.iter_mut().zip(row) {
+            *avg += (x - *avg) / n;
+        }
+    }
+
+    /// Return the dimension of the vectors.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the mean of the `i`th variable.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self, i: usize) -> f64 {
+        self.avg[i]
+    }
+
+    /// Calculate the sample covariance of the `i`th and the `j`th variable.
+    ///
+    /// This is an unbiased estimator of the covariance of the population.
+    /// Returns 0 if the sample size is smaller than 2.
+    #[inline]
+    pub fn covariance(&self, i: usize, j: usize) -> f64 {
+        let index = self.index(i, j);
+        if self.n < 2 {
+            return 0.;
+        }
+        self.sum_2[index] / (self.n - 1).to_f64().unwrap()
+    }
+
+    /// Calculate the sample variance of the `i`th variable.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    /// Returns 0 if the sample size is smaller than 2.
+    #[inline]
+    pub fn variance(&self, i: usize) -> f64 {
+        self.covariance(i, i)
+    }
+
+    /// Calculate the sample Pearson correlation coefficient of the `i`th and
+    /// the `j`th variable.
+    ///
+    /// Returns 0 if the sample size is smaller than 2 or if one of the
+    /// variances is zero.
+    #[inline]
+    pub fn correlation(&self, i: usize, j: usize) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        let sum_2_ii = self.sum_2[self.index(i, i)];
+        let sum_2_jj = self.sum_2[self.index(j, j)];
+        if self.n < 2 || sum_2_ii == 0. || sum_2_jj == 0. {
+            return 0.;
+        }
+        let r = self.sum_2[self.index(i, j)] / (sum_2_ii.sqrt() * sum_2_jj.sqrt());
+        // Rounding errors must not result in a coefficient outside of [-1, 1].
+        r.max(-1.).min(1.)
+    }
+
+    /// Calculate the sample covariance matrix.
+    ///
+    /// The `d × d` matrix is returned in row-major order.
+    pub fn covariance_matrix(&self) -> Vec<f64> {
+        self.full_matrix(|i, j| self.covariance(i, j))
+    }
+
+    /// Calculate the matrix of the sample Pearson correlation coefficients.
+    ///
+    /// The `d × d` matrix is returned in row-major order. See `correlation`
+    /// for the handling of zero variances.
+    pub fn correlation_matrix(&self) -> Vec<f64> {
+        self.full_matrix(|i, j| self.correlation(i, j))
+    }
+
+    /// Build the full `d × d` matrix in row-major order.
+    fn full_matrix<F: Fn(usize, usize) -> f64>(&self, f: F) -> Vec<f64> {
+        let mut m = Vec::with_capacity(self.dim * self.dim);
+        for i in 0..self.dim {
+            for j in 0..self.dim {
+                m.push(f(i, j));
+            }
+        }
+        m
+    }
+}
+
+impl Merge for CovarianceMatrix {
+    /// Merge another sample into this one.
+    ///
+    /// Panics if the dimensions do not match.
+    #[inline]
+    fn merge(&mut self, other: &CovarianceMatrix) {
+        // This algorithm was proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        assert_eq!(self.dim, other.dim, "dimensions must match");
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        let len_self = self.n.to_f64().unwrap();
+        let len_other = other.n.to_f64().unwrap();
+        let len_total = len_self + len_other;
+        let factor = len_self * len_other / len_total;
+        let mut k = 0;
+        for i in 0..self.dim {
+            let delta_i = other.avg[i] - self.avg[i];
+            for (&other_avg, &avg) in other.avg[i..].iter().zip(&self.avg[i..]) {
+                self.sum_2[k] += other.sum_2[k] + delta_i * (other_avg - avg) * factor;
+                k += 1;
+            }
+        }
+        for (avg, &other_avg) in self.avg.iter_mut().zip(&other.avg) {
+            *avg = (len_self * *avg + len_other * other_avg) / len_total;
+        }
+        self.n += other.n;
+    }
+}
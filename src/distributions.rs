@@ -0,0 +1,106 @@
+//! Approximations of quantile functions of some distributions.
+//!
+//! These are only used internally, for calculating confidence intervals and
+//! similar statistics.
+
+use num_traits::ToPrimitive;
+
+/// Approximate the quantile function of the standard normal distribution.
+///
+/// The relative error is smaller than `1.2e-9`.
+// This uses the algorithm by Peter J. Acklam, see
+// https://web.archive.org/web/20151030215612/http://home.online.no/~pjacklam/notes/invnorm/.
+pub fn normal_quantile(p: f64) -> f64 {
+    #[allow(unused_imports)]
+    use num_traits::Float;
+    debug_assert!(0. < p && p < 1.);
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if !(P_LOW..=1. - P_LOW).contains(&p) {
+        // Rational approximation for the tails.
+        let q = (-2. * p.min(1. - p).ln()).sqrt();
+        let x = (((((C[0]*q + C[1])*q + C[2])*q + C[3])*q + C[4])*q + C[5])
+            / ((((D[0]*q + D[1])*q + D[2])*q + D[3])*q + 1.);
+        return if p < P_LOW { x } else { -x };
+    }
+    // Rational approximation for the central region.
+    let q = p - 0.5;
+    let r = q*q;
+    (((((A[0]*r + A[1])*r + A[2])*r + A[3])*r + A[4])*r + A[5])*q
+        / (((((B[0]*r + B[1])*r + B[2])*r + B[3])*r + B[4])*r + 1.)
+}
+
+/// Approximate the quantile function of Student's t-distribution with `df`
+/// degrees of freedom.
+///
+/// The result is exact for `df <= 2` and accurate to about six significant
+/// digits otherwise.
+// This uses the algorithm by G. W. Hill, "Algorithm 396: Student's
+// t-quantiles", Communications of the ACM 13 (1970).
+pub fn student_t_quantile(p: f64, df: u64) -> f64 {
+    debug_assert!(0. < p && p < 1.);
+    debug_assert!(df > 0);
+    if p == 0.5 {
+        return 0.;
+    }
+    // The algorithm calculates the quantile for the two-tailed probability.
+    let two_tailed = 2. * p.min(1. - p);
+    let t = student_t_two_tailed(two_tailed, df);
+    if p < 0.5 { -t } else { t }
+}
+
+/// Calculate the positive quantile of Student's t-distribution for the given
+/// two-tailed probability.
+fn student_t_two_tailed(p: f64, df: u64) -> f64 {
+    use core::f64::consts::{FRAC_PI_2, PI};
+    #[allow(unused_imports)]
+    use num_traits::Float;
+
+    if df == 1 {
+        let p = p * FRAC_PI_2;
+        return p.cos() / p.sin();
+    }
+    if df == 2 {
+        return (2. / (p * (2. - p)) - 2.).sqrt();
+    }
+
+    let n = df.to_f64().unwrap();
+    let a = 1. / (n - 0.5);
+    let b = 48. / (a*a);
+    let mut c = ((20700. * a / b - 98.) * a - 16.) * a + 96.36;
+    let d = ((94.5 / (b + c) - 3.) / b + 1.) * (a * PI / 2.).sqrt() * n;
+    let x = d * p;
+    let mut y = x.powf(2. / n);
+    if y > 0.05 + a {
+        // Asymptotic inverse expansion about the normal quantile.
+        let x = normal_quantile(0.5 * p);
+        y = x*x;
+        if df < 5 {
+            c += 0.3 * (n - 4.5) * (x + 0.6);
+        }
+        c += (((0.05 * d * x - 5.) * x - 7.) * x - 2.) * x + b;
+        y = (((((0.4 * y + 6.3) * y + 36.) * y + 94.5) / c - y - 3.) / b + 1.) * x;
+        y = a * y*y;
+        y = if y > 0.002 { y.exp() - 1. } else { 0.5 * y*y + y };
+    } else {
+        y = ((1. / (((n + 6.) / (n * y) - 0.089 * d - 0.822) * (n + 2.) * 3.)
+              + 0.5 / (n + 4.)) * y - 1.) * (n + 1.) / (n + 2.) + 1. / y;
+    }
+    (n * y).sqrt()
+}
@@ -0,0 +1,390 @@
+use alloc::vec::Vec;
+use core::iter;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use super::{Histogram, Merge, OutOfRange};
+use histogram::{quantile_from_bins, percentile_rank_from_bins,
+                cumulative_from_bins, cdf_from_bins};
+#[cfg(feature = "std")]
+use histogram::write_csv_from_bins;
+
+/// A histogram with a number of bins known at runtime.
+///
+/// This complements the histograms defined by `define_histogram!` for cases
+/// where the binning is only known at runtime. The bins are stored on the
+/// heap.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{DynHistogram, Histogram};
+///
+/// let n = 10;
+/// let mut h = DynHistogram::with_uniform_bins(0., 100., n);
+/// for i in 0..100 {
+///     h.add(i as f64).unwrap();
+/// }
+/// assert_eq!(h.bins(), &[10, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DynHistogram {
+    /// The ranges defining the bins of the histogram.
+    range: Vec<f64>,
+    /// The bins of the histogram.
+    bin: Vec<u64>,
+    /// The number of samples below the range of the histogram.
+    underflow: u64,
+    /// The number of samples above the range of the histogram.
+    overflow: u64,
+    /// How samples out of range are counted.
+    out_of_range: OutOfRange,
+}
+
+impl DynHistogram {
+    /// Construct a histogram with `n` bins of constant width.
+    ///
+    /// Panics if `n` is zero, or if `start < end` does not hold for finite
+    /// `start` and `end`.
+    #[inline]
+    pub fn with_uniform_bins(start: f64, end: f64, n: usize) -> DynHistogram {
+        assert!(n > 0, "number of bins must be positive");
+        assert!(start.is_finite() && end.is_finite() && start < end,
+                "range must be finite and nonempty");
+        let step = (end - start) / (n as f64);
+        let range = (0..n + 1).map(|i| start + step * (i as f64)).collect();
+        DynHistogram::with_range(range)
+    }
+
+    /// Construct a histogram with `n` logarithmically spaced bins.
+    ///
+    /// The bin edges are `start * (end / start)^(i / n)`, so all bins have the
+    /// same width on a logarithmic scale. This is useful for data spanning
+    /// several orders of magnitude.
+    ///
+    /// Panics if `n` is zero, if `start` is not positive, or if `start < end`
+    /// does not hold for finite `end`.
+    #[inline]
+    pub fn with_log_bins(start: f64, end: f64, n: usize) -> DynHistogram {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!(n > 0, "number of bins must be positive");
+        assert!(start > 0., "lower range limit must be positive");
+        assert!(start.is_finite() && end.is_finite() && start < end,
+                "range must be finite and nonempty");
+        let ratio = end / start;
+        let mut range: Vec<f64> = (0..n + 1)
+            .map(|i| start * ratio.powf((i as f64) / (n as f64)))
+            .collect();
+        // Make sure the upper limit is not affected by rounding errors.
+        range[n] = end;
+        DynHistogram::with_range(range)
+    }
+
+    /// Construct a histogram from a slice of `n + 1` sorted bin edges defining
+    /// `n` bins.
+    ///
+    /// This allows arbitrary, non-uniform bins. Like `from_ranges`, this fails
+    /// if there are less than two edges, if they are not sorted or if they
+    /// contain `nan`.
+    #[inline]
+    pub fn with_edges(edges: &[f64]) -> Result<DynHistogram, ()> {
+        DynHistogram::from_ranges(edges.iter().cloned())
+    }
+
+    /// Construct a histogram from given ranges.
+    ///
+    /// The ranges are given by an iterator of floats where neighboring pairs
+    /// `(a, b)` define a bin for all `x` where `a <= x < b`. The number of bins
+    /// is one less than the number of ranges.
+    ///
+    /// Fails if the iterator yields less than two ranges, is not sorted or
+    /// contains `nan`. `inf` and empty ranges are allowed.
+    #[inline]
+    pub fn from_ranges<T>(ranges: T) -> Result<DynHistogram, ()>
+        where T: IntoIterator<Item = f64>
+    {
+        let mut range: Vec<f64> = Vec::new();
+        for r in ranges {
+            if r.is_nan() {
+                return Err(());
+            }
+            if let Some(&last) = range.last() {
+                if last > r {
+                    return Err(());
+                }
+            }
+            range.push(r);
+        }
+        if range.len() < 2 {
+            return Err(());
+        }
+        Ok(DynHistogram::with_range(range))
+    }
+
+    /// Construct an empty histogram from valid ranges.
+    #[inline]
+    fn with_range(range: Vec<f64>) -> DynHistogram {
+        let bin = iter::repeat(0).take(range.len() - 1).collect();
+        DynHistogram { range, bin, underflow: 0, overflow: 0, out_of_range: OutOfRange::Separate }
+    }
+
+    /// Set how samples out of range are counted by `add`.
+    ///
+    /// By default, they are counted separately by `underflow` and `overflow`.
+    #[inline]
+    pub fn with_out_of_range(mut self, mode: OutOfRange) -> DynHistogram {
+        self.out_of_range = mode;
+        self
+    }
+
+    /// Return how samples out of range are counted by `add`.
+    #[inline]
+    pub fn out_of_range(&self) -> OutOfRange {
+        self.out_of_range
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bin.len()
+    }
+
+    /// Determine whether no sample was added to the histogram.
+    ///
+    /// This includes the samples out of range.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.underflow == 0 && self.overflow == 0 && self.bin.iter().all(|&b| b == 0)
+    }
+
+    /// Find the index of the bin corresponding to the given sample.
+    ///
+    /// Fails if the sample is out of range of the histogram.
+    #[inline]
+    pub fn find(&self, x: f64) -> Result<usize, ()> {
+        // We made sure our ranges are valid at construction, so we can
+        // safely unwrap.
+        let len = self.bin.len();
+        match self.range.binary_search_by(|p| p.partial_cmp(&x).unwrap()) {
+            Ok(i) if i < len => Ok(i),
+            Err(i) if i > 0 && i < len + 1 => Ok(i - 1),
+            _ => Err(()),
+        }
+    }
+
+    /// Add a sample to the histogram.
+    ///
+    /// Fails if the sample is out of range of the histogram. Such samples are
+    /// counted by `underflow` and `overflow`, unless the histogram was
+    /// configured with `OutOfRange::Clamp`. Then they are counted by the first
+    /// or the last bin instead.
+    #[inline]
+    pub fn add(&mut self, x: f64) -> Result<(), ()> {
+        if let Ok(i) = self.find(x) {
+            self.bin[i] += 1;
+            Ok(())
+        } else if self.out_of_range == OutOfRange::Clamp {
+            if x < self.range_min() {
+                self.bin[0] += 1;
+            } else {
+                let last = self.bin.len() - 1;
+                self.bin[last] += 1;
+            }
+            Ok(())
+        } else {
+            if x < self.range_min() {
+                self.underflow += 1;
+            } else {
+                self.overflow += 1;
+            }
+            Err(())
+        }
+    }
+
+    /// Return the number of samples that were smaller than the lower range
+    /// limit.
+    #[inline]
+    pub fn underflow(&self) -> u64 {
+        self.underflow
+    }
+
+    /// Return the number of samples that were larger than or equal to the
+    /// upper range limit.
+    #[inline]
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// Return the ranges of the histogram.
+    #[inline]
+    pub fn ranges(&self) -> &[f64] {
+        &self.range[..]
+    }
+
+    /// Return an iterator over the bins and corresponding ranges:
+    /// `((lower, upper), count)`
+    ///
+    /// This does not include the samples out of range.
+    #[inline]
+    pub fn iter(&self) -> IterDynHistogram<'_> {
+        self.into_iter()
+    }
+
+    /// Reset all bins to zero.
+    #[inline]
+    pub fn reset(&mut self) {
+        for b in &mut self.bin {
+            *b = 0;
+        }
+        self.underflow = 0;
+        self.overflow = 0;
+    }
+
+    /// Return the lower range limit.
+    ///
+    /// (The corresponding bin might be empty.)
+    #[inline]
+    pub fn range_min(&self) -> f64 {
+        self.range[0]
+    }
+
+    /// Return the upper range limit.
+    ///
+    /// (The corresponding bin might be empty.)
+    #[inline]
+    pub fn range_max(&self) -> f64 {
+        self.range[self.bin.len()]
+    }
+
+    /// Estimate the `p`-quantile of the samples.
+    ///
+    /// This works like `quantile` for the histograms defined by
+    /// `define_histogram!`: The result is linearly interpolated within the bin
+    /// containing the quantile, and the samples out of range are taken into
+    /// account as if they were located at the corresponding range limit.
+    ///
+    /// Returns 0 for an empty histogram. Panics if `p` is not in [0, 1].
+    pub fn quantile(&self, p: f64) -> f64 {
+        quantile_from_bins(&self.range, &self.bin, self.underflow, self.overflow, p)
+    }
+
+    /// Estimate the fraction of the samples that are smaller than or equal to
+    /// `x`.
+    ///
+    /// This is the inverse of `quantile` and works like `percentile_rank` for
+    /// the histograms defined by `define_histogram!`.
+    ///
+    /// Returns 0 if `x` is below the range or if the histogram is empty, and 1
+    /// if `x` is above the range.
+    pub fn percentile_rank(&self, x: f64) -> f64 {
+        percentile_rank_from_bins(&self.range, &self.bin, self.underflow, self.overflow, x)
+    }
+
+    /// Return the cumulative counts of the bins, from left to right, starting
+    /// from the samples below the range.
+    ///
+    /// Unlike `Histogram::cumulative`, the first value includes the underflow
+    /// count, so the last value is the number of samples below the upper range
+    /// limit.
+    pub fn cumulative_with_underflow(&self) -> Vec<u64> {
+        cumulative_from_bins(&self.bin, self.underflow).collect()
+    }
+
+    /// Return the empirical cumulative distribution function at the upper
+    /// edges of the bins, taking the samples out of range into account.
+    ///
+    /// Unlike `Histogram::cdf`, this is normalized by the total number of
+    /// samples, including underflow and overflow, like `quantile` and
+    /// `percentile_rank`. The values agree with `percentile_rank` at the upper
+    /// bin edges, except at the upper range limit, where the last value is
+    /// smaller than 1 if there is overflow. Returns zeros for an empty
+    /// histogram.
+    pub fn cdf_with_out_of_range(&self) -> Vec<f64> {
+        cdf_from_bins(&self.bin, self.underflow, self.overflow).collect()
+    }
+
+    /// Write the histogram as CSV with the columns `bin_lower,bin_upper,count`.
+    ///
+    /// This works like `write_csv` for the histograms defined by
+    /// `define_histogram!`: After a header line, there is one row per bin, and
+    /// the samples out of range are written as additional rows from `-inf` to
+    /// `range_min` and from `range_max` to `inf`.
+    ///
+    /// This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn write_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_csv_from_bins(w, &self.range, &self.bin, self.underflow, self.overflow)
+    }
+}
+
+/// Iterate over all `(range, count)` pairs in a `DynHistogram` or an
+/// `AutoHistogram`.
+#[derive(Debug, Clone)]
+pub struct IterDynHistogram<'a> {
+    remaining_bin: &'a [u64],
+    remaining_range: &'a [f64],
+}
+
+impl<'a> IterDynHistogram<'a> {
+    /// Iterate over the given bins and the ranges defining them.
+    #[inline]
+    pub(crate) fn new(bin: &'a [u64], range: &'a [f64]) -> IterDynHistogram<'a> {
+        IterDynHistogram { remaining_bin: bin, remaining_range: range }
+    }
+}
+
+impl<'a> Iterator for IterDynHistogram<'a> {
+    type Item = ((f64, f64), u64);
+
+    #[inline]
+    fn next(&mut self) -> Option<((f64, f64), u64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_first() {
+            let left = self.remaining_range[0];
+            let right = self.remaining_range[1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[1..];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+}
+
+impl<'a> IntoIterator for &'a DynHistogram {
+    type Item = ((f64, f64), u64);
+    type IntoIter = IterDynHistogram<'a>;
+
+    #[inline]
+    fn into_iter(self) -> IterDynHistogram<'a> {
+        IterDynHistogram::new(&self.bin[..], &self.range[..])
+    }
+}
+
+impl Histogram for DynHistogram {
+    #[inline]
+    fn bins(&self) -> &[u64] {
+        &self.bin[..]
+    }
+}
+
+impl Merge for DynHistogram {
+    /// Merge another histogram into this one.
+    ///
+    /// The bin counts and the underflow and overflow counters are added.
+    /// Panics if the bin edges of the histograms differ.
+    #[inline]
+    fn merge(&mut self, other: &DynHistogram) {
+        assert_eq!(self.range, other.range, "Both histograms must have the same ranges");
+        assert_eq!(self.out_of_range, other.out_of_range,
+                   "Both histograms must have the same out-of-range mode");
+        for (a, b) in self.bin.iter_mut().zip(&other.bin) {
+            *a += *b;
+        }
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+    }
+}
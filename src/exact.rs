@@ -0,0 +1,59 @@
+//! Two-pass algorithms for data that is already in memory.
+
+use num_traits::ToPrimitive;
+
+/// Calculate the arithmetic mean of a slice using two passes.
+///
+/// The second pass corrects the rounding error of the naive sum, so this is
+/// more accurate than the iterative estimators, at the cost of having to store
+/// the data. Returns 0 for an empty slice.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::exact_mean;
+///
+/// assert_eq!(exact_mean(&[1., 2., 3., 4., 5.]), 3.);
+/// ```
+pub fn exact_mean(xs: &[f64]) -> f64 {
+    if xs.is_empty() {
+        return 0.;
+    }
+    let n = xs.len().to_f64().unwrap();
+    let mean = xs.iter().sum::<f64>() / n;
+    let correction = xs.iter().map(|&x| x - mean).sum::<f64>() / n;
+    mean + correction
+}
+
+/// Calculate the sample variance of a slice using two passes.
+///
+/// The mean is calculated first, then the sum of the squared deviations from
+/// it, which avoids the cancellation of the one-pass algorithms. This uses the
+/// corrected two-pass algorithm by Björck, giving the most accurate result for
+/// data that is already in memory.
+///
+/// This is an unbiased estimator of the variance of the population, like
+/// `Variance::sample_variance`. Returns 0 if the slice has less than two
+/// elements.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::exact_variance;
+///
+/// assert_eq!(exact_variance(&[1., 2., 3., 4., 5.]), 2.5);
+/// ```
+pub fn exact_variance(xs: &[f64]) -> f64 {
+    if xs.len() < 2 {
+        return 0.;
+    }
+    let n = xs.len().to_f64().unwrap();
+    let mean = xs.iter().sum::<f64>() / n;
+    let (sum, sum_2) = xs.iter().fold((0., 0.), |(sum, sum_2), &x| {
+        let delta = x - mean;
+        (sum + delta, sum_2 + delta * delta)
+    });
+    (sum_2 - sum * sum / n) / (n - 1.)
+}
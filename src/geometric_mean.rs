@@ -0,0 +1,101 @@
+use core;
+
+use super::{Mean, Estimate, Merge};
+
+/// Estimate the geometric mean of a sequence of numbers ("population").
+///
+/// The geometric mean is calculated as the exponential of the arithmetic mean
+/// of the logarithms of the observations. The observations are expected to be
+/// positive. If any observation is zero, negative or `nan`, the estimate is
+/// `nan`, independently of the order of the observations and of merging.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::GeometricMean;
+///
+/// let a: GeometricMean = [1., 2., 4.].iter().collect();
+/// println!("The geometric mean is {}.", a.geometric_mean());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GeometricMean {
+    /// Estimator of the mean of the logarithms.
+    log_avg: Mean,
+}
+
+impl GeometricMean {
+    /// Create a new geometric mean estimator.
+    #[inline]
+    pub fn new() -> GeometricMean {
+        GeometricMean { log_avg: Mean::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = GeometricMean::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.log_avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.log_avg.len()
+    }
+
+    /// Estimate the geometric mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn geometric_mean(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        if self.is_empty() {
+            return 0.;
+        }
+        self.log_avg.mean().exp()
+    }
+}
+
+impl core::default::Default for GeometricMean {
+    fn default() -> GeometricMean {
+        GeometricMean::new()
+    }
+}
+
+impl Estimate for GeometricMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        // `ln(0) = -inf` would result in 0 or `nan`, depending on whether more
+        // observations follow, so all non-positive observations are mapped to
+        // `nan`.
+        self.log_avg.add(if x > 0. { x.ln() } else { core::f64::NAN });
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.geometric_mean()
+    }
+}
+
+impl Merge for GeometricMean {
+    /// Merge another sample into this one.
+    ///
+    /// If either sample contains a non-positive observation, the merged
+    /// estimate is `nan`.
+    #[inline]
+    fn merge(&mut self, other: &GeometricMean) {
+        self.log_avg.merge(&other.log_avg);
+    }
+}
+
+impl_from_iterator!(GeometricMean);
@@ -0,0 +1,97 @@
+use core;
+
+use super::{Mean, Estimate, Merge};
+
+/// Estimate the harmonic mean of a sequence of numbers ("population").
+///
+/// The harmonic mean is calculated as the reciprocal of the arithmetic mean of
+/// the reciprocals of the observations. The observations are expected to be
+/// positive. If any observation is zero, negative or `nan`, the estimate is
+/// `nan`, independently of the order of the observations and of merging.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::HarmonicMean;
+///
+/// let a: HarmonicMean = [1., 2., 4.].iter().collect();
+/// println!("The harmonic mean is {}.", a.harmonic_mean());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HarmonicMean {
+    /// Estimator of the mean of the reciprocals.
+    inv_avg: Mean,
+}
+
+impl HarmonicMean {
+    /// Create a new harmonic mean estimator.
+    #[inline]
+    pub fn new() -> HarmonicMean {
+        HarmonicMean { inv_avg: Mean::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = HarmonicMean::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inv_avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.inv_avg.len()
+    }
+
+    /// Estimate the harmonic mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn harmonic_mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        1. / self.inv_avg.mean()
+    }
+}
+
+impl core::default::Default for HarmonicMean {
+    fn default() -> HarmonicMean {
+        HarmonicMean::new()
+    }
+}
+
+impl Estimate for HarmonicMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        // `1 / 0 = inf` would result in 0 or `nan`, depending on whether more
+        // observations follow, so all non-positive observations are mapped to
+        // `nan`.
+        self.inv_avg.add(if x > 0. { 1. / x } else { core::f64::NAN });
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.harmonic_mean()
+    }
+}
+
+impl Merge for HarmonicMean {
+    /// Merge another sample into this one.
+    ///
+    /// If either sample contains a non-positive observation, the merged
+    /// estimate is `nan`.
+    #[inline]
+    fn merge(&mut self, other: &HarmonicMean) {
+        self.inv_avg.merge(&other.inv_avg);
+    }
+}
+
+impl_from_iterator!(HarmonicMean);
@@ -1,3 +1,165 @@
+/// How a histogram handles samples outside of its range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum OutOfRange {
+    /// Count the samples below the range as underflow and the samples above
+    /// the range as overflow, separately from the bins.
+    ///
+    /// This is the default.
+    Separate,
+    /// Count the samples below the range in the first bin and the samples
+    /// above the range in the last bin.
+    Clamp,
+}
+
+impl Default for OutOfRange {
+    fn default() -> OutOfRange {
+        OutOfRange::Separate
+    }
+}
+
+/// Estimate the `p`-quantile of a histogram given by its ranges, bins and
+/// out-of-range counts.
+///
+/// This implements `quantile` for all histograms and is not meant to be used
+/// directly.
+#[doc(hidden)]
+pub fn quantile_from_bins(range: &[f64], bin: &[u64], underflow: u64, overflow: u64,
+                          p: f64) -> f64 {
+    assert!((0. ..=1.).contains(&p), "p must be in [0, 1]");
+    let bins_total: u64 = bin.iter().sum();
+    let total = underflow + bins_total + overflow;
+    if total == 0 {
+        return 0.;
+    }
+    let target = p * total as f64;
+    let mut cumulative = underflow as f64;
+    if target <= cumulative {
+        return range[0];
+    }
+    for (r, &count) in range.windows(2).zip(bin) {
+        let (a, b) = (r[0], r[1]);
+        let count = count as f64;
+        if count > 0. && cumulative + count >= target {
+            return a + (target - cumulative) / count * (b - a);
+        }
+        cumulative += count;
+    }
+    range[bin.len()]
+}
+
+/// Estimate the fraction of the samples that are smaller than or equal to `x`
+/// for a histogram given by its ranges, bins and out-of-range counts.
+///
+/// This implements `percentile_rank` for all histograms and is not meant to be
+/// used directly.
+#[doc(hidden)]
+pub fn percentile_rank_from_bins(range: &[f64], bin: &[u64], underflow: u64, overflow: u64,
+                                 x: f64) -> f64 {
+    let bins_total: u64 = bin.iter().sum();
+    let total = underflow + bins_total + overflow;
+    if total == 0 || x < range[0] {
+        return 0.;
+    }
+    if x >= range[bin.len()] {
+        return 1.;
+    }
+    let mut cumulative = underflow as f64;
+    for (r, &count) in range.windows(2).zip(bin) {
+        let (a, b) = (r[0], r[1]);
+        let count = count as f64;
+        if x < b {
+            cumulative += (x - a) / (b - a) * count;
+            break;
+        }
+        cumulative += count;
+    }
+    cumulative / total as f64
+}
+
+/// Return the cumulative counts of the bins of a histogram, starting from the
+/// given underflow count.
+///
+/// This implements `cumulative_with_underflow` for all histograms and is not
+/// meant to be used directly.
+#[doc(hidden)]
+pub fn cumulative_from_bins<'a>(bin: &'a [u64], underflow: u64)
+    -> impl Iterator<Item = u64> + 'a
+{
+    bin.iter().scan(underflow, |sum, &count| {
+        *sum += count;
+        Some(*sum)
+    })
+}
+
+/// Return the cumulative distribution function at the upper edges of the bins
+/// of a histogram, taking the out-of-range counts into account.
+///
+/// This implements `cdf_with_out_of_range` for all histograms and is not meant
+/// to be used directly.
+#[doc(hidden)]
+pub fn cdf_from_bins<'a>(bin: &'a [u64], underflow: u64, overflow: u64)
+    -> impl Iterator<Item = f64> + 'a
+{
+    let bins_total: u64 = bin.iter().sum();
+    let total = underflow + bins_total + overflow;
+    let total_inv = if total == 0 { 0. } else { 1. / (total as f64) };
+    cumulative_from_bins(bin, underflow).map(move |c| (c as f64) * total_inv)
+}
+
+/// Write a histogram given by its ranges, bins and out-of-range counts as CSV.
+///
+/// This implements `write_csv` for all histograms and is not meant to be used
+/// directly.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn write_csv_from_bins<W: ::std::io::Write>(w: &mut W, range: &[f64], bin: &[u64],
+                                                underflow: u64, overflow: u64)
+    -> ::std::io::Result<()> {
+    writeln!(w, "bin_lower,bin_upper,count")?;
+    writeln!(w, "-inf,{},{}", range[0], underflow)?;
+    for (r, count) in range.windows(2).zip(bin) {
+        writeln!(w, "{},{},{}", r[0], r[1], count)?;
+    }
+    writeln!(w, "{},inf,{}", range[bin.len()], overflow)
+}
+
+/// Implement `write_csv` for a histogram defined by `define_histogram!`.
+///
+/// This is defined here depending on the `std` feature, so that the feature of
+/// this crate is checked instead of the feature of the crate invoking
+/// `define_histogram!`.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! impl_histogram_write_csv {
+    () => (
+        /// Write the histogram as CSV with the columns
+        /// `bin_lower,bin_upper,count`.
+        ///
+        /// After a header line, there is one row per bin. The samples out of
+        /// range are written as an additional first row from `-inf` to
+        /// `range_min` and an additional last row from `range_max` to `inf`.
+        ///
+        /// This requires the `std` feature.
+        pub fn write_csv<W: ::std::io::Write>(&self, w: &mut W)
+            -> ::std::io::Result<()> {
+            $crate::write_csv_from_bins(w, &self.range, &self.bin,
+                                        self.underflow, self.overflow)
+        }
+    );
+}
+
+/// Implement `write_csv` for a histogram defined by `define_histogram!`.
+///
+/// Without the `std` feature, there is nothing to implement.
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! impl_histogram_write_csv {
+    () => ();
+}
+
 /// Define a histogram with a number of bins known at compile time.
 ///
 /// Because macros are not hygenic for items, everything is defined in a private
@@ -6,6 +168,13 @@
 ///
 /// Note that you need to make sure that `core` is accessible to the macro.
 ///
+/// If the `serde1` feature is enabled in the crate invoking the macro, the
+/// histogram implements `Serialize` and `Deserialize`, including the bins,
+/// the ranges and the out-of-range counts. This requires `serde_derive` and
+/// `serde_big_array` to be accessible to the macro.
+///
+/// `write_csv` is available if the `std` feature of this crate is enabled.
+///
 ///
 /// # Example
 ///
@@ -25,6 +194,25 @@
 /// assert_eq!(h.bins(), &[10, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
 /// # }
 /// ```
+///
+/// With the `std` feature, the histogram can be written as CSV:
+///
+/// ```
+/// # extern crate core;
+/// # #[macro_use] extern crate average;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_derive;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_big_array;
+/// # fn main() {
+/// define_histogram!(hist, 2);
+/// let mut h = hist::Histogram::with_const_width(0., 2.);
+/// h.add(0.5).unwrap();
+/// h.add(3.).unwrap_err();
+/// let mut csv = Vec::new();
+/// h.write_csv(&mut csv).unwrap();
+/// assert_eq!(String::from_utf8(csv).unwrap(),
+///            "bin_lower,bin_upper,count\n-inf,0,0\n0,1,1\n1,2,0\n2,inf,1\n");
+/// # }
+/// ```
 #[macro_export]
 macro_rules! define_histogram {
     ($name:ident, $LEN:expr) => (
@@ -47,16 +235,27 @@ macro_rules! define_histogram {
                 /// The bins of the histogram.
                 #[cfg_attr(feature = "serde1", serde(with = "BigArray"))]
                 bin: [u64; LEN],
+                /// The number of samples below the range of the histogram.
+                #[cfg_attr(feature = "serde1", serde(default))]
+                underflow: u64,
+                /// The number of samples above the range of the histogram.
+                #[cfg_attr(feature = "serde1", serde(default))]
+                overflow: u64,
+                /// How samples out of range are counted.
+                #[cfg_attr(feature = "serde1", serde(default))]
+                out_of_range: $crate::OutOfRange,
             }
 
             impl ::core::fmt::Debug for Histogram {
                 fn fmt(&self, formatter: &mut ::core::fmt::Formatter)
                     -> ::core::fmt::Result {
-                    formatter.write_str("Histogram {{ range: ")?;
-                    self.range[..].fmt(formatter)?;
-                    formatter.write_str(", bins: ")?;
-                    self.bin[..].fmt(formatter)?;
-                    formatter.write_str(" }}")
+                    formatter.debug_struct("Histogram")
+                        .field("range", &&self.range[..])
+                        .field("bins", &&self.bin[..])
+                        .field("underflow", &self.underflow)
+                        .field("overflow", &self.overflow)
+                        .field("out_of_range", &self.out_of_range)
+                        .finish()
                 }
             }
 
@@ -73,6 +272,9 @@ macro_rules! define_histogram {
                     Self {
                         range,
                         bin: [0; LEN],
+                        underflow: 0,
+                        overflow: 0,
+                        out_of_range: $crate::OutOfRange::Separate,
                     }
                 }
 
@@ -109,9 +311,28 @@ macro_rules! define_histogram {
                     Ok(Self {
                         range,
                         bin: [0; LEN],
+                        underflow: 0,
+                        overflow: 0,
+                        out_of_range: $crate::OutOfRange::Separate,
                     })
                 }
 
+                /// Set how samples out of range are counted by `add`.
+                ///
+                /// By default, they are counted separately by `underflow` and
+                /// `overflow`.
+                #[inline]
+                pub fn with_out_of_range(mut self, mode: $crate::OutOfRange) -> Self {
+                    self.out_of_range = mode;
+                    self
+                }
+
+                /// Return how samples out of range are counted by `add`.
+                #[inline]
+                pub fn out_of_range(&self) -> $crate::OutOfRange {
+                    self.out_of_range
+                }
+
                 /// Find the index of the bin corresponding to the given sample.
                 ///
                 /// Fails if the sample is out of range of the histogram.
@@ -134,17 +355,50 @@ macro_rules! define_histogram {
 
                 /// Add a sample to the histogram.
                 ///
-                /// Fails if the sample is out of range of the histogram.
+                /// Fails if the sample is out of range of the histogram. Such
+                /// samples are counted by `underflow` and `overflow`, unless
+                /// the histogram was configured with `OutOfRange::Clamp`. Then
+                /// they are counted by the first or the last bin instead.
                 #[inline]
                 pub fn add(&mut self, x: f64) -> Result<(), ()> {
                     if let Ok(i) = self.find(x) {
                         self.bin[i] += 1;
                         Ok(())
+                    } else if self.out_of_range == $crate::OutOfRange::Clamp {
+                        if x < self.range_min() {
+                            self.bin[0] += 1;
+                        } else {
+                            self.bin[LEN - 1] += 1;
+                        }
+                        Ok(())
                     } else {
+                        if x < self.range_min() {
+                            self.underflow += 1;
+                        } else {
+                            self.overflow += 1;
+                        }
                         Err(())
                     }
                 }
 
+                /// Return the number of samples that were smaller than the
+                /// lower range limit.
+                ///
+                /// These are not counted by any bin.
+                #[inline]
+                pub fn underflow(&self) -> u64 {
+                    self.underflow
+                }
+
+                /// Return the number of samples that were larger than or equal
+                /// to the upper range limit.
+                ///
+                /// These are not counted by any bin.
+                #[inline]
+                pub fn overflow(&self) -> u64 {
+                    self.overflow
+                }
+
                 /// Return the ranges of the histogram.
                 #[inline]
                 pub fn ranges(&self) -> &[f64] {
@@ -153,8 +407,11 @@ macro_rules! define_histogram {
 
                 /// Return an iterator over the bins and corresponding ranges:
                 /// `((lower, upper), count)`
+                ///
+                /// This does not include the samples out of range, see
+                /// `underflow` and `overflow` for these.
                 #[inline]
-                pub fn iter(&self) -> IterHistogram {
+                pub fn iter(&self) -> IterHistogram<'_> {
                     self.into_iter()
                 }
 
@@ -162,6 +419,8 @@ macro_rules! define_histogram {
                 #[inline]
                 pub fn reset(&mut self) {
                     self.bin = [0; LEN];
+                    self.underflow = 0;
+                    self.overflow = 0;
                 }
 
                 /// Return the lower range limit.
@@ -179,6 +438,77 @@ macro_rules! define_histogram {
                 pub fn range_max(&self) -> f64 {
                     self.range[LEN]
                 }
+
+                /// Estimate the `p`-quantile of the samples.
+                ///
+                /// The cumulative counts are walked until the bin containing
+                /// the quantile is found, and the result is linearly
+                /// interpolated within that bin. The error is therefore
+                /// bounded by the width of that bin, assuming the samples are
+                /// distributed uniformly within each bin.
+                ///
+                /// Samples out of range are taken into account as if they were
+                /// located at the corresponding range limit, so quantiles
+                /// falling into the underflow or overflow are clamped to
+                /// `range_min` or `range_max`.
+                ///
+                /// Returns 0 for an empty histogram. Panics if `p` is not in
+                /// [0, 1].
+                pub fn quantile(&self, p: f64) -> f64 {
+                    $crate::quantile_from_bins(&self.range, &self.bin,
+                                               self.underflow, self.overflow, p)
+                }
+
+                /// Estimate the fraction of the samples that are smaller than
+                /// or equal to `x`.
+                ///
+                /// This is the inverse of `quantile`: The cumulative counts
+                /// are summed up to the bin containing `x`, and the count of
+                /// that bin is linearly interpolated. The samples out of range
+                /// are counted like in `quantile`.
+                ///
+                /// Returns 0 if `x` is below the range or if the histogram is
+                /// empty, and 1 if `x` is above the range.
+                pub fn percentile_rank(&self, x: f64) -> f64 {
+                    $crate::percentile_rank_from_bins(&self.range, &self.bin,
+                                                      self.underflow, self.overflow, x)
+                }
+
+                /// Return the cumulative counts of the bins, from left to
+                /// right, starting from the samples below the range.
+                ///
+                /// Unlike `Histogram::cumulative`, the first value includes
+                /// the underflow count, so the last value is the number of
+                /// samples below the upper range limit.
+                pub fn cumulative_with_underflow(&self) -> [u64; LEN] {
+                    let mut result = [0; LEN];
+                    let cumulative = $crate::cumulative_from_bins(&self.bin, self.underflow);
+                    for (r, c) in result.iter_mut().zip(cumulative) {
+                        *r = c;
+                    }
+                    result
+                }
+
+                /// Return the empirical cumulative distribution function at
+                /// the upper edges of the bins, taking the samples out of
+                /// range into account.
+                ///
+                /// Unlike `Histogram::cdf`, this is normalized by the total
+                /// number of samples, including underflow and overflow, like
+                /// `quantile` and `percentile_rank`. The values agree with
+                /// `percentile_rank` at the upper bin edges, except at the
+                /// upper range limit, where the last value is smaller than 1
+                /// if there is overflow. Returns zeros for an empty histogram.
+                pub fn cdf_with_out_of_range(&self) -> [f64; LEN] {
+                    let mut result = [0.; LEN];
+                    let cdf = $crate::cdf_from_bins(&self.bin, self.underflow, self.overflow);
+                    for (r, c) in result.iter_mut().zip(cdf) {
+                        *r = c;
+                    }
+                    result
+                }
+
+                $crate::impl_histogram_write_csv!();
             }
 
             /// Iterate over all `(range, count)` pairs in the histogram.
@@ -225,9 +555,13 @@ macro_rules! define_histogram {
                     for (a, b) in self.range.iter().zip(other.range.iter()) {
                         assert_eq!(a, b, "Both histograms must have the same ranges");
                     }
+                    assert_eq!(self.out_of_range, other.out_of_range,
+                               "Both histograms must have the same out-of-range mode");
                     for (x, y) in self.bin.iter_mut().zip(other.bin.iter()) {
                         *x += y;
                     }
+                    self.underflow += other.underflow;
+                    self.overflow += other.overflow;
                 }
             }
 
@@ -237,18 +571,28 @@ macro_rules! define_histogram {
                     for x in &mut self.bin[..] {
                         *x *= other;
                     }
+                    self.underflow *= other;
+                    self.overflow *= other;
                 }
             }
 
             impl $crate::Merge for Histogram {
+                /// Merge another histogram into this one.
+                ///
+                /// The bin counts and the underflow and overflow counters are
+                /// added. Panics if the bin edges of the histograms differ.
                 fn merge(&mut self, other: &Self) {
                     assert_eq!(self.bin.len(), other.bin.len());
                     for (a, b) in self.range.iter().zip(other.range.iter()) {
                         assert_eq!(a, b, "Both histograms must have the same ranges");
                     }
+                    assert_eq!(self.out_of_range, other.out_of_range,
+                               "Both histograms must have the same out-of-range mode");
                     for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
                         *a += *b;
                     }
+                    self.underflow += other.underflow;
+                    self.overflow += other.overflow;
                 }
             }
         }
@@ -0,0 +1,139 @@
+use core;
+use num_traits::ToPrimitive;
+
+use super::{Estimate, Merge};
+
+/// Add `x` to `sum` using Neumaier's variant of Kahan summation, updating the
+/// compensation term `c`.
+#[inline]
+pub fn compensated_add(sum: &mut f64, c: &mut f64, x: f64) {
+    let t = *sum + x;
+    if sum.abs() >= x.abs() {
+        *c += (*sum - t) + x;
+    } else {
+        *c += (x - t) + *sum;
+    }
+    *sum = t;
+}
+
+/// Estimate the arithmetic mean of a sequence of numbers ("population") using
+/// compensated summation.
+///
+/// This works like [`Mean`], but the updates of the running mean are
+/// accumulated using Neumaier's variant of Kahan summation. The estimator keeps
+/// an additional compensation term, which collects the rounding errors of the
+/// updates. This reduces the accumulated rounding error for long sequences,
+/// in particular if the magnitudes of the observations vary a lot. The cost
+/// is one more `f64` of state and a few more floating point operations per
+/// observation.
+///
+/// [`Mean`]: ./struct.Mean.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::KahanMean;
+///
+/// let a: KahanMean = (1..6).map(f64::from).collect();
+/// println!("The mean is {}.", a.mean());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct KahanMean {
+    /// Mean value, without the compensation.
+    avg: f64,
+    /// Compensation term collecting the rounding errors of `avg`.
+    compensation: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl KahanMean {
+    /// Create a new compensated mean estimator.
+    #[inline]
+    pub fn new() -> KahanMean {
+        KahanMean { avg: 0., compensation: 0., n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = KahanMean::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg + self.compensation
+    }
+}
+
+impl core::default::Default for KahanMean {
+    fn default() -> KahanMean {
+        KahanMean::new()
+    }
+}
+
+impl Estimate for KahanMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.n += 1;
+        let delta_n = (x - self.mean()) / self.n.to_f64().unwrap();
+        compensated_add(&mut self.avg, &mut self.compensation, delta_n);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for KahanMean {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{KahanMean, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let avg_total: KahanMean = sequence.iter().collect();
+    /// let mut avg_left: KahanMean = left.iter().collect();
+    /// let avg_right: KahanMean = right.iter().collect();
+    /// avg_left.merge(&avg_right);
+    /// assert_eq!(avg_total.mean(), avg_left.mean());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &KahanMean) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        self.n += other.n;
+        let weight = other.n.to_f64().unwrap() / self.n.to_f64().unwrap();
+        let delta = other.mean() - self.mean();
+        compensated_add(&mut self.avg, &mut self.compensation, delta * weight);
+    }
+}
+
+impl_from_iterator!(KahanMean);
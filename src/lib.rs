@@ -9,15 +9,38 @@
 //!    or `collect()`.
 //! 4. Calculate the statistic with `mean()` or similar.
 //!
+//! The most commonly used estimators, traits and macros can be imported at
+//! once with `use average::prelude::*`.
+//!
 //! You can run several estimators in parallel and merge them into one with
-//! `merge()`.
+//! `merge()`. [`merge_all`] merges many estimators pairwise for better
+//! accuracy. With the `rayon` feature, [`par_reduce`] does this for a
+//! parallel iterator. With the `ndarray` feature, [`estimate_columns`] and
+//! [`estimate_rows`] calculate a statistic for each column or row of a
+//! two-dimensional array.
 //!
 //! Everything is calculated iteratively in a single pass using constant memory,
 //! so the sequence of numbers can be an iterator. The used algorithms try to
-//! avoid numerical instabilities.
+//! avoid numerical instabilities. If the data is already in memory,
+//! [`exact_mean`] and [`exact_variance`] use two passes for more accuracy.
+//!
+//! Two independently estimated samples can be compared with [`cohens_d`] and
+//! [`welch_t`].
+//!
+//! [`Mean`] and [`Variance`] are generic over the floating point type (using
+//! `num_traits::Float`), so they can be used with `f32`. The type parameter
+//! defaults to `f64`, all other estimators only support `f64`.
 //!
 //! If you want [Serde](https://github.com/serde-rs/serde) support,
-//! include `"serde1"` in your list of features.
+//! include `"serde1"` in your list of features. All fields of the estimators
+//! are serialized in declaration order, without skipping or flattening, so
+//! compact binary formats like `postcard` or `bincode` can be used as well.
+//!
+//! The crate is `no_std`. By default, the `std` feature is enabled, which is
+//! only used for the floating point functions (like `sqrt`). Without it, the
+//! `libm` feature has to be enabled instead to provide these functions, so
+//! that the estimators can be used with just `core`. Estimators that need to
+//! allocate require the `alloc` feature, which is implied by `std`.
 //!
 //! Note that deserializing does not currently check for all invalid inputs.
 //! For example, if you deserialize a corrupted [`Variance`] it may return
@@ -39,13 +62,47 @@
 //! ## Estimators
 //!
 //! * Mean ([`Mean`]) and its error ([`MeanWithError`]).
+//! * Mean and its error for correlated samples using batch means
+//!   ([`BatchMeansMean`]).
+//! * Mean using compensated summation ([`KahanMean`]).
+//! * Sum using compensated summation ([`Sum`]).
+//! * Trimmed mean ([`TrimmedMean`]) and winsorized mean ([`WinsorizedMean`]),
+//!   which are robust against outliers, and a mean ignoring observations
+//!   outside of estimated quantiles ([`RobustMean`]).
 //! * Weighted mean ([`WeightedMean`]) and its error
 //!   ([`WeightedMeanWithError`]).
+//! * Geometric mean ([`GeometricMean`]) and harmonic mean ([`HarmonicMean`]).
+//! * Arithmetic, geometric and harmonic mean at once ([`AllMeans`]).
+//! * Covariance ([`CoVariance`]) and weighted covariance
+//!   ([`WeightedCoVariance`]) with reliability or frequency weights
+//!   ([`WeightKind`]).
+//! * Covariance of the logarithms of the variables ([`LogCoVariance`]).
+//! * Covariance matrix of several variables ([`CovarianceMatrix`]).
+//! * Mean and variance of the differences of paired samples ([`PairedMean`]).
+//! * Autocovariance and autocorrelation of a time series
+//!   ([`AutoCovariance`]).
 //! * Variance ([`Variance`]), skewness ([`Skewness`]) and kurtosis
-//!   ([`Kurtosis`]).
+//!   ([`Kurtosis`]), as well as weighted skewness ([`WeightedSkewness`]) and
+//!   kurtosis ([`WeightedKurtosis`]).
 //! * Arbitrary higher moments ([`define_moments`]).
-//! * Quantiles ([`Quantile`]).
-//! * Minimum ([`Min`]) and maximum ([`Max`]).
+//! * Mean absolute deviation, Gini mean difference and Gini coefficient
+//!   ([`MeanAbsoluteDeviation`]).
+//! * Quantiles ([`Quantile`], [`Quantiles`] and [`define_quantiles`]) and
+//!   quartiles ([`Quartiles`]), as well as weighted quantiles
+//!   ([`WeightedQuantile`]).
+//! * Quantiles with a selectable algorithm ([`QuantileEstimator`]).
+//! * Accurate quantiles across the whole range, especially the tails
+//!   ([`TDigest`]).
+//! * Mode of discrete data ([`Mode`]).
+//! * Minimum ([`Min`]) and maximum ([`Max`]), optionally with the index where
+//!   they occurred ([`IndexedMin`] and [`IndexedMax`]).
+//! * Minimum, maximum and range at once ([`Range`]).
+//! * Minimum ([`SlidingMin`]) and maximum ([`SlidingMax`]) over a sliding
+//!   window.
+//! * Exponentially weighted moving mean ([`ExpMovingMean`]) and variance
+//!   ([`ExpMovingVariance`]).
+//! * Mean over a sliding window ([`SlidingMean`] and [`define_sliding_mean`]).
+//! * Uniform random sample of the sequence ([`Reservoir`]).
 //!
 //!
 //! ## Estimating several statistics at once
@@ -69,28 +126,84 @@
 //! `define_histogram!(..., 10)`) and the extension trait [`Histogram`]
 //! for the methods available to the generated struct.
 //!
+//! If the number of bins is only known at runtime, [`DynHistogram`] can be
+//! used instead. It requires the `alloc` feature. If even the range is not
+//! known in advance, [`AutoHistogram`] expands its range as needed.
+//! [`BinnedMean`] estimates the mean of a second variable and its error for
+//! each bin (a profile histogram).
+//!
+//! By default, samples out of range are counted separately from the bins.
+//! With [`OutOfRange`], they can be counted by the edge bins instead.
+//!
 //!
 //! [`Mean`]: ./struct.Mean.html
 //! [`MeanWithError`]: ./type.MeanWithError.html
+//! [`KahanMean`]: ./struct.KahanMean.html
+//! [`RobustMean`]: ./struct.RobustMean.html
+//! [`BatchMeansMean`]: ./struct.BatchMeansMean.html
+//! [`Sum`]: ./struct.Sum.html
+//! [`TrimmedMean`]: ./struct.TrimmedMean.html
+//! [`WinsorizedMean`]: ./struct.WinsorizedMean.html
 //! [`WeightedMean`]: ./struct.WeightedMean.html
 //! [`WeightedMeanWithError`]: ./struct.WeightedMeanWithError.html
+//! [`GeometricMean`]: ./struct.GeometricMean.html
+//! [`HarmonicMean`]: ./struct.HarmonicMean.html
+//! [`AllMeans`]: ./struct.AllMeans.html
+//! [`CoVariance`]: ./struct.CoVariance.html
+//! [`WeightedCoVariance`]: ./struct.WeightedCoVariance.html
+//! [`WeightKind`]: ./enum.WeightKind.html
+//! [`LogCoVariance`]: ./struct.LogCoVariance.html
+//! [`CovarianceMatrix`]: ./struct.CovarianceMatrix.html
+//! [`PairedMean`]: ./struct.PairedMean.html
+//! [`AutoCovariance`]: ./struct.AutoCovariance.html
 //! [`Variance`]: ./struct.Variance.html
 //! [`Skewness`]: ./struct.Skewness.html
 //! [`Kurtosis`]: ./struct.Kurtosis.html
+//! [`WeightedSkewness`]: ./struct.WeightedSkewness.html
+//! [`MeanAbsoluteDeviation`]: ./struct.MeanAbsoluteDeviation.html
+//! [`WeightedKurtosis`]: ./struct.WeightedKurtosis.html
 //! [`Quantile`]: ./struct.Quantile.html
+//! [`Quartiles`]: ./struct.Quartiles.html
+//! [`WeightedQuantile`]: ./struct.WeightedQuantile.html
+//! [`QuantileEstimator`]: ./struct.QuantileEstimator.html
+//! [`TDigest`]: ./struct.TDigest.html
+//! [`Mode`]: ./struct.Mode.html
 //! [`Min`]: ./struct.Min.html
 //! [`Max`]: ./struct.Max.html
+//! [`Range`]: ./struct.Range.html
+//! [`IndexedMin`]: ./struct.IndexedMin.html
+//! [`IndexedMax`]: ./struct.IndexedMax.html
+//! [`SlidingMin`]: ./struct.SlidingMin.html
+//! [`SlidingMax`]: ./struct.SlidingMax.html
+//! [`SlidingMean`]: ./struct.SlidingMean.html
+//! [`ExpMovingMean`]: ./struct.ExpMovingMean.html
+//! [`ExpMovingVariance`]: ./struct.ExpMovingVariance.html
 //! [`concatenate`]: ./macro.concatenate.html
 //! [`define_moments`]: ./macro.define_moments.html
+//! [`define_quantiles`]: ./macro.define_quantiles.html
+//! [`Quantiles`]: ./struct.Quantiles.html
 //! [`define_histogram`]: ./macro.define_histogram.html
+//! [`define_sliding_mean`]: ./macro.define_sliding_mean.html
 //! [`Histogram10`]: ./struct.Histogram10.html
 //! [`Histogram`]: ./trait.Histogram.html
+//! [`OutOfRange`]: ./enum.OutOfRange.html
+//! [`DynHistogram`]: ./struct.DynHistogram.html
+//! [`AutoHistogram`]: ./struct.AutoHistogram.html
+//! [`BinnedMean`]: ./struct.BinnedMean.html
+//! [`merge_all`]: ./fn.merge_all.html
+//! [`par_reduce`]: ./fn.par_reduce.html
+//! [`exact_mean`]: ./fn.exact_mean.html
+//! [`exact_variance`]: ./fn.exact_variance.html
+//! [`cohens_d`]: ./fn.cohens_d.html
+//! [`welch_t`]: ./fn.welch_t.html
+//! [`Reservoir`]: ./struct.Reservoir.html
+//! [`estimate_columns`]: ./fn.estimate_columns.html
+//! [`estimate_rows`]: ./fn.estimate_rows.html
 
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+#![allow(clippy::float_cmp)]
 
 #![no_std]
 
-extern crate conv;
 extern crate float_ord;
 #[cfg(feature = "serde1")]
 extern crate serde;
@@ -99,20 +212,140 @@ extern crate serde;
 #[cfg(feature = "serde1")]
 #[macro_use] extern crate serde_big_array;
 extern crate num_traits;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rand_core")]
+extern crate rand_core;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature has to be enabled");
 
 #[macro_use] mod macros;
 #[macro_use] mod moments;
 mod weighted_mean;
+mod weighted_covariance;
+mod log_covariance;
+mod paired_mean;
+mod weighted_quantile;
+mod weighted_moments;
+#[cfg(feature = "alloc")]
+mod covariance_matrix;
+#[cfg(feature = "alloc")]
+mod autocovariance;
+#[cfg(feature = "alloc")]
+mod trimmed_mean;
+#[cfg(feature = "alloc")]
+mod quantile_method;
+#[cfg(feature = "alloc")]
+mod tdigest;
+#[cfg(feature = "alloc")]
+mod mode;
+#[cfg(feature = "alloc")]
+mod mean_absolute_deviation;
+mod kahan_mean;
+mod batch_means;
+mod sum;
+mod geometric_mean;
+mod harmonic_mean;
+mod all_means;
 mod minmax;
-mod quantile;
+#[cfg(feature = "alloc")]
+mod sliding_minmax;
+#[cfg(feature = "alloc")]
+mod sliding_mean;
+#[macro_use] mod quantile;
+#[cfg(feature = "alloc")]
+mod quantiles;
+mod robust_mean;
+#[macro_use] mod moving;
 mod traits;
+mod distributions;
+mod exact;
+mod comparison;
 #[macro_use] mod histogram;
+#[cfg(feature = "alloc")]
+mod dyn_histogram;
+#[cfg(feature = "alloc")]
+mod auto_histogram;
+#[cfg(feature = "alloc")]
+mod binned_mean;
+#[cfg(feature = "alloc")]
+mod merge_all;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(all(feature = "alloc", feature = "rand_core"))]
+mod reservoir;
+#[cfg(all(feature = "alloc", feature = "ndarray"))]
+mod array;
+pub mod prelude;
 
 pub use moments::{Mean, Variance, CoVariance, Skewness, Kurtosis, MeanWithError};
 pub use weighted_mean::{WeightedMean, WeightedMeanWithError};
-pub use minmax::{Min, Max};
-pub use quantile::Quantile;
+pub use weighted_covariance::{WeightedCoVariance, WeightKind};
+pub use log_covariance::LogCoVariance;
+pub use paired_mean::PairedMean;
+pub use weighted_moments::{WeightedSkewness, WeightedKurtosis};
+#[cfg(feature = "alloc")]
+pub use covariance_matrix::CovarianceMatrix;
+#[cfg(feature = "alloc")]
+pub use autocovariance::AutoCovariance;
+#[cfg(feature = "alloc")]
+pub use trimmed_mean::{TrimmedMean, WinsorizedMean};
+pub use kahan_mean::KahanMean;
+pub use batch_means::BatchMeansMean;
+pub use sum::Sum;
+pub use geometric_mean::GeometricMean;
+pub use harmonic_mean::HarmonicMean;
+pub use all_means::AllMeans;
+pub use minmax::{Min, Max, Range, IndexedMin, IndexedMax};
+#[cfg(feature = "alloc")]
+pub use sliding_minmax::{SlidingMin, SlidingMax};
+#[cfg(feature = "alloc")]
+pub use sliding_mean::SlidingMean;
+pub use quantile::{Quantile, Quartiles};
+#[cfg(feature = "alloc")]
+pub use quantiles::Quantiles;
+pub use robust_mean::RobustMean;
+pub use weighted_quantile::WeightedQuantile;
+#[cfg(feature = "alloc")]
+pub use quantile_method::{QuantileEstimator, QuantileMethod};
+#[cfg(feature = "alloc")]
+pub use tdigest::TDigest;
+#[cfg(feature = "alloc")]
+pub use mode::Mode;
+#[cfg(feature = "alloc")]
+pub use mean_absolute_deviation::MeanAbsoluteDeviation;
+pub use moving::{ExpMovingMean, ExpMovingVariance};
 pub use traits::{Estimate, Merge, Histogram};
+pub use histogram::OutOfRange;
+#[doc(hidden)]
+pub use histogram::{quantile_from_bins, percentile_rank_from_bins,
+                    cumulative_from_bins, cdf_from_bins};
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use histogram::write_csv_from_bins;
+pub use exact::{exact_mean, exact_variance};
+pub use comparison::{cohens_d, welch_t};
+#[cfg(feature = "alloc")]
+pub use dyn_histogram::{DynHistogram, IterDynHistogram};
+#[cfg(feature = "alloc")]
+pub use auto_histogram::AutoHistogram;
+#[cfg(feature = "alloc")]
+pub use binned_mean::BinnedMean;
+#[cfg(feature = "alloc")]
+pub use merge_all::merge_all;
+#[cfg(feature = "rayon")]
+pub use parallel::par_reduce;
+#[cfg(all(feature = "alloc", feature = "rand_core"))]
+pub use reservoir::Reservoir;
+#[cfg(all(feature = "alloc", feature = "ndarray"))]
+pub use array::{estimate_columns, estimate_rows};
 
 define_histogram!(hist, 10);
 pub use hist::Histogram as Histogram10;
@@ -0,0 +1,187 @@
+use core;
+
+use super::{CoVariance, Merge};
+
+/// Estimate the covariance of the logarithms of a sequence of pairs of
+/// numbers ("population").
+///
+/// This is equivalent to adding `(x.ln(), y.ln())` to a `CoVariance`, which
+/// is useful for quantities that are naturally compared by ratios, like the
+/// log-returns of prices. All estimates refer to the log-transformed data.
+///
+/// The logarithm is only defined for positive numbers. Pairs where `x` or `y`
+/// is not positive (including `NaN`) are not added to the sample. They are
+/// counted by `rejected`, so that they can be detected.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::LogCoVariance;
+///
+/// let mut a = LogCoVariance::new();
+/// a.add(1., 2.).add(2., 4.).add(4., 8.).add(-1., 3.);
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.rejected(), 1);
+/// assert!((a.pearson() - 1.).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LogCoVariance {
+    /// Estimator of the covariance of the logarithms.
+    cov: CoVariance,
+    /// Number of pairs that were not added, because they were not positive.
+    rejected: u64,
+}
+
+impl LogCoVariance {
+    /// Create a new covariance estimator for the logarithms.
+    #[inline]
+    pub fn new() -> LogCoVariance {
+        LogCoVariance { cov: CoVariance::new(), rejected: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = LogCoVariance::new();
+    }
+
+    /// Add an observed pair `(x, y)` sampled from the population.
+    ///
+    /// The pair is rejected if `x` or `y` is not positive. This returns the
+    /// estimator, so calls can be chained.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) -> &mut LogCoVariance {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        if x > 0. && y > 0. {
+            self.cov.add(x.ln(), y.ln());
+        } else {
+            self.rejected += 1;
+        }
+        self
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cov.is_empty()
+    }
+
+    /// Return the sample size, not including the rejected pairs.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.cov.len()
+    }
+
+    /// Return the number of rejected pairs, where `x` or `y` was not positive.
+    #[inline]
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Estimate the mean of the logarithms of the X population.
+    #[inline]
+    pub fn mean_x(&self) -> f64 {
+        self.cov.mean_x()
+    }
+
+    /// Estimate the mean of the logarithms of the Y population.
+    #[inline]
+    pub fn mean_y(&self) -> f64 {
+        self.cov.mean_y()
+    }
+
+    /// Calculate the sample covariance of the logarithms.
+    ///
+    /// This is an unbiased estimator of the covariance of the logarithms of
+    /// the population.
+    #[inline]
+    pub fn sample_covariance(&self) -> f64 {
+        self.cov.sample_covariance()
+    }
+
+    /// Calculate the sample variance of the logarithms of X.
+    #[inline]
+    pub fn sample_variance_x(&self) -> f64 {
+        self.cov.sample_variance_x()
+    }
+
+    /// Calculate the sample variance of the logarithms of Y.
+    #[inline]
+    pub fn sample_variance_y(&self) -> f64 {
+        self.cov.sample_variance_y()
+    }
+
+    /// Calculate the population covariance of the logarithms of the sample.
+    #[inline]
+    pub fn population_covariance(&self) -> f64 {
+        self.cov.population_covariance()
+    }
+
+    /// Calculate the population variance of the logarithms of X.
+    #[inline]
+    pub fn population_variance_x(&self) -> f64 {
+        self.cov.population_variance_x()
+    }
+
+    /// Calculate the population variance of the logarithms of Y.
+    #[inline]
+    pub fn population_variance_y(&self) -> f64 {
+        self.cov.population_variance_y()
+    }
+
+    /// Calculate the Pearson correlation coefficient of the logarithms.
+    ///
+    /// See `CoVariance::pearson` for the edge cases.
+    #[inline]
+    pub fn pearson(&self) -> f64 {
+        self.cov.pearson()
+    }
+
+    /// Return the estimator of the covariance of the logarithms.
+    #[inline]
+    pub fn covariance(&self) -> &CoVariance {
+        &self.cov
+    }
+}
+
+impl core::default::Default for LogCoVariance {
+    fn default() -> LogCoVariance {
+        LogCoVariance::new()
+    }
+}
+
+impl Merge for LogCoVariance {
+    /// Merge another sample into this one.
+    #[inline]
+    fn merge(&mut self, other: &LogCoVariance) {
+        self.cov.merge(&other.cov);
+        self.rejected += other.rejected;
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for LogCoVariance {
+    fn from_iter<T>(iter: T) -> LogCoVariance
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = LogCoVariance::new();
+        for (x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for LogCoVariance {
+    fn from_iter<T>(iter: T) -> LogCoVariance
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = LogCoVariance::new();
+        for &(x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
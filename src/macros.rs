@@ -29,9 +29,14 @@ macro_rules! assert_almost_eq {
 /// For moments, only an estimator for the highest moment should be used and
 /// reused for the lower moments (see the example below).
 ///
+/// The name may be preceded by attributes and a visibility, which are applied
+/// to the struct, for example `#[derive(Debug, Clone)] pub Estimator`.
+///
 /// The following methods will be implemented: `new`, `add`, `$statistic`.
 ///
 /// The following traits will be implemented: `Default`, `FromIterator<f64>`.
+/// If all estimators implement `Merge`, `Merge` is implemented as well, by
+/// merging each field.
 ///
 ///
 /// # Examples
@@ -97,11 +102,14 @@ macro_rules! assert_almost_eq {
 /// ```
 #[macro_export]
 macro_rules! concatenate {
-    ( $name:ident, $([$estimator:ident, $statistic:ident]),+ ) => {
-        concatenate!( $name, $([$estimator, $statistic, $statistic]),* );
+    ( $(#[$attr:meta])* $vis:vis $name:ident,
+      $([$estimator:ident, $statistic:ident]),+ ) => {
+        concatenate!( $(#[$attr])* $vis $name, $([$estimator, $statistic, $statistic]),* );
     };
-    ( $name:ident, $( [$estimator:ident, $field:ident, $($statistic:ident),+] ),+ ) => {
-        struct $name {
+    ( $(#[$attr:meta])* $vis:vis $name:ident,
+      $( [$estimator:ident, $field:ident, $($statistic:ident),+] ),+ ) => {
+        $(#[$attr])*
+        $vis struct $name {
         $(
             $field: $estimator,
         )*
@@ -138,11 +146,27 @@ macro_rules! concatenate {
             }
         }
 
-        impl_from_iterator!($name);
+        $crate::impl_from_iterator!($name);
+
+        // The higher-ranked bounds make sure this is only implemented if all
+        // estimators implement `Merge`, instead of failing to compile.
+        impl $crate::Merge for $name
+            where $( for<'a> $estimator: $crate::Merge, )*
+        {
+            #[inline]
+            fn merge(&mut self, other: &$name) {
+                $(
+                    $crate::Merge::merge(&mut self.$field, &other.$field);
+                )*
+            }
+        }
     };
 }
 
 /// Implement `FromIterator<f64>` for an iterative estimator.
+///
+/// This also implements it for `&f64` and `&&f64`, so that iterators over
+/// references can be collected without copying them first.
 #[macro_export]
 macro_rules! impl_from_iterator {
     ( $name:ident ) => {
@@ -169,5 +193,17 @@ macro_rules! impl_from_iterator {
                 e
             }
         }
+
+        impl<'a, 'b> ::core::iter::FromIterator<&'a &'b f64> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=&'a &'b f64>
+            {
+                let mut e = $name::new();
+                for &&i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
     };
 }
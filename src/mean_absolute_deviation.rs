@@ -0,0 +1,168 @@
+use alloc::vec::Vec;
+use core;
+
+use float_ord::sort as sort_floats;
+use num_traits::ToPrimitive;
+
+use super::{exact_mean, Estimate, Merge};
+
+/// Estimate the mean absolute deviation from the mean and the Gini mean
+/// difference of a sequence of numbers ("population").
+///
+/// The mean absolute deviation `mean(|x - mean(x)|)` measures the dispersion
+/// like the standard deviation, but is less sensitive to outliers. Because the
+/// deviations depend on the final mean, all samples have to be stored, so this
+/// requires O(n) memory. Calculating the mean absolute deviation takes O(n)
+/// operations, calculating the Gini mean difference sorts a copy of the
+/// samples, which takes O(n log n) operations.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::MeanAbsoluteDeviation;
+///
+/// let a: MeanAbsoluteDeviation = [1., 2., 3., 4., 5.].iter().collect();
+/// assert_eq!(a.mean(), 3.);
+/// assert_eq!(a.mean_absolute_deviation(), 1.2);
+/// assert_eq!(a.gini_mean_difference(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MeanAbsoluteDeviation {
+    /// All samples.
+    samples: Vec<f64>,
+}
+
+impl MeanAbsoluteDeviation {
+    /// Create a new mean absolute deviation estimator.
+    #[inline]
+    pub fn new() -> MeanAbsoluteDeviation {
+        MeanAbsoluteDeviation { samples: Vec::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.samples.len().to_u64().unwrap()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        exact_mean(&self.samples)
+    }
+
+    /// Calculate the mean absolute deviation from the mean of the sample.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn mean_absolute_deviation(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.;
+        }
+        let mean = self.mean();
+        let sum: f64 = self.samples.iter().map(|&x| (x - mean).abs()).sum();
+        sum / self.samples.len().to_f64().unwrap()
+    }
+
+    /// Calculate the Gini mean difference of the sample.
+    ///
+    /// This is the mean absolute difference `|x_i - x_j|` over all pairs of
+    /// distinct samples. Returns 0 if the sample has less than two elements.
+    pub fn gini_mean_difference(&self) -> f64 {
+        let n = self.samples.len();
+        if n < 2 {
+            return 0.;
+        }
+        let mut sorted = self.samples.clone();
+        sort_floats(&mut sorted);
+        // For sorted samples, `x_i` is larger than `i` samples and smaller
+        // than `n - 1 - i` samples.
+        let n_f = n.to_f64().unwrap();
+        let sum: f64 = sorted.iter().enumerate()
+            .map(|(i, &x)| (2. * i.to_f64().unwrap() - n_f + 1.) * x)
+            .sum();
+        2. * sum / (n_f * (n_f - 1.))
+    }
+
+    /// Calculate the Gini coefficient of the sample.
+    ///
+    /// This is the Gini mean difference divided by twice the mean, a measure
+    /// of inequality between 0 (all samples are equal) and 1. It is only
+    /// meaningful for nonnegative samples. Returns 0 if the sample has less
+    /// than two elements or if the mean is 0.
+    pub fn gini_coefficient(&self) -> f64 {
+        let mean = self.mean();
+        if mean == 0. {
+            return 0.;
+        }
+        self.gini_mean_difference() / (2. * mean)
+    }
+}
+
+impl core::default::Default for MeanAbsoluteDeviation {
+    fn default() -> MeanAbsoluteDeviation {
+        MeanAbsoluteDeviation::new()
+    }
+}
+
+impl Estimate for MeanAbsoluteDeviation {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.samples.push(x);
+    }
+
+    fn estimate(&self) -> f64 {
+        self.mean_absolute_deviation()
+    }
+}
+
+impl Merge for MeanAbsoluteDeviation {
+    /// Merge another sample into this one.
+    ///
+    /// This is exact.
+    #[inline]
+    fn merge(&mut self, other: &MeanAbsoluteDeviation) {
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
+
+impl core::iter::FromIterator<f64> for MeanAbsoluteDeviation {
+    fn from_iter<T>(iter: T) -> MeanAbsoluteDeviation
+        where T: IntoIterator<Item=f64>
+    {
+        MeanAbsoluteDeviation { samples: iter.into_iter().collect() }
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for MeanAbsoluteDeviation {
+    fn from_iter<T>(iter: T) -> MeanAbsoluteDeviation
+        where T: IntoIterator<Item=&'a f64>
+    {
+        MeanAbsoluteDeviation { samples: iter.into_iter().cloned().collect() }
+    }
+}
+
+impl<'a, 'b> core::iter::FromIterator<&'a &'b f64> for MeanAbsoluteDeviation {
+    fn from_iter<T>(iter: T) -> MeanAbsoluteDeviation
+        where T: IntoIterator<Item=&'a &'b f64>
+    {
+        MeanAbsoluteDeviation { samples: iter.into_iter().map(|&&x| x).collect() }
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+
+use super::Merge;
+
+/// Merge a sequence of estimators into one.
+///
+/// The estimators are merged pairwise like the nodes of a balanced binary
+/// tree, instead of merging them one after another into an accumulator. This
+/// keeps the estimators that are merged of similar size, which reduces the
+/// accumulated rounding errors when merging many estimators. It requires
+/// O(log n) additional memory for `n` estimators. The order of the estimators
+/// is preserved, so this is equivalent to merging them from left to right, up
+/// to rounding errors.
+///
+/// Returns `E::default()` for an empty sequence.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, merge_all};
+///
+/// let shards: Vec<Variance> = (0..10)
+///     .map(|i| (10 * i..10 * (i + 1)).map(f64::from).collect())
+///     .collect();
+/// let a: Variance = merge_all(shards);
+/// assert_eq!(a.len(), 100);
+/// assert_eq!(a.mean(), 49.5);
+/// ```
+pub fn merge_all<E, I>(iter: I) -> E
+    where E: Merge + Default,
+          I: IntoIterator<Item = E>
+{
+    // Partial results, each with the number of estimators it contains. The
+    // sizes are strictly decreasing powers of two, like in a binary counter.
+    let mut stack: Vec<(u64, E)> = Vec::new();
+    for e in iter {
+        let mut size = 1;
+        let mut e = e;
+        while let Some(&(top, _)) = stack.last() {
+            if top != size {
+                break;
+            }
+            let (_, mut left) = stack.pop().unwrap();
+            left.merge(&e);
+            e = left;
+            size *= 2;
+        }
+        stack.push((size, e));
+    }
+    let mut result = match stack.pop() {
+        Some((_, e)) => e,
+        None => return E::default(),
+    };
+    while let Some((_, mut left)) = stack.pop() {
+        left.merge(&result);
+        result = left;
+    }
+    result
+}
@@ -42,6 +42,12 @@ impl Min {
         Min::from_value(::core::f64::INFINITY)
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Min::new();
+    }
+
     /// Estimate the minium of the population.
     #[inline]
     pub fn min(&self) -> f64 {
@@ -72,6 +78,9 @@ impl Estimate for Min {
 impl Merge for Min {
     /// Merge another sample into this one.
     ///
+    /// An empty estimator has a minimum of infinity, so merging it has no
+    /// effect, and merging into it yields the minimum of the other sample.
+    ///
     ///
     /// ## Example
     ///
@@ -122,6 +131,12 @@ impl Max {
         Max::from_value(::core::f64::NEG_INFINITY)
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Max::new();
+    }
+
     /// Estimate the maxium of the population.
     #[inline]
     pub fn max(&self) -> f64 {
@@ -152,6 +167,10 @@ impl Estimate for Max {
 impl Merge for Max {
     /// Merge another sample into this one.
     ///
+    /// An empty estimator has a maximum of negative infinity, so merging it
+    /// has no effect, and merging into it yields the maximum of the other
+    /// sample.
+    ///
     ///
     /// ## Example
     ///
@@ -171,3 +190,354 @@ impl Merge for Max {
         self.add(other.x);
     }
 }
+
+/// Estimate the minimum, the maximum and the range of a sequence of numbers
+/// ("population").
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Range;
+///
+/// let a: Range = (1..6).map(f64::from).collect();
+/// assert_eq!(a.min(), 1.);
+/// assert_eq!(a.max(), 5.);
+/// assert_eq!(a.range(), 4.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Range {
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl Range {
+    /// Create a new range estimator.
+    #[inline]
+    pub fn new() -> Range {
+        Range { min: Min::new(), max: Max::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Range::new();
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// `NaN` samples are ignored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.min() > self.max.max()
+    }
+
+    /// Estimate the minium of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the maxium of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Estimate the range of the population, i.e. `max - min`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn range(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        self.max() - self.min()
+    }
+}
+
+impl core::default::Default for Range {
+    fn default() -> Range {
+        Range::new()
+    }
+}
+
+impl_from_iterator!(Range);
+
+impl Estimate for Range {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.min.add(x);
+        self.max.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.range()
+    }
+}
+
+impl Merge for Range {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Range, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let range_total: Range = sequence.iter().collect();
+    /// let mut range_left: Range = left.iter().collect();
+    /// let range_right: Range = right.iter().collect();
+    /// range_left.merge(&range_right);
+    /// assert_eq!(range_total.range(), range_left.range());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Range) {
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
+    }
+}
+
+/// Estimate the minimum of a sequence of numbers ("population") and the index
+/// of the sample where it occurred.
+///
+/// Indices are zero-based and count all added samples. On ties, the first
+/// index wins. `NaN` samples are ignored, like for [`Min`].
+///
+/// [`Min`]: ./struct.Min.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::IndexedMin;
+///
+/// let a: IndexedMin = [3., 1., 4., 1., 5.].iter().collect();
+/// assert_eq!(a.min(), 1.);
+/// assert_eq!(a.index(), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct IndexedMin {
+    /// Current minimum.
+    x: f64,
+    /// Index of the current minimum.
+    index: Option<u64>,
+    /// Sample size.
+    n: u64,
+}
+
+impl IndexedMin {
+    /// Create a new indexed minimum estimator.
+    #[inline]
+    pub fn new() -> IndexedMin {
+        IndexedMin { x: ::core::f64::INFINITY, index: None, n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = IndexedMin::new();
+    }
+
+    /// Estimate the minium of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.x
+    }
+
+    /// Return the index of the sample where the minimum occurred.
+    ///
+    /// Returns `None` if there are no samples (not counting `NaN`).
+    #[inline]
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Update the estimate with a candidate minimum at the given index.
+    #[inline]
+    fn update(&mut self, x: f64, index: u64) {
+        if x < self.x || (self.index.is_none() && !x.is_nan()) {
+            self.x = x;
+            self.index = Some(index);
+        }
+    }
+}
+
+impl core::default::Default for IndexedMin {
+    fn default() -> IndexedMin {
+        IndexedMin::new()
+    }
+}
+
+impl_from_iterator!(IndexedMin);
+
+impl Estimate for IndexedMin {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        let index = self.n;
+        self.update(x, index);
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.min()
+    }
+}
+
+impl Merge for IndexedMin {
+    /// Merge another sample into this one.
+    ///
+    /// The samples of `other` are considered to come after the samples of
+    /// `self`, so their indices are shifted by `self.len()`.
+    #[inline]
+    fn merge(&mut self, other: &IndexedMin) {
+        if let Some(index) = other.index {
+            let offset = self.n;
+            self.update(other.x, offset + index);
+        }
+        self.n += other.n;
+    }
+}
+
+/// Estimate the maximum of a sequence of numbers ("population") and the index
+/// of the sample where it occurred.
+///
+/// Indices are zero-based and count all added samples. On ties, the first
+/// index wins. `NaN` samples are ignored, like for [`Max`].
+///
+/// [`Max`]: ./struct.Max.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::IndexedMax;
+///
+/// let a: IndexedMax = [3., 5., 4., 1., 5.].iter().collect();
+/// assert_eq!(a.max(), 5.);
+/// assert_eq!(a.index(), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct IndexedMax {
+    /// Current maximum.
+    x: f64,
+    /// Index of the current maximum.
+    index: Option<u64>,
+    /// Sample size.
+    n: u64,
+}
+
+impl IndexedMax {
+    /// Create a new indexed maximum estimator.
+    #[inline]
+    pub fn new() -> IndexedMax {
+        IndexedMax { x: ::core::f64::NEG_INFINITY, index: None, n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = IndexedMax::new();
+    }
+
+    /// Estimate the maxium of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.x
+    }
+
+    /// Return the index of the sample where the maximum occurred.
+    ///
+    /// Returns `None` if there are no samples (not counting `NaN`).
+    #[inline]
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Update the estimate with a candidate maximum at the given index.
+    #[inline]
+    fn update(&mut self, x: f64, index: u64) {
+        if x > self.x || (self.index.is_none() && !x.is_nan()) {
+            self.x = x;
+            self.index = Some(index);
+        }
+    }
+}
+
+impl core::default::Default for IndexedMax {
+    fn default() -> IndexedMax {
+        IndexedMax::new()
+    }
+}
+
+impl_from_iterator!(IndexedMax);
+
+impl Estimate for IndexedMax {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        let index = self.n;
+        self.update(x, index);
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.max()
+    }
+}
+
+impl Merge for IndexedMax {
+    /// Merge another sample into this one.
+    ///
+    /// The samples of `other` are considered to come after the samples of
+    /// `self`, so their indices are shifted by `self.len()`.
+    #[inline]
+    fn merge(&mut self, other: &IndexedMax) {
+        if let Some(index) = other.index {
+            let offset = self.n;
+            self.update(other.x, offset + index);
+        }
+        self.n += other.n;
+    }
+}
@@ -0,0 +1,189 @@
+use alloc::vec::Vec;
+use core;
+
+use float_ord::FloatOrd;
+
+use super::{Estimate, Merge};
+
+/// Estimate the mode (the most frequent value) of a sequence of numbers
+/// ("population").
+///
+/// The samples are counted exactly, so this is meant for discrete data with
+/// few distinct values, like integers or categories encoded as numbers. For
+/// continuous data, round the samples to the desired resolution before adding
+/// them, or use a histogram and take the bin with the largest count.
+///
+/// If several values are equally frequent, the smallest of them is the mode.
+///
+/// This stores every distinct value, so it requires O(k) memory and O(k)
+/// operations for adding a new distinct value, where `k` is the number of
+/// distinct values.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Mode;
+///
+/// let a: Mode = [1., 2., 2., 3., 2., 1.].iter().collect();
+/// assert_eq!(a.mode(), 2.);
+/// assert_eq!(a.mode_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Mode {
+    /// The distinct values and their counts, sorted by value.
+    counts: Vec<(f64, u64)>,
+    /// Sample size.
+    len: u64,
+}
+
+impl Mode {
+    /// Create a new mode estimator.
+    #[inline]
+    pub fn new() -> Mode {
+        Mode { counts: Vec::new(), len: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.counts.clear();
+        self.len = 0;
+    }
+
+    /// Add `n` observations of the value `x`.
+    #[inline]
+    fn add_count(&mut self, x: f64, n: u64) {
+        match self.counts.binary_search_by(|&(y, _)| FloatOrd(y).cmp(&FloatOrd(x))) {
+            Ok(i) => self.counts[i].1 += n,
+            Err(i) => self.counts.insert(i, (x, n)),
+        }
+        self.len += n;
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Return the number of distinct values.
+    #[inline]
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Return how often the value `x` was observed.
+    #[inline]
+    pub fn count(&self, x: f64) -> u64 {
+        match self.counts.binary_search_by(|&(y, _)| FloatOrd(y).cmp(&FloatOrd(x))) {
+            Ok(i) => self.counts[i].1,
+            Err(_) => 0,
+        }
+    }
+
+    /// Find the most frequent value and its count.
+    #[inline]
+    fn find_mode(&self) -> (f64, u64) {
+        let mut best = (0., 0);
+        for &(x, n) in &self.counts {
+            // The values are sorted, so ties keep the smallest value.
+            if n > best.1 {
+                best = (x, n);
+            }
+        }
+        best
+    }
+
+    /// Estimate the mode of the population.
+    ///
+    /// Returns the smallest of the most frequent values, or 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn mode(&self) -> f64 {
+        self.find_mode().0
+    }
+
+    /// Return how often the mode was observed.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mode_count(&self) -> u64 {
+        self.find_mode().1
+    }
+}
+
+impl core::default::Default for Mode {
+    fn default() -> Mode {
+        Mode::new()
+    }
+}
+
+impl Estimate for Mode {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add_count(x, 1);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mode()
+    }
+}
+
+impl Merge for Mode {
+    /// Merge another sample into this one.
+    ///
+    /// This is exact.
+    #[inline]
+    fn merge(&mut self, other: &Mode) {
+        for &(x, n) in &other.counts {
+            self.add_count(x, n);
+        }
+    }
+}
+
+impl core::iter::FromIterator<f64> for Mode {
+    fn from_iter<T>(iter: T) -> Mode
+        where T: IntoIterator<Item=f64>
+    {
+        let mut a = Mode::new();
+        for x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for Mode {
+    fn from_iter<T>(iter: T) -> Mode
+        where T: IntoIterator<Item=&'a f64>
+    {
+        let mut a = Mode::new();
+        for &x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a, 'b> core::iter::FromIterator<&'a &'b f64> for Mode {
+    fn from_iter<T>(iter: T) -> Mode
+        where T: IntoIterator<Item=&'a &'b f64>
+    {
+        let mut a = Mode::new();
+        for &&x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
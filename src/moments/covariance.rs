@@ -1,6 +1,6 @@
 // own extension to https://crates.io/crates/average
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct CoVariance {
     /// Estimator of X average.
@@ -22,9 +22,33 @@ impl CoVariance {
         CoVariance { avg_x: Mean::new(), avg_y: Mean::new(), sum_2: 0., sum_2_x: 0., sum_2_y: 0. }
     }
 
+    /// Create a new covariance estimator from already accumulated means of
+    /// the X and Y samples.
+    ///
+    /// The cross terms of the previous samples cannot be recovered, so they
+    /// are treated as if all of them were located at their means: The
+    /// covariance and the variances only accumulate from subsequent calls to
+    /// `add`, while the means and the sample size include the previous
+    /// samples. This is equivalent to adding `len` times the pair
+    /// `(mean_x, mean_y)` to an empty estimator.
+    ///
+    /// Panics if the means have different sample sizes.
+    #[inline]
+    pub fn from_means(avg_x: Mean, avg_y: Mean) -> CoVariance {
+        assert_eq!(avg_x.len(), avg_y.len(), "means must have the same sample size");
+        CoVariance { avg_x, avg_y, sum_2: 0., sum_2_x: 0., sum_2_y: 0. }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = CoVariance::new();
+    }
+
     /// Increment the sample size.
     ///
-    /// This does not update anything else.
+    /// This does not update anything else. The sample size is not checked for
+    /// exceeding the exactly representable integers, see `len_exhausted`.
     #[inline]
     fn increment(&mut self) {
         self.avg_x.increment();
@@ -42,7 +66,7 @@ impl CoVariance {
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.avg_x.len()).unwrap();
+        let n = self.avg_x.len().to_f64().unwrap();
         self.avg_x.add_inner(delta_x);
         self.avg_y.add_inner(delta_y);
 
@@ -55,7 +79,7 @@ impl CoVariance {
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.avg_x.is_empty() || self.avg_y.is_empty()
+        self.len() == 0
     }
 
     /// Estimate the mean of the X population.
@@ -74,12 +98,41 @@ impl CoVariance {
         self.avg_y.mean()
     }
 
+    /// Return the estimator of the mean of the X population.
+    ///
+    /// It is only borrowed, because it has to stay in sync with the covariance.
+    #[inline]
+    pub fn mean_x_estimator(&self) -> &Mean {
+        &self.avg_x
+    }
+
+    /// Return the estimator of the mean of the Y population.
+    ///
+    /// It is only borrowed, because it has to stay in sync with the covariance.
+    #[inline]
+    pub fn mean_y_estimator(&self) -> &Mean {
+        &self.avg_y
+    }
+
     /// Return the sample size.
+    ///
+    /// The sample size is converted to `f64` for calculating the estimates.
+    /// Beyond `2^53` samples, sample sizes can no longer be represented exactly
+    /// and the estimates lose precision, see `len_exhausted`.
     #[inline]
     pub fn len(&self) -> u64 {
+        // X and Y are always added together, so their sample sizes must agree.
+        debug_assert_eq!(self.avg_x.len(), self.avg_y.len());
         self.avg_x.len()
     }
 
+    /// Determine whether the sample size exceeds the range of integers that
+    /// can be represented exactly, see `Mean::len_exhausted`.
+    #[inline]
+    pub fn len_exhausted(&self) -> bool {
+        self.avg_x.len_exhausted()
+    }
+
     /// Calculate the sample covariance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -89,7 +142,7 @@ impl CoVariance {
         if n < 2 {
             return 0.;
         }
-        self.sum_2 / f64::approx_from(n - 1).unwrap()
+        self.sum_2 / (n - 1).to_f64().unwrap()
     }
 
     /// Calculate the sample X variance.
@@ -101,7 +154,7 @@ impl CoVariance {
         if n < 2 {
             return 0.;
         }
-        self.sum_2_x / f64::approx_from(n - 1).unwrap()
+        self.sum_2_x / (n - 1).to_f64().unwrap()
     }
 
     /// Calculate the sample Y variance.
@@ -113,16 +166,300 @@ impl CoVariance {
         if n < 2 {
             return 0.;
         }
-        self.sum_2_y / f64::approx_from(n - 1).unwrap()
+        self.sum_2_y / (n - 1).to_f64().unwrap()
+    }
+
+    /// Calculate the population covariance of the sample.
+    ///
+    /// This is a biased estimator of the covariance of the population.
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn population_covariance(&self) -> f64 {
+        let n = self.avg_x.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.sum_2 / n.to_f64().unwrap()
+    }
+
+    /// Calculate the population X variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the X population.
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn population_variance_x(&self) -> f64 {
+        let n = self.avg_x.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.sum_2_x / n.to_f64().unwrap()
+    }
+
+    /// Calculate the population Y variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the Y population.
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn population_variance_y(&self) -> f64 {
+        let n = self.avg_y.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.sum_2_y / n.to_f64().unwrap()
+    }
+
+    /// Calculate the sample X standard deviation.
+    ///
+    /// This is the square root of the sample X variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn sample_standard_deviation_x(&self) -> f64 {
+        self.sample_variance_x().sqrt()
+    }
+
+    /// Calculate the sample Y standard deviation.
+    ///
+    /// This is the square root of the sample Y variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn sample_standard_deviation_y(&self) -> f64 {
+        self.sample_variance_y().sqrt()
+    }
+
+    /// Calculate the population X standard deviation of the sample.
+    ///
+    /// This is the square root of the population X variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn population_standard_deviation_x(&self) -> f64 {
+        self.population_variance_x().sqrt()
+    }
+
+    /// Calculate the population Y standard deviation of the sample.
+    ///
+    /// This is the square root of the population Y variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn population_standard_deviation_y(&self) -> f64 {
+        self.population_variance_y().sqrt()
+    }
+
+    /// Calculate the Pearson correlation coefficient of the sample.
+    ///
+    /// Returns 0 if the sample size is smaller than 2 or if either variance is
+    /// zero. The result is clamped to `[-1, 1]` to guard against rounding
+    /// errors.
+    #[inline]
+    pub fn pearson(&self) -> f64 {
+        if self.len() < 2 || self.sum_2_x == 0. || self.sum_2_y == 0. {
+            return 0.;
+        }
+        // The normalization factors cancel, so we can use the sums directly.
+        let r = self.sum_2 / (self.sum_2_x.sqrt() * self.sum_2_y.sqrt());
+        r.max(-1.).min(1.)
+    }
+
+    /// Calculate the slope of the ordinary least squares fit `y = a + b*x`.
+    ///
+    /// Returns 0 if the sample size is smaller than 2 or if the X variance is
+    /// zero (i.e. the best fit would be a vertical line).
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        if self.len() < 2 || self.sum_2_x == 0. {
+            return 0.;
+        }
+        self.sum_2 / self.sum_2_x
+    }
+
+    /// Calculate the intercept of the ordinary least squares fit `y = a + b*x`.
+    ///
+    /// Returns 0 if the sample size is smaller than 2 or if the X variance is
+    /// zero (i.e. the best fit would be a vertical line).
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        if self.len() < 2 || self.sum_2_x == 0. {
+            return 0.;
+        }
+        self.mean_y() - self.slope() * self.mean_x()
+    }
+
+    /// Predict `y` for the given `x` using the ordinary least squares fit.
+    ///
+    /// Returns 0 if the fit is undefined (see `slope` and `intercept`).
+    #[inline]
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept() + self.slope() * x
+    }
+
+    /// Calculate the coefficient of determination (R²) of the ordinary least
+    /// squares fit.
+    ///
+    /// For simple linear regression, this is the square of the Pearson
+    /// correlation coefficient. Returns 0 if the sample size is smaller than 2.
+    /// The result is clamped to `[0, 1]`.
+    #[inline]
+    pub fn r_squared(&self) -> f64 {
+        let r = self.pearson();
+        (r * r).max(0.).min(1.)
+    }
+
+    /// Add all pairs `(xs[i], ys[i])` of two slices.
+    ///
+    /// This is equivalent to calling `add` for each pair, but faster. The
+    /// means and the sums of products of the slices are calculated in two
+    /// passes, requiring only a few divisions, and then merged into this
+    /// sample.
+    ///
+    /// Panics if the slices have different lengths.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[f64], ys: &[f64]) {
+        assert_eq!(xs.len(), ys.len(), "slices must have the same length");
+        if xs.is_empty() {
+            return;
+        }
+        let mut avg_x = Mean::new();
+        avg_x.add_slice(xs);
+        let mut avg_y = Mean::new();
+        avg_y.add_slice(ys);
+        let (mean_x, mean_y) = (avg_x.mean(), avg_y.mean());
+        let (mut sum_2, mut sum_2_x, mut sum_2_y) = (0., 0., 0.);
+        for (&x, &y) in xs.iter().zip(ys) {
+            let delta_x = x - mean_x;
+            let delta_y = y - mean_y;
+            sum_2 += delta_x * delta_y;
+            sum_2_x += delta_x * delta_x;
+            sum_2_y += delta_y * delta_y;
+        }
+        self.merge(&CoVariance { avg_x, avg_y, sum_2, sum_2_x, sum_2_y });
     }
 
+    /// Add an observation sampled from the population.
+    ///
+    /// This returns the estimator, so calls can be chained.
+    ///
+    /// For numerical stability, this divides by the sample size for every
+    /// observation. If the observations are available as slices, `add_slice`
+    /// is faster.
     #[inline]
-    pub fn add(&mut self, sample_x: f64, sample_y: f64) {
+    pub fn add(&mut self, sample_x: f64, sample_y: f64) -> &mut CoVariance {
         self.increment();
         let delta_x = (sample_x - self.avg_x.mean())
-            / f64::approx_from(self.avg_x.len()).unwrap();
+            / self.avg_x.len().to_f64().unwrap();
         let delta_y = (sample_y - self.avg_y.mean())
-            / f64::approx_from(self.avg_y.len()).unwrap();
+            / self.avg_y.len().to_f64().unwrap();
         self.add_inner(delta_x, delta_y);
+        self
+    }
+}
+
+impl core::default::Default for CoVariance {
+    fn default() -> CoVariance {
+        CoVariance::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for CoVariance {
+    /// Format the sample covariance and the Pearson correlation coefficient
+    /// like `cov=1.5, corr=0.8 (n=5)`.
+    ///
+    /// The precision of the formatter is applied to both numbers.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "cov={:.*}, corr={:.*} (n={})",
+                              p, self.sample_covariance(), p, self.pearson(), self.len()),
+            None => write!(f, "cov={}, corr={} (n={})",
+                           self.sample_covariance(), self.pearson(), self.len()),
+        }
+    }
+}
+
+impl Merge for CoVariance {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{CoVariance, Merge};
+    ///
+    /// let sequence: &[(f64, f64)] = &[
+    ///     (1., 2.), (2., 1.), (3., 4.), (4., 3.), (5., 5.),
+    ///     (6., 8.), (7., 6.), (8., 9.), (9., 7.)];
+    /// let (left, right) = sequence.split_at(3);
+    /// let mut cov_total = CoVariance::new();
+    /// for &(x, y) in sequence {
+    ///     cov_total.add(x, y);
+    /// }
+    /// let mut cov_left = CoVariance::new();
+    /// for &(x, y) in left {
+    ///     cov_left.add(x, y);
+    /// }
+    /// let mut cov_right = CoVariance::new();
+    /// for &(x, y) in right {
+    ///     cov_right.add(x, y);
+    /// }
+    /// cov_left.merge(&cov_right);
+    /// assert!((cov_total.sample_covariance() - cov_left.sample_covariance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &CoVariance) {
+        // This algorithm was proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let len_self = self.len().to_f64().unwrap();
+        let len_other = other.len().to_f64().unwrap();
+        let len_total = len_self + len_other;
+        if len_total == 0. {
+            return;
+        }
+        let delta_x = other.mean_x() - self.mean_x();
+        let delta_y = other.mean_y() - self.mean_y();
+        let factor = len_self * len_other / len_total;
+        self.avg_x.merge(&other.avg_x);
+        self.avg_y.merge(&other.avg_y);
+        self.sum_2 += other.sum_2 + delta_x * delta_y * factor;
+        self.sum_2_x += other.sum_2_x + delta_x * delta_x * factor;
+        self.sum_2_y += other.sum_2_y + delta_y * delta_y * factor;
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for CoVariance {
+    fn from_iter<T>(iter: T) -> CoVariance
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = CoVariance::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for CoVariance {
+    fn from_iter<T>(iter: T) -> CoVariance
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = CoVariance::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for CoVariance {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for CoVariance {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        for &(x, y) in iter {
+            self.add(x, y);
+        }
     }
 }
@@ -2,6 +2,11 @@
 /// a sequence of numbers ("population").
 ///
 /// This can be used to estimate the standard error of the mean.
+///
+/// All four statistics are calculated in a single pass sharing the update of
+/// the mean, so this is faster than using `Mean`, `Variance`, `Skewness` and
+/// `Kurtosis` side by side. There is no need to combine it with the lower
+/// moments.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct Kurtosis {
@@ -21,6 +26,12 @@ impl Kurtosis {
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Kurtosis::new();
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -39,7 +50,7 @@ impl Kurtosis {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         let term = delta * delta_n * (n - 1.);
         let delta_n_sq = delta_n*delta_n;
         self.sum_4 += term * delta_n_sq * (n*n - 3.*n + 3.)
@@ -68,6 +79,13 @@ impl Kurtosis {
         self.avg.len()
     }
 
+    /// Determine whether the sample size exceeds the range of integers that
+    /// can be represented exactly, see `Mean::len_exhausted`.
+    #[inline]
+    pub fn len_exhausted(&self) -> bool {
+        self.avg.len_exhausted()
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -84,6 +102,34 @@ impl Kurtosis {
         self.avg.population_variance()
     }
 
+    /// Calculate the second central moment of the sample.
+    ///
+    /// This is the population variance, see `Skewness::central_moment_2`.
+    #[inline]
+    pub fn central_moment_2(&self) -> f64 {
+        self.avg.central_moment_2()
+    }
+
+    /// Calculate the third central moment of the sample, see
+    /// `Skewness::central_moment_3`.
+    #[inline]
+    pub fn central_moment_3(&self) -> f64 {
+        self.avg.central_moment_3()
+    }
+
+    /// Calculate the fourth central moment of the sample.
+    ///
+    /// This is the sum of the fourth powers of the deviations from the mean
+    /// divided by the sample size. Returns 0 for an empty sample.
+    #[inline]
+    pub fn central_moment_4(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.sum_4 / n.to_f64().unwrap()
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
     pub fn error_mean(&self) -> f64 {
@@ -102,10 +148,28 @@ impl Kurtosis {
         if self.sum_4 == 0. {
             return 0.;
         }
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         n * self.sum_4 / (self.avg.avg.sum_2 * self.avg.avg.sum_2) - 3.
     }
 
+    /// Calculate the Jarque-Bera test statistic.
+    ///
+    /// This is `n/6 * (S² + K²/4)`, where `S` is the skewness and `K` the
+    /// *excess* kurtosis. For a normally distributed population, it is
+    /// asymptotically chi-squared distributed with two degrees of freedom, so
+    /// large values indicate that the population is not normally distributed.
+    /// Returns 0 if the sample size is smaller than 4.
+    #[inline]
+    pub fn jarque_bera(&self) -> f64 {
+        if self.len() < 4 {
+            return 0.;
+        }
+        let n = self.len().to_f64().unwrap();
+        let s = self.skewness();
+        let k = self.kurtosis();
+        n / 6. * (s*s + 0.25 * k*k)
+    }
+
 }
 
 impl core::default::Default for Kurtosis {
@@ -119,7 +183,7 @@ impl Estimate for Kurtosis {
     fn add(&mut self, x: f64) {
         let delta = x - self.mean();
         self.increment();
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         self.add_inner(delta, delta/n);
     }
 
@@ -132,8 +196,15 @@ impl Estimate for Kurtosis {
 impl Merge for Kurtosis {
     #[inline]
     fn merge(&mut self, other: &Kurtosis) {
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        let len_self = self.len().to_f64().unwrap();
+        let len_other = other.len().to_f64().unwrap();
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
@@ -9,20 +9,43 @@
 /// let a: Mean = (1..6).map(f64::from).collect();
 /// println!("The mean is {}.", a.mean());
 /// ```
-#[derive(Debug, Clone)]
+///
+/// The estimator is generic over the floating point type, which defaults to
+/// `f64`. Estimators for other types can be created with `Default`:
+///
+/// ```
+/// use average::Mean;
+///
+/// let mut a = Mean::<f32>::default();
+/// for i in 1..6 {
+///     a.add(i as f32);
+/// }
+/// assert_eq!(a.mean(), 3.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Mean {
+pub struct Mean<T = f64> {
     /// Mean value.
-    avg: f64,
+    avg: T,
     /// Sample size.
     n: u64,
 }
 
 impl Mean {
     /// Create a new mean estimator.
+    ///
+    /// Use `Default` to create an estimator for another floating point type.
     #[inline]
     pub fn new() -> Mean {
-        Mean { avg: 0., n: 0 }
+        Mean::default()
+    }
+}
+
+impl<T: Float> Mean<T> {
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Mean::default();
     }
 
     /// Increment the sample size.
@@ -39,12 +62,12 @@ impl Mean {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: T) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        self.avg += delta_n;
+        self.avg = self.avg + delta_n;
     }
 
     /// Determine whether the sample is empty.
@@ -57,31 +80,148 @@ impl Mean {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> T {
         self.avg
     }
 
     /// Return the sample size.
+    ///
+    /// The sample size is converted to `T` for calculating the estimates.
+    /// Beyond `2^53` samples for `f64` (`2^24` for `f32`), sample sizes can no
+    /// longer be represented exactly and the estimates lose precision, see
+    /// `len_exhausted`.
     #[inline]
     pub fn len(&self) -> u64 {
         self.n
     }
 
+    /// Determine whether the sample size exceeds the range of integers that
+    /// can be represented exactly by `T`.
+    ///
+    /// If this returns true, adding more samples is no longer accurate, and
+    /// long-running aggregations should continue with a fresh estimator
+    /// (which may be merged with this one when calculating the estimates).
+    #[inline]
+    pub fn len_exhausted(&self) -> bool {
+        // All integers up to `2 / epsilon` can be represented exactly.
+        let two = T::one() + T::one();
+        let max_len = (two / T::epsilon()).to_u64().unwrap_or(u64::max_value());
+        self.n > max_len
+    }
+
+    /// Calculate the sum of the observations, i.e. `mean() * len()`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum(&self) -> T {
+        self.avg * to_float(self.n)
+    }
+
+    /// Add all observations of a slice.
+    ///
+    /// This is equivalent to calling `add` for each element, but faster,
+    /// because only one division is required for the whole slice.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[T]) {
+        if xs.is_empty() {
+            return;
+        }
+        // Summing the differences to a pivot avoids losing precision for
+        // large values. If the sample is empty, we take the first observation
+        // as the pivot.
+        let pivot = if self.is_empty() { xs[0] } else { self.avg };
+        let delta_sum = xs.iter().fold(T::zero(), |sum, &x| sum + (x - pivot));
+        self.n += xs.len().to_u64().unwrap();
+        self.avg = pivot + delta_sum / to_float(self.n);
+    }
+
+    /// Estimate the mean of an iterator using pairwise summation.
+    ///
+    /// The observations are combined in a balanced tree of partial means, so
+    /// the rounding error grows like `O(log n)` instead of `O(n)` for
+    /// `collect()`. This is slower, because every observation requires some
+    /// merging, and it needs 64 floats of extra memory on the stack, one for
+    /// each level of the tree.
+    pub fn from_iter_pairwise<I: IntoIterator<Item = T>>(iter: I) -> Mean<T> {
+        // Level `k` holds the mean of a block of `2^k` observations if bit `k`
+        // of the sample size is set, like a binary counter.
+        let mut levels = [T::zero(); 64];
+        let mut n: u64 = 0;
+        let two = T::one() + T::one();
+        for x in iter {
+            let mut avg = x;
+            let mut k = 0;
+            while n & (1 << k) != 0 {
+                avg = (levels[k] + avg) / two;
+                k += 1;
+            }
+            levels[k] = avg;
+            n += 1;
+        }
+        let mut result = Mean::default();
+        for (k, &avg) in levels.iter().enumerate() {
+            if n & (1 << k) != 0 {
+                result.merge(&Mean { avg, n: 1 << k });
+            }
+        }
+        result
+    }
+
+    /// Remove an observation that was previously added to the sample.
+    ///
+    /// This reverses the effect of `add`. The result is only meaningful if `x`
+    /// was actually added before. Note that rounding errors can accumulate if
+    /// many observations are added and removed.
+    ///
+    /// Panics if the sample is empty.
+    #[inline]
+    pub fn remove(&mut self, x: T) {
+        assert!(!self.is_empty(), "cannot remove from an empty sample");
+        self.n -= 1;
+        if self.n == 0 {
+            self.avg = T::zero();
+            return;
+        }
+        self.avg = self.avg - (x - self.avg) / to_float(self.n);
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// This is the same as `Estimate::add`, but it is also available for
+    /// floating point types other than `f64`. It returns the estimator, so
+    /// calls can be chained.
+    #[inline]
+    pub fn add(&mut self, sample: T) -> &mut Self {
+        self.increment();
+        let delta_n = (sample - self.avg) / to_float(self.n);
+        self.add_inner(delta_n);
+        self
+    }
 }
 
-impl core::default::Default for Mean {
-    fn default() -> Mean {
-        Mean::new()
+impl<T: Float> core::default::Default for Mean<T> {
+    fn default() -> Mean<T> {
+        Mean { avg: T::zero(), n: 0 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + core::fmt::Display> core::fmt::Display for Mean<T> {
+    /// Format the estimator like `mean=3 (n=5)`.
+    ///
+    /// The precision of the formatter is applied to the mean.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "mean={:.*} (n={})", p, self.mean(), self.len()),
+            None => write!(f, "mean={} (n={})", self.mean(), self.len()),
+        }
     }
 }
 
 impl Estimate for Mean {
     #[inline]
     fn add(&mut self, sample: f64) {
-        self.increment();
-        let delta_n = (sample - self.avg)
-            / f64::approx_from(self.n).unwrap();
-        self.add_inner(delta_n);
+        Mean::add(self, sample);
     }
 
     fn estimate(&self) -> f64 {
@@ -89,7 +229,7 @@ impl Estimate for Mean {
     }
 }
 
-impl Merge for Mean {
+impl<T: Float> Merge for Mean<T> {
     /// Merge another sample into this one.
     ///
     ///
@@ -107,12 +247,19 @@ impl Merge for Mean {
     /// assert_eq!(avg_total.mean(), avg_left.mean());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Mean) {
+    fn merge(&mut self, other: &Mean<T>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
         // This algorithm was proposed by Chan et al. in 1979.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = f64::approx_from(self.n).unwrap();
-        let len_other = f64::approx_from(other.n).unwrap();
+        let len_self: T = to_float(self.n);
+        let len_other: T = to_float(other.n);
         let len_total = len_self + len_other;
         self.n += other.n;
         self.avg = (len_self * self.avg + len_other * other.avg) / len_total;
@@ -124,4 +271,4 @@ impl Merge for Mean {
     }
 }
 
-impl_from_iterator!(Mean);
+impl_float_from_iterator!(Mean);
@@ -1,8 +1,57 @@
 use core;
 
-use conv::ApproxFrom;
+use num_traits::{Float, ToPrimitive};
 
 use super::{Estimate, Merge};
+use super::distributions::student_t_quantile;
+
+/// Convert a sample size to a floating point number.
+#[inline]
+fn to_float<T: Float>(n: u64) -> T {
+    T::from(n).unwrap()
+}
+
+/// Implement `FromIterator<T>` for an iterative estimator that is generic over
+/// the floating point type `T`.
+macro_rules! impl_float_from_iterator {
+    ( $name:ident ) => {
+        impl<T: Float> ::core::iter::FromIterator<T> for $name<T> {
+            fn from_iter<I>(iter: I) -> $name<T>
+                where I: IntoIterator<Item=T>
+            {
+                let mut e = $name::default();
+                for i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+
+        impl<'a, T: Float> ::core::iter::FromIterator<&'a T> for $name<T> {
+            fn from_iter<I>(iter: I) -> $name<T>
+                where I: IntoIterator<Item=&'a T>
+            {
+                let mut e = $name::default();
+                for &i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+
+        impl<'a, 'b, T: Float> ::core::iter::FromIterator<&'a &'b T> for $name<T> {
+            fn from_iter<I>(iter: I) -> $name<T>
+                where I: IntoIterator<Item=&'a &'b T>
+            {
+                let mut e = $name::default();
+                for &&i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+    };
+}
 
 include!("mean.rs");
 include!("variance.rs");
@@ -11,7 +60,7 @@ include!("skewness.rs");
 include!("kurtosis.rs");
 
 /// Alias for `Variance`.
-pub type MeanWithError = Variance;
+pub type MeanWithError<T = f64> = Variance<T>;
 
 /// Define an estimator of all moments up to a number given at compile time.
 ///
@@ -22,6 +71,13 @@ pub type MeanWithError = Variance;
 /// (In practise, there is an upper limit due to integer overflow and possibly
 /// numerical issues.)
 ///
+/// The binomial coefficients used by the update overflow `u64` for orders
+/// larger than about 60. Long before that, the estimates of higher moments
+/// become unreliable: They are dominated by the few most extreme samples, and
+/// the update suffers from cancellation, so the relative error grows quickly
+/// with the order. Orders up to about 8 are unproblematic for well-scaled
+/// data.
+///
 /// [paper]: https://doi.org/10.1007/s00180-015-0637-z.
 /// [`Mean`]: ./struct.Mean.html
 /// [`Variance`]: ./struct.Variance.html
@@ -33,7 +89,6 @@ pub type MeanWithError = Variance;
 ///
 /// ```
 /// # extern crate core;
-/// # extern crate conv;
 /// # extern crate num_traits;
 /// #[cfg(feature = "serde1")]
 /// extern crate serde;
@@ -62,7 +117,7 @@ pub type MeanWithError = Variance;
 #[macro_export]
 macro_rules! define_moments {
     ($name:ident, $MAX_MOMENT:expr) => (
-        use ::conv::ApproxFrom;
+        use ::num_traits::ToPrimitive;
         use ::num_traits::pow;
 
         /// An iterator over binomial coefficients.
@@ -79,7 +134,7 @@ macro_rules! define_moments {
                 IterBinomial {
                     k: 0,
                     a: 1,
-                    n: n,
+                    n,
                 }
             }
         }
@@ -133,6 +188,12 @@ macro_rules! define_moments {
                 }
             }
 
+            /// Reset the estimator to an empty sample.
+            #[inline]
+            pub fn reset(&mut self) {
+                *self = $name::new();
+            }
+
             /// Determine whether the sample is empty.
             #[inline]
             pub fn is_empty(&self) -> bool {
@@ -154,9 +215,13 @@ macro_rules! define_moments {
             }
 
             /// Estimate the `p`th central moment of the population.
+            ///
+            /// Panics if `p` is larger than the maximal order of the
+            /// estimator.
             #[inline]
             pub fn central_moment(&self, p: usize) -> f64 {
-                let n = f64::approx_from(self.n).unwrap();
+                assert!(p <= MAX_MOMENT, "order of the moment is too large");
+                let n = self.n.to_f64().unwrap();
                 match p {
                     0 => 1.,
                     1 => 0.,
@@ -167,8 +232,10 @@ macro_rules! define_moments {
             /// Estimate the `p`th standardized moment of the population.
             #[inline]
             pub fn standardized_moment(&self, p: usize) -> f64 {
+                #[allow(unused_imports)]
+                use ::num_traits::Float;
                 match p {
-                    0 => f64::approx_from(self.n).unwrap(),
+                    0 => self.n.to_f64().unwrap(),
                     1 => 0.,
                     2 => 1.,
                     _ => {
@@ -187,16 +254,18 @@ macro_rules! define_moments {
                 if self.n < 2 {
                     return 0.;
                 }
-                self.m[0] / f64::approx_from(self.n - 1).unwrap()
+                self.m[0] / (self.n - 1).to_f64().unwrap()
             }
 
             /// Calculate the sample skewness.
             #[inline]
             pub fn sample_skewness(&self) -> f64 {
+                #[allow(unused_imports)]
+                use ::num_traits::Float;
                 if self.n < 2 {
                     return 0.;
                 }
-                let n = f64::approx_from(self.n).unwrap();
+                let n = self.n.to_f64().unwrap();
                 if self.n < 3 {
                     // Method of moments
                     return self.central_moment(3) /
@@ -213,7 +282,7 @@ macro_rules! define_moments {
                 if self.n < 4 {
                     return 0.;
                 }
-                let n = f64::approx_from(self.n).unwrap();
+                let n = self.n.to_f64().unwrap();
                 (n + 1.) * n * self.central_moment(4) /
                     ((n - 1.) * (n - 2.) * (n - 3.) * pow(self.central_moment(2), 2)) -
                     3. * pow(n - 1., 2) / ((n - 2.) * (n - 3.))
@@ -224,7 +293,7 @@ macro_rules! define_moments {
             pub fn add(&mut self, x: f64) {
                 self.n += 1;
                 let delta = x - self.avg;
-                let n = f64::approx_from(self.n).unwrap();
+                let n = self.n.to_f64().unwrap();
                 self.avg += delta / n;
 
                 let mut coeff_delta = delta;
@@ -248,7 +317,7 @@ macro_rules! define_moments {
                     binom.next().unwrap();  // Skip k = 0.
                     for k in 1..(p - 1) {
                         coeff *= factor_coeff;
-                        self.m[p - 2] += f64::approx_from(binom.next().unwrap()).unwrap() *
+                        self.m[p - 2] += binom.next().unwrap().to_f64().unwrap() *
                             prev_m[p - 2 - k] * coeff;
                     }
                 }
@@ -258,12 +327,19 @@ macro_rules! define_moments {
         impl $crate::Merge for $name {
             #[inline]
             fn merge(&mut self, other: &$name) {
-                let n_a = f64::approx_from(self.n).unwrap();
-                let n_b = f64::approx_from(other.n).unwrap();
+                if other.n == 0 {
+                    return;
+                }
+                if self.n == 0 {
+                    *self = other.clone();
+                    return;
+                }
+                let n_a = self.n.to_f64().unwrap();
+                let n_b = other.n.to_f64().unwrap();
                 let delta = other.avg - self.avg;
 
                 self.n += other.n;
-                let n = f64::approx_from(self.n).unwrap();
+                let n = self.n.to_f64().unwrap();
                 let n_a_over_n = n_a / n;
                 let n_b_over_n = n_b / n;
                 self.avg += n_b_over_n * delta;
@@ -288,7 +364,7 @@ macro_rules! define_moments {
                         coeff_b *= n_a_over_n;
                         coeff_delta *= delta;
                         self.m[p - 2] +=
-                            f64::approx_from(binom.next().unwrap()).unwrap() *
+                            binom.next().unwrap().to_f64().unwrap() *
                             coeff_delta * (prev_m[p - 2 - k] * coeff_a +
                             other.m[p - 2 - k] * coeff_b);
                     }
@@ -302,6 +378,6 @@ macro_rules! define_moments {
             }
         }
 
-        impl_from_iterator!($name);
+        $crate::impl_from_iterator!($name);
     );
 }
@@ -21,6 +21,12 @@ impl Skewness {
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Skewness::new();
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -39,7 +45,7 @@ impl Skewness {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         let term = delta * delta_n * (n - 1.);
         self.sum_3 += term * delta_n * (n - 2.)
             - 3.*delta_n * self.avg.sum_2;
@@ -66,6 +72,13 @@ impl Skewness {
         self.avg.len()
     }
 
+    /// Determine whether the sample size exceeds the range of integers that
+    /// can be represented exactly, see `Mean::len_exhausted`.
+    #[inline]
+    pub fn len_exhausted(&self) -> bool {
+        self.avg.len_exhausted()
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -82,6 +95,33 @@ impl Skewness {
         self.avg.population_variance()
     }
 
+    /// Calculate the second central moment of the sample.
+    ///
+    /// This is the sum of squared deviations from the mean divided by the
+    /// sample size, i.e. the population variance. Returns 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn central_moment_2(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.avg.sum_2 / n.to_f64().unwrap()
+    }
+
+    /// Calculate the third central moment of the sample.
+    ///
+    /// This is the sum of cubed deviations from the mean divided by the
+    /// sample size. Returns 0 for an empty sample.
+    #[inline]
+    pub fn central_moment_3(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return 0.;
+        }
+        self.sum_3 / n.to_f64().unwrap()
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
     pub fn error_mean(&self) -> f64 {
@@ -94,11 +134,38 @@ impl Skewness {
         if self.sum_3 == 0. {
             return 0.;
         }
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         let sum_2 = self.avg.sum_2;
         debug_assert_ne!(sum_2, 0.);
         n.sqrt() * self.sum_3 / (sum_2*sum_2*sum_2).sqrt()
     }
+
+    /// Estimate the standard error of the skewness, assuming the population
+    /// is normally distributed.
+    ///
+    /// This is calculated as `sqrt(6n(n - 1) / ((n - 2)(n + 1)(n + 3)))`.
+    /// Returns 0 if the sample size is smaller than 3.
+    #[inline]
+    pub fn standard_error(&self) -> f64 {
+        if self.len() < 3 {
+            return 0.;
+        }
+        let n = self.len().to_f64().unwrap();
+        (6. * n * (n - 1.) / ((n - 2.) * (n + 1.) * (n + 3.))).sqrt()
+    }
+
+    /// Calculate the z-score of the skewness, i.e. the skewness divided by its
+    /// standard error.
+    ///
+    /// Large absolute values indicate that the population is significantly
+    /// skewed. Returns 0 if the sample size is smaller than 3.
+    #[inline]
+    pub fn z_score(&self) -> f64 {
+        if self.len() < 3 {
+            return 0.;
+        }
+        self.skewness() / self.standard_error()
+    }
 }
 
 impl Default for Skewness {
@@ -112,7 +179,7 @@ impl Estimate for Skewness {
     fn add(&mut self, x: f64) {
         let delta = x - self.mean();
         self.increment();
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.len().to_f64().unwrap();
         self.add_inner(delta, delta/n);
     }
 
@@ -125,8 +192,15 @@ impl Estimate for Skewness {
 impl Merge for Skewness {
     #[inline]
     fn merge(&mut self, other: &Skewness) {
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        let len_self = self.len().to_f64().unwrap();
+        let len_other = other.len().to_f64().unwrap();
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
@@ -12,20 +12,34 @@
 /// let a: Variance = (1..6).map(f64::from).collect();
 /// println!("The mean is {} ± {}.", a.mean(), a.error());
 /// ```
-#[derive(Debug, Clone)]
+///
+/// Like `Mean`, the estimator is generic over the floating point type, which
+/// defaults to `f64`. Estimators for other types can be created with
+/// `Default`.
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Variance {
+pub struct Variance<T = f64> {
     /// Estimator of average.
-    avg: Mean,
+    avg: Mean<T>,
     /// Intermediate sum of squares for calculating the variance.
-    sum_2: f64,
+    sum_2: T,
 }
 
 impl Variance {
     /// Create a new variance estimator.
+    ///
+    /// Use `Default` to create an estimator for another floating point type.
     #[inline]
     pub fn new() -> Variance {
-        Variance { avg: Mean::new(), sum_2: 0. }
+        Variance::default()
+    }
+}
+
+impl<T: Float> Variance<T> {
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Variance::default();
     }
 
     /// Increment the sample size.
@@ -42,14 +56,14 @@ impl Variance {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: T) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.avg.len()).unwrap();
+        let n: T = to_float(self.avg.len());
         self.avg.add_inner(delta_n);
-        self.sum_2 += delta_n * delta_n * n * (n - 1.);
+        self.sum_2 = self.sum_2 + delta_n * delta_n * n * (n - T::one());
     }
 
     /// Determine whether the sample is empty.
@@ -62,7 +76,7 @@ impl Variance {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> T {
         self.avg.mean()
     }
 
@@ -72,54 +86,274 @@ impl Variance {
         self.avg.len()
     }
 
+    /// Determine whether the sample size exceeds the range of integers that
+    /// can be represented exactly, see `Mean::len_exhausted`.
+    #[inline]
+    pub fn len_exhausted(&self) -> bool {
+        self.avg.len_exhausted()
+    }
+
+    /// Calculate the sum of the observations, i.e. `mean() * len()`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum(&self) -> T {
+        self.avg.sum()
+    }
+
+    /// Return the sum of the squared deviations from the mean.
+    ///
+    /// This is the accumulator the variances are calculated from, i.e. the
+    /// sample variance is `sum_of_squares_centered() / (len() - 1)`. Returns 0
+    /// for an empty sample.
+    #[inline]
+    pub fn sum_of_squares_centered(&self) -> T {
+        self.sum_2
+    }
+
+    /// Add all observations of a slice.
+    ///
+    /// This is equivalent to calling `add` for each element, but faster. The
+    /// mean and the sum of squares of the slice are calculated in two passes
+    /// and then merged into this sample.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[T]) {
+        if xs.is_empty() {
+            return;
+        }
+        let mut avg = Mean::default();
+        avg.add_slice(xs);
+        let mean = avg.mean();
+        let sum_2 = xs.iter()
+            .fold(T::zero(), |sum, &x| sum + (x - mean) * (x - mean));
+        self.merge(&Variance { avg, sum_2 });
+    }
+
+    /// Remove an observation that was previously added to the sample.
+    ///
+    /// This reverses the effect of `add`. The result is only meaningful if `x`
+    /// was actually added before. Note that rounding errors can accumulate if
+    /// many observations are added and removed.
+    ///
+    /// Panics if the sample is empty.
+    #[inline]
+    pub fn remove(&mut self, x: T) {
+        let prev_mean = self.mean();
+        self.avg.remove(x);
+        if self.avg.is_empty() {
+            self.sum_2 = T::zero();
+            return;
+        }
+        self.sum_2 = self.sum_2 - (x - self.avg.mean()) * (x - prev_mean);
+        // Rounding errors must not result in a negative variance.
+        self.sum_2 = self.sum_2.max(T::zero());
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
     #[inline]
-    pub fn sample_variance(&self) -> f64 {
+    pub fn sample_variance(&self) -> T {
         if self.avg.len() < 2 {
-            return 0.;
+            return T::zero();
         }
-        self.sum_2 / f64::approx_from(self.avg.len() - 1).unwrap()
+        self.sum_2 / to_float(self.avg.len() - 1)
     }
 
     /// Calculate the population variance of the sample.
     ///
     /// This is a biased estimator of the variance of the population.
     #[inline]
-    pub fn population_variance(&self) -> f64 {
+    pub fn population_variance(&self) -> T {
         let n = self.avg.len();
         if n < 2 {
-            return 0.;
+            return T::zero();
         }
-        self.sum_2 / f64::approx_from(n).unwrap()
+        self.sum_2 / to_float(n)
+    }
+
+    /// Calculate the sample standard deviation.
+    ///
+    /// This is the square root of the sample variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn sample_standard_deviation(&self) -> T {
+        self.sample_variance().sqrt()
+    }
+
+    /// Calculate the population standard deviation of the sample.
+    ///
+    /// This is the square root of the population variance. Returns 0 if the
+    /// sample size is smaller than 2.
+    #[inline]
+    pub fn population_standard_deviation(&self) -> T {
+        self.population_variance().sqrt()
     }
 
     /// Estimate the standard error of the mean of the population.
+    ///
+    /// This is the same as `standard_error_of_mean`.
     #[inline]
-    pub fn error(&self) -> f64 {
+    pub fn error(&self) -> T {
+        self.standard_error_of_mean()
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    ///
+    /// This is calculated as `sqrt(s² / n)`, where `s²` is the (unbiased)
+    /// sample variance and `n` the sample size. Returns 0 if the sample size
+    /// is smaller than 2.
+    #[inline]
+    pub fn standard_error_of_mean(&self) -> T {
         let n = self.avg.len();
-        if n == 0 {
-            return 0.;
+        if n < 2 {
+            return T::zero();
         }
-        (self.sample_variance() / f64::approx_from(n).unwrap()).sqrt()
+        (self.sample_variance() / to_float(n)).sqrt()
     }
 
+    /// Estimate the relative standard error of the mean of the population.
+    ///
+    /// This is calculated as `standard_error_of_mean() / mean().abs()`. It is
+    /// only meaningful if the mean is clearly nonzero: When the mean is close
+    /// to zero, the relative error grows without bounds, and it is infinite
+    /// for a mean of zero. Returns infinity if the sample size is smaller
+    /// than 2, and 0 if all observations are equal.
+    #[inline]
+    pub fn relative_standard_error(&self) -> T {
+        if self.avg.len() < 2 {
+            return T::infinity();
+        }
+        let error = self.standard_error_of_mean();
+        if error == T::zero() {
+            return T::zero();
+        }
+        error / self.mean().abs()
+    }
+
+    /// Determine whether the relative standard error of the mean is at most
+    /// `rel_tol`.
+    ///
+    /// This can be used to stop sampling once the mean is known precisely
+    /// enough. It is never true for less than 2 observations. If the mean
+    /// might be close to zero, a tolerance on the absolute error (`error()`)
+    /// should be used instead.
+    #[inline]
+    pub fn has_converged(&self, rel_tol: T) -> bool {
+        self.relative_standard_error() <= rel_tol
+    }
+
+    /// Calculate the confidence interval `(lower, upper)` of the mean of the
+    /// population for the given confidence level (e.g. `0.95`).
+    ///
+    /// This uses Student's t-distribution with `n - 1` degrees of freedom,
+    /// assuming the population is normally distributed. The quantile of the
+    /// t-distribution is approximated to about six significant digits.
+    /// Returns `(mean, mean)` if the sample size is smaller than 2.
+    ///
+    /// Panics if `confidence` is not between 0 and 1.
+    #[inline]
+    pub fn confidence_interval(&self, confidence: f64) -> (T, T) {
+        assert!(0. < confidence && confidence < 1.);
+        let mean = self.mean();
+        let n = self.len();
+        if n < 2 {
+            return (mean, mean);
+        }
+        let t: T = T::from(student_t_quantile(0.5 * (1. + confidence), n - 1))
+            .unwrap();
+        let delta = t * self.standard_error_of_mean();
+        (mean - delta, mean + delta)
+    }
+
+    /// Calculate the sample coefficient of variation.
+    ///
+    /// This is the sample standard deviation divided by the mean. Returns 0
+    /// for an empty sample and `NaN` if the mean is zero.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        let mean = self.mean();
+        if mean == T::zero() {
+            return T::nan();
+        }
+        self.sample_variance().sqrt() / mean
+    }
+
+    /// Standardize `x` using the sample mean and the sample standard
+    /// deviation, i.e. calculate its z-score `(x - mean) / std`.
+    ///
+    /// This uses the statistics of the sample at the time of the call, so it
+    /// is meant to be called after all observations were added, or on a
+    /// held-out point. If the sample variance is zero (e.g. for a sample size
+    /// smaller than 2), the standard deviation is treated as 1 and `x - mean`
+    /// is returned.
+    #[inline]
+    pub fn standardize(&self, x: T) -> T {
+        let std = self.sample_variance().sqrt();
+        if std == T::zero() {
+            return x - self.mean();
+        }
+        (x - self.mean()) / std
+    }
+
+    /// Standardize all values of a slice in place.
+    ///
+    /// This is equivalent to calling `standardize` for each element, see
+    /// there for details.
+    #[inline]
+    pub fn standardize_slice(&self, xs: &mut [T]) {
+        let mean = self.mean();
+        let mut std = self.sample_variance().sqrt();
+        if std == T::zero() {
+            std = T::one();
+        }
+        for x in xs {
+            *x = (*x - mean) / std;
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// This is the same as `Estimate::add`, but it is also available for
+    /// floating point types other than `f64`. It returns the estimator, so
+    /// calls can be chained.
+    #[inline]
+    pub fn add(&mut self, sample: T) -> &mut Self {
+        self.increment();
+        let delta_n = (sample - self.avg.mean()) / to_float(self.len());
+        self.add_inner(delta_n);
+        self
+    }
 }
 
-impl core::default::Default for Variance {
-    fn default() -> Variance {
-        Variance::new()
+impl<T: Float> core::default::Default for Variance<T> {
+    fn default() -> Variance<T> {
+        Variance { avg: Mean::default(), sum_2: T::zero() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + core::fmt::Display> core::fmt::Display for Variance<T> {
+    /// Format the mean and its standard error like `mean=3 ± 0.7 (n=5)`.
+    ///
+    /// The precision of the formatter is applied to both numbers.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "mean={:.*} ± {:.*} (n={})",
+                              p, self.mean(), p, self.error(), self.len()),
+            None => write!(f, "mean={} ± {} (n={})",
+                           self.mean(), self.error(), self.len()),
+        }
     }
 }
 
 impl Estimate for Variance {
     #[inline]
     fn add(&mut self, sample: f64) {
-        self.increment();
-        let delta_n = (sample - self.avg.mean())
-            / f64::approx_from(self.len()).unwrap();
-        self.add_inner(delta_n);
+        Variance::add(self, sample);
     }
 
     #[inline]
@@ -128,7 +362,7 @@ impl Estimate for Variance {
     }
 }
 
-impl Merge for Variance {
+impl<T: Float> Merge for Variance<T> {
     /// Merge another sample into this one.
     ///
     ///
@@ -147,17 +381,25 @@ impl Merge for Variance {
     /// assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Variance) {
+    fn merge(&mut self, other: &Variance<T>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
         // This algorithm was proposed by Chan et al. in 1979.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
+        let len_self: T = to_float(self.len());
+        let len_other: T = to_float(other.len());
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         self.avg.merge(&other.avg);
-        self.sum_2 += other.sum_2 + delta*delta * len_self * len_other / len_total;
+        self.sum_2 = self.sum_2
+            + other.sum_2 + delta*delta * len_self * len_other / len_total;
     }
 }
 
-impl_from_iterator!(Variance);
+impl_float_from_iterator!(Variance);
@@ -0,0 +1,463 @@
+use num_traits::ToPrimitive;
+use super::Estimate;
+
+/// Estimate the exponentially weighted moving average of a sequence of
+/// numbers.
+///
+/// Each observation `x` updates the estimate to `alpha * x + (1 - alpha) *
+/// mean`, so older observations are forgotten exponentially. The first
+/// observation initializes the mean.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ExpMovingMean, Estimate};
+///
+/// let mut a = ExpMovingMean::new(0.5);
+/// a.add(1.);
+/// a.add(3.);
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ExpMovingMean {
+    /// Smoothing factor.
+    alpha: f64,
+    /// Moving mean value.
+    avg: f64,
+    /// Moving mean value initialized with 0 instead of the first observation.
+    zero_avg: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl ExpMovingMean {
+    /// Create a new exponentially weighted moving mean estimator with the
+    /// given smoothing factor.
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    #[inline]
+    pub fn new(alpha: f64) -> ExpMovingMean {
+        assert!(0. < alpha && alpha <= 1.);
+        ExpMovingMean { alpha, avg: 0., zero_avg: 0., n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The smoothing factor is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.avg = 0.;
+        self.zero_avg = 0.;
+        self.n = 0;
+    }
+
+    /// Create a new exponentially weighted moving mean estimator, such that
+    /// the weight of an observation halves after `half_life` further
+    /// observations.
+    ///
+    /// Panics if `half_life` is not positive.
+    #[inline]
+    pub fn with_half_life(half_life: f64) -> ExpMovingMean {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!(half_life > 0.);
+        ExpMovingMean::new(1. - 0.5f64.powf(1. / half_life))
+    }
+
+    /// Return the smoothing factor.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the moving mean of the sequence.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg
+    }
+
+    /// Estimate the bias-corrected moving mean of the sequence.
+    ///
+    /// Like for the Adam optimizer, a moving mean initialized with 0 is
+    /// divided by `1 - (1 - alpha)^t`, where `t` is the sample size. This is
+    /// the average of the observations weighted by `(1 - alpha)^k`, where `k`
+    /// is the number of later observations. In contrast, `mean` gives the
+    /// first observation the weight `(1 - alpha)^(t - 1) / alpha`, which
+    /// overrepresents it during the warm-up.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn bias_corrected_mean(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        if self.n == 0 {
+            return 0.;
+        }
+        // `1 - (1 - alpha)^t`, avoiding cancellation for small `alpha`.
+        let correction = -(self.n.to_f64().unwrap() * (-self.alpha).ln_1p()).exp_m1();
+        self.zero_avg / correction
+    }
+}
+
+impl Estimate for ExpMovingMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        if self.n == 0 {
+            self.avg = x;
+        } else {
+            self.avg += self.alpha * (x - self.avg);
+        }
+        self.zero_avg += self.alpha * (x - self.zero_avg);
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+/// Estimate the exponentially weighted moving mean and variance of a sequence
+/// of numbers.
+///
+/// This uses the same smoothing factor for the mean and the variance. Like the
+/// moving mean, the estimate adapts to changes of the underlying distribution,
+/// but it is biased: For a stationary sequence with variance `σ²`, the expected
+/// estimate approaches `2 (1 - alpha) / (2 - alpha) σ² ≈ (1 - alpha/2) σ²`
+/// instead of `σ²`. It also fluctuates more than the variance over a sliding
+/// window of comparable length `~2/alpha`.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ExpMovingVariance, Estimate};
+///
+/// let mut a = ExpMovingVariance::new(0.1);
+/// for &x in &[1., 3., 2., 4., 3.] {
+///     a.add(x);
+/// }
+/// println!("The moving mean is {} ± {}.", a.mean(), a.standard_deviation());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ExpMovingVariance {
+    /// Estimator of the moving average.
+    avg: ExpMovingMean,
+    /// Moving variance value.
+    var: f64,
+}
+
+impl ExpMovingVariance {
+    /// Create a new exponentially weighted moving variance estimator with the
+    /// given smoothing factor.
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    #[inline]
+    pub fn new(alpha: f64) -> ExpMovingVariance {
+        ExpMovingVariance { avg: ExpMovingMean::new(alpha), var: 0. }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The smoothing factor is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.avg.reset();
+        self.var = 0.;
+    }
+
+    /// Create a new exponentially weighted moving variance estimator, such
+    /// that the weight of an observation halves after `half_life` further
+    /// observations.
+    ///
+    /// Panics if `half_life` is not positive.
+    #[inline]
+    pub fn with_half_life(half_life: f64) -> ExpMovingVariance {
+        ExpMovingVariance { avg: ExpMovingMean::with_half_life(half_life), var: 0. }
+    }
+
+    /// Return the smoothing factor.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.avg.alpha()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Estimate the moving mean of the sequence.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Estimate the moving variance of the sequence.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.var
+    }
+
+    /// Estimate the moving standard deviation of the sequence.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn standard_deviation(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        self.var.sqrt()
+    }
+}
+
+impl Estimate for ExpMovingVariance {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        // This algorithm was described by Finch in 2009.
+        //
+        // See http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
+        if self.avg.is_empty() {
+            self.avg.add(x);
+            return;
+        }
+        let alpha = self.alpha();
+        let delta = x - self.avg.mean();
+        let incr = alpha * delta;
+        self.avg.add(x);
+        self.var = (1. - alpha) * (self.var + delta * incr);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.variance()
+    }
+}
+
+/// Define an estimator of the mean over a sliding window with a number of
+/// samples known at compile time.
+///
+/// Because macros are not hygenic for items, everything is defined in a private
+/// module with the given name. This includes the `SlidingMean` struct and the
+/// window size `LEN`, which has to be positive.
+///
+/// The samples of the window are stored in a ring buffer, and a running sum is
+/// kept such that `add` and `mean` are O(1). To avoid accumulating rounding
+/// errors over many updates, the sum is recalculated from the buffer each time
+/// the window was completely replaced.
+///
+/// This does not require the `alloc` feature, which provides `SlidingMean` for
+/// a window size known at runtime.
+///
+/// Note that you need to make sure that `core` is accessible to the macro.
+///
+///
+/// # Example
+///
+/// ```
+/// # extern crate core;
+/// # #[macro_use] extern crate average;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_derive;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_big_array;
+/// # fn main() {
+/// use average::Estimate;
+///
+/// define_sliding_mean!(window, 3);
+/// let mut a = window::SlidingMean::new();
+/// for i in 1..6 {
+///     a.add(f64::from(i));
+/// }
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.mean(), 4.);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_sliding_mean {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            #[cfg(feature = "serde1")] big_array! {
+                BigArray; LEN,
+            }
+
+            /// The number of samples in the window.
+            const LEN: usize = $LEN;
+
+            /// Estimate the mean over a sliding window with a number of
+            /// samples known at compile time.
+            #[derive(Clone)]
+            #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+            pub struct SlidingMean {
+                /// Ring buffer of the samples in the window.
+                #[cfg_attr(feature = "serde1", serde(with = "BigArray"))]
+                buf: [f64; LEN],
+                /// Index of the oldest sample, once the window is full.
+                head: usize,
+                /// Number of samples in the window.
+                len: usize,
+                /// Running sum of the samples in the window.
+                sum: f64,
+            }
+
+            impl ::core::fmt::Debug for SlidingMean {
+                fn fmt(&self, formatter: &mut ::core::fmt::Formatter)
+                    -> ::core::fmt::Result {
+                    formatter.write_str("SlidingMean { buf: ")?;
+                    // Print the samples from the oldest to the newest.
+                    formatter.debug_list()
+                        .entries(self.buf[self.head..self.len].iter()
+                                 .chain(&self.buf[..self.head]))
+                        .finish()?;
+                    formatter.write_str(", sum: ")?;
+                    self.sum.fmt(formatter)?;
+                    formatter.write_str(" }")
+                }
+            }
+
+            impl SlidingMean {
+                /// Create a new sliding mean estimator.
+                #[inline]
+                pub fn new() -> SlidingMean {
+                    SlidingMean {
+                        buf: [0.; LEN],
+                        head: 0,
+                        len: 0,
+                        sum: 0.,
+                    }
+                }
+
+                /// Reset the estimator to an empty window.
+                #[inline]
+                pub fn reset(&mut self) {
+                    *self = SlidingMean::new();
+                }
+
+                /// Return the maximal number of samples in the window.
+                #[inline]
+                pub fn capacity(&self) -> usize {
+                    LEN
+                }
+
+                /// Determine whether the window is empty.
+                #[inline]
+                pub fn is_empty(&self) -> bool {
+                    self.len == 0
+                }
+
+                /// Return the number of samples in the window.
+                #[inline]
+                pub fn len(&self) -> usize {
+                    self.len
+                }
+
+                /// Estimate the mean of the samples in the window.
+                ///
+                /// Returns 0 for an empty window.
+                #[inline]
+                pub fn mean(&self) -> f64 {
+                    if self.len == 0 {
+                        return 0.;
+                    }
+                    self.sum / (self.len as f64)
+                }
+            }
+
+            impl ::core::default::Default for SlidingMean {
+                fn default() -> SlidingMean {
+                    SlidingMean::new()
+                }
+            }
+
+            impl $crate::Estimate for SlidingMean {
+                #[inline]
+                fn add(&mut self, x: f64) {
+                    if self.len < LEN {
+                        self.buf[self.len] = x;
+                        self.len += 1;
+                        self.sum += x;
+                        return;
+                    }
+                    self.sum += x - self.buf[self.head];
+                    self.buf[self.head] = x;
+                    self.head += 1;
+                    if self.head == LEN {
+                        self.head = 0;
+                        // Every sample was replaced, recalculate the sum to
+                        // get rid of the accumulated rounding errors.
+                        self.sum = self.buf.iter().sum();
+                    }
+                }
+
+                #[inline]
+                fn estimate(&self) -> f64 {
+                    self.mean()
+                }
+            }
+
+            impl ::core::iter::FromIterator<f64> for SlidingMean {
+                fn from_iter<T>(iter: T) -> SlidingMean
+                    where T: IntoIterator<Item=f64>
+                {
+                    use $crate::Estimate;
+                    let mut e = SlidingMean::new();
+                    for i in iter {
+                        e.add(i);
+                    }
+                    e
+                }
+            }
+
+            impl<'a> ::core::iter::FromIterator<&'a f64> for SlidingMean {
+                fn from_iter<T>(iter: T) -> SlidingMean
+                    where T: IntoIterator<Item=&'a f64>
+                {
+                    use $crate::Estimate;
+                    let mut e = SlidingMean::new();
+                    for &i in iter {
+                        e.add(i);
+                    }
+                    e
+                }
+            }
+
+            impl<'a, 'b> ::core::iter::FromIterator<&'a &'b f64> for SlidingMean {
+                fn from_iter<T>(iter: T) -> SlidingMean
+                    where T: IntoIterator<Item=&'a &'b f64>
+                {
+                    use $crate::Estimate;
+                    let mut e = SlidingMean::new();
+                    for &&i in iter {
+                        e.add(i);
+                    }
+                    e
+                }
+            }
+        }
+    );
+}
@@ -0,0 +1,153 @@
+use core;
+
+use super::{MeanWithError, Merge};
+
+/// Estimate the mean and the variance of the differences `y - x` of a
+/// sequence of pairs ("population").
+///
+/// This is meant for paired measurements, like the values before (`x`) and
+/// after (`y`) a change for the same subjects. It is equivalent to adding the
+/// differences to a `MeanWithError`, without precomputing them.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::PairedMean;
+///
+/// let before = [10., 12., 9., 11.];
+/// let after = [12., 13., 11., 14.];
+/// let a: PairedMean = before.iter().cloned().zip(after.iter().cloned()).collect();
+/// assert_eq!(a.mean_difference(), 2.);
+/// println!("The paired t-statistic is {}.", a.t_statistic());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PairedMean {
+    /// Estimator of the mean and the variance of the differences.
+    diff: MeanWithError,
+}
+
+impl PairedMean {
+    /// Create a new paired mean estimator.
+    #[inline]
+    pub fn new() -> PairedMean {
+        PairedMean { diff: MeanWithError::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.diff.reset();
+    }
+
+    /// Add an observed pair `(x, y)` sampled from the population.
+    ///
+    /// This returns the estimator, so calls can be chained.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) -> &mut PairedMean {
+        self.diff.add(y - x);
+        self
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+
+    /// Return the number of pairs.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.diff.len()
+    }
+
+    /// Estimate the mean of the differences `y - x`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_difference(&self) -> f64 {
+        self.diff.mean()
+    }
+
+    /// Calculate the sample variance of the differences `y - x`.
+    ///
+    /// This is an unbiased estimator of the variance of the differences in
+    /// the population. Returns 0 if the sample size is smaller than 2.
+    #[inline]
+    pub fn variance_of_difference(&self) -> f64 {
+        self.diff.sample_variance()
+    }
+
+    /// Estimate the standard error of the mean difference.
+    ///
+    /// Returns 0 if the sample size is smaller than 2.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.diff.error()
+    }
+
+    /// Calculate the paired t-statistic `mean_difference / error`.
+    ///
+    /// Under the hypothesis that the mean difference of the population is
+    /// zero, this follows Student's t-distribution with `n - 1` degrees of
+    /// freedom, assuming the differences are normally distributed. Returns 0
+    /// if the sample size is smaller than 2 or if all differences are zero,
+    /// and an infinity if all differences are equal, but nonzero.
+    #[inline]
+    pub fn t_statistic(&self) -> f64 {
+        let mean = self.mean_difference();
+        if self.len() < 2 || mean == 0. {
+            return 0.;
+        }
+        let error = self.error();
+        if error == 0. {
+            return if mean > 0. { core::f64::INFINITY } else { core::f64::NEG_INFINITY };
+        }
+        mean / error
+    }
+
+    /// Return the estimator of the mean and the variance of the differences.
+    #[inline]
+    pub fn differences(&self) -> &MeanWithError {
+        &self.diff
+    }
+}
+
+impl core::default::Default for PairedMean {
+    fn default() -> PairedMean {
+        PairedMean::new()
+    }
+}
+
+impl Merge for PairedMean {
+    /// Merge another sample into this one.
+    #[inline]
+    fn merge(&mut self, other: &PairedMean) {
+        self.diff.merge(&other.diff);
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for PairedMean {
+    fn from_iter<T>(iter: T) -> PairedMean
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = PairedMean::new();
+        for (x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for PairedMean {
+    fn from_iter<T>(iter: T) -> PairedMean
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = PairedMean::new();
+        for &(x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
@@ -0,0 +1,42 @@
+use rayon::iter::ParallelIterator;
+
+use super::{Estimate, Merge};
+
+/// Estimate a statistic of a parallel iterator.
+///
+/// The iterator is split across threads, each of which builds a partial
+/// estimator with `add`. The partial estimators are then combined with
+/// `merge`. For an estimator type `E`, this is equivalent to (but faster
+/// than) collecting the sequential iterator into `E`, up to rounding errors.
+///
+/// This requires the `rayon` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// # extern crate rayon;
+/// # extern crate average;
+/// # fn main() {
+/// use rayon::prelude::*;
+/// use average::{Variance, par_reduce};
+///
+/// let values: Vec<f64> = (1..10_001).map(f64::from).collect();
+/// let a: Variance = par_reduce(values.par_iter().cloned());
+/// assert_eq!(a.mean(), 5000.5);
+/// # }
+/// ```
+pub fn par_reduce<E, I>(iter: I) -> E
+    where E: Estimate + Merge + Default + Send,
+          I: ParallelIterator<Item = f64>
+{
+    iter
+        .fold(E::default, |mut e, x| {
+            e.add(x);
+            e
+        })
+        .reduce(E::default, |mut a, b| {
+            a.merge(&b);
+            a
+        })
+}
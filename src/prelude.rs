@@ -0,0 +1,19 @@
+//! Commonly used estimators, traits and macros.
+//!
+//! This is meant to be glob imported:
+//!
+//! ```
+//! use average::prelude::*;
+//!
+//! let a: Variance = (1..6).map(f64::from).collect();
+//! let mut b: Variance = (6..11).map(f64::from).collect();
+//! b.merge(&a);
+//! assert_eq!(b.mean(), 5.5);
+//! ```
+
+pub use {Estimate, Merge, Histogram};
+pub use {Mean, MeanWithError, Variance, CoVariance, Skewness, Kurtosis};
+pub use {WeightedMean, WeightedMeanWithError};
+pub use {Min, Max, Quantile, Sum};
+pub use {concatenate, define_histogram, define_moments, define_quantiles};
+pub use define_sliding_mean;
@@ -1,10 +1,10 @@
 use core;
 use core::cmp::min;
 
-use conv::{ApproxFrom, ConvAsUtil, ConvUtil, ValueFrom};
 use float_ord::sort as sort_floats;
+use num_traits::ToPrimitive;
 
-use super::Estimate;
+use super::{Estimate, Merge};
 
 /// Estimate the p-quantile of a sequence of numbers ("population").
 ///
@@ -14,7 +14,7 @@ use super::Estimate;
 /// recommended over this algorithm for small samples.
 // This uses the P² algorithm introduced here:
 // http://www.cs.wustl.edu/~jain/papers/ftp/psqr.pdf
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct Quantile {
     /// Marker heights.
@@ -33,7 +33,7 @@ impl Quantile {
     /// Panics if `p` is not between 0 and 1.
     #[inline]
     pub fn new(p: f64) -> Quantile {
-        assert!(0. <= p && p <= 1.);
+        assert!((0. ..=1.).contains(&p));
         Quantile {
             q: [0.; 5],
             n: [1, 2, 3, 4, 0],
@@ -42,6 +42,14 @@ impl Quantile {
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The value of `p` is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Quantile::new(self.p());
+    }
+
     /// Return the value of `p` for this p-quantile.
     #[inline]
     pub fn p(&self) -> f64 {
@@ -52,14 +60,14 @@ impl Quantile {
     #[inline]
     fn parabolic(&self, i: usize, d: f64) -> f64 {
         debug_assert_eq!(d.abs(), 1.);
-        let s: i64 = d.approx().unwrap();
-        self.q[i] + d / f64::approx_from(self.n[i + 1] - self.n[i - 1]).unwrap()
-            * (f64::approx_from(self.n[i] - self.n[i - 1] + s).unwrap()
+        let s = d.to_i64().unwrap();
+        self.q[i] + d / (self.n[i + 1] - self.n[i - 1]).to_f64().unwrap()
+            * ((self.n[i] - self.n[i - 1] + s).to_f64().unwrap()
                * (self.q[i + 1] - self.q[i])
-               / f64::approx_from(self.n[i + 1] - self.n[i]).unwrap()
-               + f64::approx_from(self.n[i + 1] - self.n[i] - s).unwrap()
+               / (self.n[i + 1] - self.n[i]).to_f64().unwrap()
+               + (self.n[i + 1] - self.n[i] - s).to_f64().unwrap()
                * (self.q[i] - self.q[i - 1])
-               / f64::approx_from(self.n[i] - self.n[i - 1]).unwrap())
+               / (self.n[i] - self.n[i - 1]).to_f64().unwrap())
     }
 
     /// Linear prediction for marker height.
@@ -68,7 +76,7 @@ impl Quantile {
         debug_assert_eq!(d.abs(), 1.);
         let sum = if d < 0. { i - 1 } else { i + 1 };
         self.q[i] + d * (self.q[sum] - self.q[i])
-            / f64::approx_from(self.n[sum] - self.n[i]).unwrap()
+            / (self.n[sum] - self.n[i]).to_f64().unwrap()
     }
 
     /// Estimate the p-quantile of the population.
@@ -76,6 +84,8 @@ impl Quantile {
     /// Returns 0 for an empty sample.
     #[inline]
     pub fn quantile(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
         if self.len() >= 5 {
             return self.q[2];
         }
@@ -87,12 +97,12 @@ impl Quantile {
         let mut heights: [f64; 4] = [
             self.q[0], self.q[1], self.q[2], self.q[3]
         ];
-        let len = usize::value_from(self.len()).unwrap();  // < 5
+        let len = self.len().to_usize().unwrap();  // < 5
         sort_floats(&mut heights[..len]);
-        let desired_index = ConvUtil::approx_as::<f64>(len).unwrap() * self.p() - 1.;
+        let desired_index = len.to_f64().unwrap() * self.p() - 1.;
         let mut index = desired_index.ceil();
         if desired_index == index && index >= 0. {
-            let index: usize = index.approx().unwrap();  // < 5
+            let index = index.to_usize().unwrap();  // < 5
             if index < len - 1 {
                 // `q[index]` and `q[index + 1]` are equally valid estimates,
                 // by convention we take their average.
@@ -100,7 +110,7 @@ impl Quantile {
             }
         }
         index = index.max(0.);
-        let mut index: usize = index.approx().unwrap();  // < 5
+        let mut index = index.to_usize().unwrap();  // < 5
         index = min(index, len - 1);
         self.q[index]
     }
@@ -108,7 +118,7 @@ impl Quantile {
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
-        u64::value_from(self.n[4]).unwrap()  // n[4] >= 0
+        (self.n[4]).to_u64().unwrap()  // n[4] >= 0
     }
 
     /// Determine whether the sample is empty.
@@ -130,7 +140,7 @@ impl Estimate for Quantile {
     fn add(&mut self, x: f64) {
         // n[4] is the sample size.
         if self.n[4] < 5 {
-            self.q[usize::value_from(self.n[4]).unwrap()] = x;  // n[4] < 5
+            self.q[(self.n[4]).to_usize().unwrap()] = x;  // n[4] < 5
             self.n[4] += 1;
             if self.n[4] == 5 {
                 sort_floats(&mut self.q);
@@ -166,7 +176,7 @@ impl Estimate for Quantile {
 
         // Adjust height of markers.
         for i in 1..4 {
-            let d: f64 = self.m[i] - f64::approx_from(self.n[i]).unwrap();
+            let d: f64 = self.m[i] - (self.n[i]).to_f64().unwrap();
             if d >= 1. && self.n[i + 1] - self.n[i] > 1 ||
                d <= -1. && self.n[i - 1] - self.n[i] < -1 {
                 let d = d.signum();
@@ -176,7 +186,7 @@ impl Estimate for Quantile {
                 } else {
                     self.q[i] = self.linear(i, d);
                 }
-                let delta: i64 = d.approx().unwrap();  // d == +-1
+                let delta = d.to_i64().unwrap();  // d == +-1
                 self.n[i] += delta;
             }
         }
@@ -187,6 +197,381 @@ impl Estimate for Quantile {
     }
 }
 
+impl Merge for Quantile {
+    /// Merge another sample into this one.
+    ///
+    /// The P² algorithm does not support merging, so this uses a heuristic:
+    /// If one of the samples has less than 5 observations, its observations
+    /// are simply added to the other one, which is exact. Otherwise, the
+    /// marker positions are added and the marker heights are averaged,
+    /// weighted by the sample sizes. Minimum and maximum remain exact, but the
+    /// estimate of the quantile is less accurate than for a single sample.
+    /// The error is small if the samples are drawn from the same population.
+    ///
+    /// Panics if the samples estimate different quantiles.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Quantile, Estimate, Merge};
+    ///
+    /// let mut q_left = Quantile::new(0.5);
+    /// let mut q_right = Quantile::new(0.5);
+    /// for i in 0..1000 {
+    ///     let x = f64::from((i * 7919) % 1000);
+    ///     if i % 2 == 0 {
+    ///         q_left.add(x);
+    ///     } else {
+    ///         q_right.add(x);
+    ///     }
+    /// }
+    /// q_left.merge(&q_right);
+    /// assert!((q_left.quantile() - 499.5).abs() < 10.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Quantile) {
+        assert_eq!(self.p(), other.p(), "Both estimators must have the same p");
+        if other.len() < 5 {
+            let len = other.len().to_usize().unwrap();  // < 5
+            for &x in &other.q[..len] {
+                self.add(x);
+            }
+            return;
+        }
+        if self.len() < 5 {
+            let mut merged = *other;
+            let len = self.len().to_usize().unwrap();  // < 5
+            for &x in &self.q[..len] {
+                merged.add(x);
+            }
+            *self = merged;
+            return;
+        }
+
+        let len_self = self.len().to_f64().unwrap();
+        let len_other = other.len().to_f64().unwrap();
+        let len_total = len_self + len_other;
+        self.q[0] = self.q[0].min(other.q[0]);
+        self.q[4] = self.q[4].max(other.q[4]);
+        for i in 1..4 {
+            self.q[i] = (len_self * self.q[i] + len_other * other.q[i]) / len_total;
+            self.n[i] += other.n[i];
+        }
+        self.n[4] += other.n[4];
+        for i in 0..5 {
+            self.m[i] = 1. + (len_total - 1.) * self.dm[i];
+        }
+    }
+}
+
+impl core::iter::FromIterator<f64> for Quantile {
+    /// Create a new median estimator from samples.
+    fn from_iter<T>(iter: T) -> Quantile
+        where T: IntoIterator<Item=f64>
+    {
+        let mut a = Quantile::default();
+        for x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for Quantile {
+    /// Create a new median estimator from samples.
+    fn from_iter<T>(iter: T) -> Quantile
+        where T: IntoIterator<Item=&'a f64>
+    {
+        let mut a = Quantile::default();
+        for &x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a, 'b> core::iter::FromIterator<&'a &'b f64> for Quantile {
+    /// Create a new median estimator from samples.
+    fn from_iter<T>(iter: T) -> Quantile
+        where T: IntoIterator<Item=&'a &'b f64>
+    {
+        let mut a = Quantile::default();
+        for &&x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+/// Estimate the quartiles of a sequence of numbers ("population").
+///
+/// This uses one [`Quantile`] estimator for each quartile. See there for the
+/// caveats.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Quartiles;
+///
+/// let a: Quartiles = (1..101).map(f64::from).collect();
+/// println!("The median is {} with an IQR of {}.", a.median(), a.iqr());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Quartiles {
+    /// Estimator of the first quartile.
+    q1: Quantile,
+    /// Estimator of the median.
+    q2: Quantile,
+    /// Estimator of the third quartile.
+    q3: Quantile,
+}
+
+impl Quartiles {
+    /// Create a new quartiles estimator.
+    #[inline]
+    pub fn new() -> Quartiles {
+        Quartiles {
+            q1: Quantile::new(0.25),
+            q2: Quantile::new(0.5),
+            q3: Quantile::new(0.75),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Quartiles::new();
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.q2.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.q2.is_empty()
+    }
+
+    /// Estimate the first quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn q1(&self) -> f64 {
+        self.q1.quantile()
+    }
+
+    /// Estimate the median of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn median(&self) -> f64 {
+        self.q2.quantile()
+    }
+
+    /// Estimate the third quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn q3(&self) -> f64 {
+        self.q3.quantile()
+    }
+
+    /// Estimate the interquartile range of the population, i.e. `q3 - q1`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn iqr(&self) -> f64 {
+        self.q3() - self.q1()
+    }
+}
+
+impl core::default::Default for Quartiles {
+    fn default() -> Quartiles {
+        Quartiles::new()
+    }
+}
+
+impl Estimate for Quartiles {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.q1.add(x);
+        self.q2.add(x);
+        self.q3.add(x);
+    }
+
+    /// Estimate the median of the population.
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.median()
+    }
+}
+
+impl Merge for Quartiles {
+    /// Merge another sample into this one.
+    ///
+    /// See `Quantile::merge` for the caveats.
+    #[inline]
+    fn merge(&mut self, other: &Quartiles) {
+        self.q1.merge(&other.q1);
+        self.q2.merge(&other.q2);
+        self.q3.merge(&other.q3);
+    }
+}
+
+impl_from_iterator!(Quartiles);
+
+/// Define an estimator of several quantiles with a number of quantiles known
+/// at compile time.
+///
+/// Because macros are not hygenic for items, everything is defined in a private
+/// module with the given name. This includes the `Quantiles` struct and the
+/// number of quantiles `LEN`, which has to be positive.
+///
+/// All quantiles are updated by a single call to `add`. Internally, one
+/// [`Quantile`] estimator is used for each probability. This does not require
+/// the `alloc` feature, which provides [`Quantiles`] for a number of quantiles
+/// known at runtime.
+///
+/// Note that you need to make sure that `core` is accessible to the macro.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+/// [`Quantiles`]: ./struct.Quantiles.html
+///
+///
+/// # Example
+///
+/// ```
+/// # extern crate core;
+/// # #[macro_use] extern crate average;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_derive;
+/// # #[cfg(feature = "serde1")] #[macro_use] extern crate serde_big_array;
+/// # fn main() {
+/// define_quantiles!(percentiles, 3);
+/// let mut q = percentiles::Quantiles::new(&[0.05, 0.5, 0.95]);
+/// for i in 0..100 {
+///     q.add(f64::from(i));
+/// }
+/// println!("The median is {}.", q.quantile(0.5).unwrap());
+/// assert!(q.quantile(0.25).is_none());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_quantiles {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            use $crate::{Estimate, Merge, Quantile};
+            #[cfg(feature = "serde1")] big_array! {
+                BigArray; LEN,
+            }
+
+            /// The number of estimated quantiles.
+            const LEN: usize = $LEN;
+
+            /// Estimate several quantiles of a sequence of numbers
+            /// ("population").
+            #[derive(Debug, Clone)]
+            #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+            pub struct Quantiles {
+                /// The estimators of the quantiles.
+                #[cfg_attr(feature = "serde1", serde(with = "BigArray"))]
+                estimators: [Quantile; LEN],
+            }
+
+            impl Quantiles {
+                /// Create a new estimator of the `p`-quantiles for all given
+                /// `p`.
+                ///
+                /// Panics if the number of probabilities is not `LEN` or if
+                /// any of them is not between 0 and 1.
+                #[inline]
+                pub fn new(ps: &[f64]) -> Quantiles {
+                    assert_eq!(ps.len(), LEN, "Expected {} probabilities", LEN);
+                    let mut estimators = [Quantile::new(0.5); LEN];
+                    for (e, &p) in estimators.iter_mut().zip(ps) {
+                        *e = Quantile::new(p);
+                    }
+                    Quantiles { estimators }
+                }
+
+                /// Reset the estimator to an empty sample.
+                #[inline]
+                pub fn reset(&mut self) {
+                    for e in &mut self.estimators[..] {
+                        e.reset();
+                    }
+                }
+
+                /// Add an observation sampled from the population.
+                #[inline]
+                pub fn add(&mut self, x: f64) {
+                    for e in &mut self.estimators[..] {
+                        e.add(x);
+                    }
+                }
+
+                /// Return the sample size.
+                #[inline]
+                pub fn len(&self) -> u64 {
+                    self.estimators[0].len()
+                }
+
+                /// Determine whether the sample is empty.
+                #[inline]
+                pub fn is_empty(&self) -> bool {
+                    self.estimators[0].is_empty()
+                }
+
+                /// Return the estimators of the individual quantiles.
+                #[inline]
+                pub fn estimators(&self) -> &[Quantile] {
+                    &self.estimators[..]
+                }
+
+                /// Estimate the `p`-quantile of the population.
+                ///
+                /// Returns `None` if `p` is not one of the probabilities this
+                /// estimator was created with.
+                #[inline]
+                pub fn quantile(&self, p: f64) -> Option<f64> {
+                    self.estimators.iter()
+                        .find(|e| e.p() == p)
+                        .map(|e| e.quantile())
+                }
+            }
+
+            impl ::core::iter::Extend<f64> for Quantiles {
+                fn extend<T>(&mut self, iter: T)
+                    where T: IntoIterator<Item=f64>
+                {
+                    for x in iter {
+                        self.add(x);
+                    }
+                }
+            }
+
+            impl Merge for Quantiles {
+                /// Merge another sample into this one.
+                ///
+                /// See `Quantile::merge` for the caveats.
+                #[inline]
+                fn merge(&mut self, other: &Quantiles) {
+                    for (a, b) in self.estimators.iter_mut().zip(other.estimators.iter()) {
+                        a.merge(b);
+                    }
+                }
+            }
+        }
+    );
+}
+
 #[test]
 fn reference() {
     let observations = [
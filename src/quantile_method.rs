@@ -0,0 +1,267 @@
+use alloc::vec::Vec;
+use core;
+
+use float_ord::sort as sort_floats;
+use num_traits::ToPrimitive;
+
+use super::{DynHistogram, Estimate, Histogram, Merge, Quantile, TDigest};
+
+/// The algorithm used by `QuantileEstimator`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum QuantileMethod {
+    /// Use the P² algorithm like `Quantile`.
+    ///
+    /// This requires constant memory, but only the quantile given at
+    /// construction can be estimated, and there is no bound on the error.
+    P2,
+    /// Store all samples and sort them.
+    ///
+    /// This gives exact quantiles, but requires O(n) memory and O(n log n)
+    /// operations for each query.
+    Exact,
+    /// Count the samples in `bins` bins of constant width from `start` to
+    /// `end`.
+    ///
+    /// This requires memory proportional to the number of bins. The error is
+    /// bounded by the bin width, as long as the quantile is within the range.
+    Histogram {
+        /// The lower range limit.
+        start: f64,
+        /// The upper range limit.
+        end: f64,
+        /// The number of bins.
+        bins: usize,
+    },
+    /// Summarize the samples with a t-digest like `TDigest`.
+    ///
+    /// This requires memory proportional to `compression`, and the rank error
+    /// is roughly proportional to `1 / compression`, independent of the range
+    /// of the samples.
+    TDigest {
+        /// The compression parameter.
+        compression: f64,
+    },
+}
+
+/// The state of the selected algorithm.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+enum Backend {
+    P2(Quantile),
+    Exact(Vec<f64>),
+    Histogram(DynHistogram),
+    TDigest(TDigest),
+}
+
+/// Estimate the p-quantile of a sequence of numbers ("population") with a
+/// selectable algorithm.
+///
+/// See `QuantileMethod` for the available algorithms and their trade-offs.
+/// The `P2` method only supports the quantile given at construction, while
+/// the other ones can estimate arbitrary quantiles.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{QuantileEstimator, QuantileMethod};
+///
+/// let mut a = QuantileEstimator::new(QuantileMethod::Exact, 0.5);
+/// for &x in &[4., 1., 3., 2.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.quantile(0.5), Some(2.5));
+/// assert_eq!(a.quantile(0.25), Some(1.75));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct QuantileEstimator {
+    /// The quantile given at construction.
+    p: f64,
+    /// The state of the used algorithm.
+    backend: Backend,
+}
+
+impl QuantileEstimator {
+    /// Create a new p-quantile estimator using the given algorithm.
+    ///
+    /// Panics if `p` is not between 0 and 1, or if the `Histogram` method has
+    /// zero bins.
+    #[inline]
+    pub fn new(method: QuantileMethod, p: f64) -> QuantileEstimator {
+        assert!((0. ..=1.).contains(&p), "p must be in [0, 1]");
+        let backend = match method {
+            QuantileMethod::P2 => Backend::P2(Quantile::new(p)),
+            QuantileMethod::Exact => Backend::Exact(Vec::new()),
+            QuantileMethod::Histogram { start, end, bins } =>
+                Backend::Histogram(DynHistogram::with_uniform_bins(start, end, bins)),
+            QuantileMethod::TDigest { compression } =>
+                Backend::TDigest(TDigest::new(compression)),
+        };
+        QuantileEstimator { p, backend }
+    }
+
+    /// Create a new p-quantile estimator with a rank error of at most about
+    /// `epsilon`.
+    ///
+    /// The estimated quantile is the exact `q`-quantile of the sample for some
+    /// `q` within `p ± epsilon`. This uses the `TDigest` method with a
+    /// compression of `ceil(pi / epsilon)`: Around the median, a centroid of
+    /// the t-digest spans a fraction of about `pi / compression` of the
+    /// samples, and the centroids are smaller towards the tails. The memory
+    /// is proportional to the compression, so halving `epsilon` doubles the
+    /// required memory.
+    ///
+    /// Panics if `p` is not between 0 and 1, or if `epsilon` is not in
+    /// (0, 1).
+    #[inline]
+    pub fn with_error(p: f64, epsilon: f64) -> QuantileEstimator {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!(0. < epsilon && epsilon < 1., "epsilon must be in (0, 1)");
+        let compression = (core::f64::consts::PI / epsilon).ceil();
+        QuantileEstimator::new(QuantileMethod::TDigest { compression }, p)
+    }
+
+    /// Return the algorithm used by this estimator.
+    #[inline]
+    pub fn method(&self) -> QuantileMethod {
+        match self.backend {
+            Backend::P2(_) => QuantileMethod::P2,
+            Backend::Exact(_) => QuantileMethod::Exact,
+            Backend::Histogram(ref h) => QuantileMethod::Histogram {
+                start: h.range_min(),
+                end: h.range_max(),
+                bins: h.len(),
+            },
+            Backend::TDigest(ref t) => QuantileMethod::TDigest {
+                compression: t.compression(),
+            },
+        }
+    }
+
+    /// Return the value of `p` given at construction.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The algorithm and `p` are kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        match self.backend {
+            Backend::P2(ref mut q) => q.reset(),
+            Backend::Exact(ref mut samples) => samples.clear(),
+            Backend::Histogram(ref mut h) => h.reset(),
+            Backend::TDigest(ref mut t) => t.reset(),
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        match self.backend {
+            Backend::P2(ref mut q) => q.add(x),
+            Backend::Exact(ref mut samples) => samples.push(x),
+            Backend::Histogram(ref mut h) => {
+                // Samples out of range are counted by the histogram.
+                let _ = h.add(x);
+            },
+            Backend::TDigest(ref mut t) => t.add(x),
+        }
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        match self.backend {
+            Backend::P2(ref q) => q.len(),
+            Backend::Exact(ref samples) => samples.len().to_u64().unwrap(),
+            Backend::Histogram(ref h) => {
+                let bins: u64 = h.bins().iter().sum();
+                h.underflow() + bins + h.overflow()
+            },
+            Backend::TDigest(ref t) => t.len(),
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimate the `p`-quantile of the population.
+    ///
+    /// For the `Exact` method, this interpolates linearly between the closest
+    /// order statistics, i.e. the quantile is located at position
+    /// `(len - 1) * p` of the sorted samples. Returns `None` for the `P2`
+    /// method if `p` differs from the one given at construction.
+    ///
+    /// Returns 0 for an empty sample. Panics if `p` is not between 0 and 1.
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!((0. ..=1.).contains(&p), "p must be in [0, 1]");
+        Some(match self.backend {
+            Backend::P2(ref q) => {
+                if p != q.p() {
+                    return None;
+                }
+                q.quantile()
+            },
+            Backend::Exact(ref samples) => {
+                if samples.is_empty() {
+                    return Some(0.);
+                }
+                let mut sorted = samples.clone();
+                sort_floats(&mut sorted);
+                let h = (sorted.len() - 1).to_f64().unwrap() * p;
+                let lower = h.floor();
+                let i = lower.to_usize().unwrap();
+                if i + 1 == sorted.len() {
+                    sorted[i]
+                } else {
+                    sorted[i] + (h - lower) * (sorted[i + 1] - sorted[i])
+                }
+            },
+            Backend::Histogram(ref h) => h.quantile(p),
+            Backend::TDigest(ref t) => t.quantile(p),
+        })
+    }
+}
+
+impl Estimate for QuantileEstimator {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        // The quantile given at construction is supported by all methods.
+        self.quantile(self.p).unwrap()
+    }
+}
+
+impl Merge for QuantileEstimator {
+    /// Merge another sample into this one.
+    ///
+    /// Panics if the estimators use different methods or, for the `P2`
+    /// method, different values of `p`.
+    #[inline]
+    fn merge(&mut self, other: &QuantileEstimator) {
+        match (&mut self.backend, &other.backend) {
+            (Backend::P2(a), Backend::P2(b)) => a.merge(b),
+            (Backend::Exact(a), Backend::Exact(b)) => a.extend_from_slice(b),
+            (Backend::Histogram(a), Backend::Histogram(b)) => a.merge(b),
+            (Backend::TDigest(a), Backend::TDigest(b)) => a.merge(b),
+            _ => panic!("Both estimators must use the same method"),
+        }
+    }
+}
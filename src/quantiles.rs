@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+use core;
+
+use super::{Estimate, Merge, Quantile};
+
+/// Estimate several quantiles of a sequence of numbers ("population").
+///
+/// All quantiles are updated by a single call to `add`. Internally, one
+/// `Quantile` estimator is used for each probability. Unlike the estimators
+/// defined by `define_quantiles!`, the number of quantiles is only known at
+/// runtime.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Quantiles;
+///
+/// let mut q = Quantiles::new(&[0.05, 0.5, 0.95]);
+/// for i in 0..100 {
+///     q.add(f64::from(i));
+/// }
+/// println!("The median is {}.", q.quantile(0.5).unwrap());
+/// assert!(q.quantile(0.25).is_none());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Quantiles {
+    /// The estimators of the quantiles.
+    estimators: Vec<Quantile>,
+}
+
+impl Quantiles {
+    /// Create a new estimator of the `p`-quantiles for all given `p`.
+    ///
+    /// Panics if no probability is given or if any of them is not between 0
+    /// and 1.
+    #[inline]
+    pub fn new(ps: &[f64]) -> Quantiles {
+        assert!(!ps.is_empty(), "Expected at least one probability");
+        Quantiles { estimators: ps.iter().map(|&p| Quantile::new(p)).collect() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The probabilities are kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        for e in &mut self.estimators {
+            e.reset();
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        for e in &mut self.estimators {
+            e.add(x);
+        }
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.estimators[0].len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.estimators[0].is_empty()
+    }
+
+    /// Return the estimators of the individual quantiles.
+    #[inline]
+    pub fn estimators(&self) -> &[Quantile] {
+        &self.estimators[..]
+    }
+
+    /// Estimate the `p`-quantile of the population.
+    ///
+    /// Returns `None` if `p` is not one of the probabilities this estimator
+    /// was created with.
+    #[inline]
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        self.estimators.iter()
+            .find(|e| e.p() == p)
+            .map(|e| e.quantile())
+    }
+}
+
+impl core::iter::Extend<f64> for Quantiles {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=f64>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl Merge for Quantiles {
+    /// Merge another sample into this one.
+    ///
+    /// Panics if the estimators were created with different probabilities.
+    /// See `Quantile::merge` for the caveats.
+    #[inline]
+    fn merge(&mut self, other: &Quantiles) {
+        assert_eq!(self.estimators.len(), other.estimators.len(),
+                   "Both estimators must have the same probabilities");
+        for (a, b) in self.estimators.iter_mut().zip(&other.estimators) {
+            a.merge(b);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+
+use rand_core::{RngCore, SeedableRng};
+
+/// Draw a uniformly distributed integer in `[0, bound)`.
+///
+/// This uses Lemire's widening multiplication with rejection, so the result
+/// is unbiased.
+#[inline]
+fn gen_index<R: RngCore>(rng: &mut R, bound: u64) -> u64 {
+    debug_assert!(bound > 0);
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let m = u128::from(rng.next_u64()) * u128::from(bound);
+        if (m as u64) >= threshold {
+            return (m >> 64) as u64;
+        }
+    }
+}
+
+/// Draw a uniform random sample of fixed size from a sequence of items
+/// ("population").
+///
+/// This uses Vitter's algorithm R: Each item seen so far has the same
+/// probability `k / n` of being retained, where `k` is the capacity and `n`
+/// the number of items seen. Unlike the other estimators, this stores the
+/// items, so it can be used to recompute arbitrary statistics later.
+///
+/// The random number generator is supplied by the user, either directly or
+/// as a seed, so the same seed gives reproducible results. No global random
+/// number generator is used.
+///
+/// This requires the `alloc` and `rand_core` features.
+///
+///
+/// ## Example
+///
+/// ```
+/// # extern crate rand;
+/// # extern crate average;
+/// # fn main() {
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+/// use average::Reservoir;
+///
+/// let rng = SmallRng::from_seed([42; 16]);
+/// let mut a = Reservoir::new(10, rng);
+/// for i in 0..1000 {
+///     a.add(i);
+/// }
+/// assert_eq!(a.samples().len(), 10);
+/// assert_eq!(a.len(), 1000);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reservoir<T, R> {
+    /// The retained items.
+    samples: Vec<T>,
+    /// Maximal number of retained items.
+    capacity: usize,
+    /// Number of items seen.
+    n: u64,
+    /// Random number generator.
+    rng: R,
+}
+
+impl<T, R: RngCore> Reservoir<T, R> {
+    /// Create a new reservoir retaining up to `capacity` items, using the
+    /// given random number generator.
+    #[inline]
+    pub fn new(capacity: usize, rng: R) -> Reservoir<T, R> {
+        Reservoir {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            n: 0,
+            rng,
+        }
+    }
+
+    /// Create a new reservoir retaining up to `capacity` items, using a
+    /// random number generator created from the given seed.
+    ///
+    /// The same seed always results in the same retained items for the same
+    /// sequence of items.
+    #[inline]
+    pub fn from_seed(capacity: usize, seed: R::Seed) -> Reservoir<T, R>
+        where R: SeedableRng
+    {
+        Reservoir::new(capacity, R::from_seed(seed))
+    }
+
+    /// Add an item of the population.
+    #[inline]
+    pub fn add(&mut self, item: T) {
+        self.n += 1;
+        if self.samples.len() < self.capacity {
+            self.samples.push(item);
+            return;
+        }
+        let j = gen_index(&mut self.rng, self.n);
+        if j < self.capacity as u64 {
+            self.samples[j as usize] = item;
+        }
+    }
+
+    /// Return the retained items.
+    ///
+    /// Their order is not meaningful.
+    #[inline]
+    pub fn samples(&self) -> &[T] {
+        &self.samples[..]
+    }
+
+    /// Return the maximal number of retained items.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the number of items seen.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether no item was seen.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Discard all items, keeping the state of the random number generator.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.n = 0;
+    }
+}
+
+impl<T, R: RngCore> Extend<T> for Reservoir<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use num_traits::ToPrimitive;
+
+use super::{Estimate, Mean, Quantile};
+
+/// Estimate the arithmetic mean of a sequence of numbers ("population"),
+/// ignoring observations outside of an inter-quantile range.
+///
+/// The `lower` and `upper` quantiles of all observations are estimated online
+/// using the P² algorithm, like by `Quantile`. An observation is only added to
+/// the mean if it lies within the range estimated from the previous
+/// observations. This makes the mean robust against rare, extreme outliers.
+/// Because the quantiles are only estimated, the fraction of ignored
+/// observations is only approximately `lower + 1 - upper`.
+///
+/// During a warm-up period, the quantile estimates are not reliable, so all
+/// observations are added to the mean. The warm-up lasts for
+/// `max(5, round(1 / min(lower, 1 - upper)))` observations, i.e. until enough
+/// observations were seen to expect about one of them beyond each quantile.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::RobustMean;
+///
+/// let mut a = RobustMean::new(0.01, 0.99);
+/// for i in 0..1000 {
+///     a.add(f64::from(i % 10));
+/// }
+/// a.add(1e6);
+/// assert!(a.mean() < 10.);
+/// assert!(a.rejected() > 0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RobustMean {
+    /// Estimator of the lower quantile.
+    lower: Quantile,
+    /// Estimator of the upper quantile.
+    upper: Quantile,
+    /// Estimator of the mean of the accepted observations.
+    avg: Mean,
+    /// Number of observations that are always accepted.
+    warm_up: u64,
+}
+
+impl RobustMean {
+    /// Create a new estimator ignoring observations below the `lower` and
+    /// above the `upper` quantile.
+    ///
+    /// Panics unless `0 < lower < upper < 1`.
+    #[inline]
+    pub fn new(lower: f64, upper: f64) -> RobustMean {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!(0. < lower && lower < upper && upper < 1.,
+                "quantiles must satisfy 0 < lower < upper < 1");
+        let warm_up = (1. / lower.min(1. - upper)).round().to_u64().unwrap();
+        RobustMean {
+            lower: Quantile::new(lower),
+            upper: Quantile::new(upper),
+            avg: Mean::new(),
+            warm_up: warm_up.max(5),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The quantiles are kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.lower.reset();
+        self.upper.reset();
+        self.avg.reset();
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// The observation is always used for estimating the quantiles, but only
+    /// added to the mean if it is within the current inter-quantile range,
+    /// or during the warm-up period.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if self.total() < self.warm_up
+            || (self.lower.quantile() <= x && x <= self.upper.quantile())
+        {
+            self.avg.add(x);
+        }
+        self.lower.add(x);
+        self.upper.add(x);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Return the number of observations added to the mean.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the number of all observations, including the ignored ones.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.lower.len()
+    }
+
+    /// Return the number of ignored observations.
+    #[inline]
+    pub fn rejected(&self) -> u64 {
+        self.total() - self.len()
+    }
+
+    /// Return the number of observations that are always added to the mean.
+    #[inline]
+    pub fn warm_up(&self) -> u64 {
+        self.warm_up
+    }
+
+    /// Estimate the mean of the population, ignoring outliers.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Estimate the lower quantile of all observations.
+    #[inline]
+    pub fn lower_quantile(&self) -> f64 {
+        self.lower.quantile()
+    }
+
+    /// Estimate the upper quantile of all observations.
+    #[inline]
+    pub fn upper_quantile(&self) -> f64 {
+        self.upper.quantile()
+    }
+}
+
+impl Estimate for RobustMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+use core;
+
+use num_traits::ToPrimitive;
+
+use super::Estimate;
+
+/// Estimate the mean of the last `window` numbers of a sequence.
+///
+/// The samples of the window are stored in a ring buffer, and a running sum is
+/// kept such that `add` and `mean` are O(1). To avoid accumulating rounding
+/// errors over many updates, the sum is recalculated from the buffer each time
+/// the window was completely replaced.
+///
+/// Unlike the estimators defined by `define_sliding_mean!`, the window size is
+/// only known at runtime. This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::SlidingMean;
+///
+/// let mut a = SlidingMean::new(3);
+/// for i in 1..6 {
+///     a.add(f64::from(i));
+/// }
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.mean(), 4.);
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SlidingMean {
+    /// Number of samples in the window.
+    window: usize,
+    /// Ring buffer of the samples in the window.
+    buf: Vec<f64>,
+    /// Index of the oldest sample, once the window is full.
+    head: usize,
+    /// Running sum of the samples in the window.
+    sum: f64,
+}
+
+impl core::fmt::Debug for SlidingMean {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("SlidingMean { window: ")?;
+        self.window.fmt(formatter)?;
+        formatter.write_str(", buf: ")?;
+        // Print the samples from the oldest to the newest.
+        formatter.debug_list()
+            .entries(self.buf[self.head..].iter().chain(&self.buf[..self.head]))
+            .finish()?;
+        formatter.write_str(", sum: ")?;
+        self.sum.fmt(formatter)?;
+        formatter.write_str(" }")
+    }
+}
+
+impl SlidingMean {
+    /// Create a new estimator over a window of the given size.
+    ///
+    /// Panics if `window` is 0.
+    #[inline]
+    pub fn new(window: usize) -> SlidingMean {
+        assert!(window > 0, "window must be positive");
+        SlidingMean {
+            window,
+            buf: Vec::with_capacity(window),
+            head: 0,
+            sum: 0.,
+        }
+    }
+
+    /// Return the size of the window.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Reset the estimator to an empty window.
+    ///
+    /// The window size is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buf.clear();
+        self.head = 0;
+        self.sum = 0.;
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if self.buf.len() < self.window {
+            self.buf.push(x);
+            self.sum += x;
+            return;
+        }
+        self.sum += x - self.buf[self.head];
+        self.buf[self.head] = x;
+        self.head += 1;
+        if self.head == self.window {
+            self.head = 0;
+            // Every sample was replaced, recalculate the sum to get rid of the
+            // accumulated rounding errors.
+            self.sum = self.buf.iter().sum();
+        }
+    }
+
+    /// Return the number of samples in the window.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.buf.len() as u64
+    }
+
+    /// Determine whether the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Estimate the mean of the samples in the window.
+    ///
+    /// Returns 0 for an empty window.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.buf.is_empty() {
+            return 0.;
+        }
+        self.sum / self.buf.len().to_f64().unwrap()
+    }
+}
+
+impl Estimate for SlidingMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
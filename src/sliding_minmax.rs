@@ -0,0 +1,153 @@
+use alloc::collections::VecDeque;
+use core;
+
+use super::Estimate;
+
+macro_rules! define_sliding_extreme {
+    ($name:ident, $method:ident, $what:expr, $dominates:tt, $empty:expr, $empty_doc:expr,
+     $example:expr) => {
+        #[doc = "Estimate the "]
+        #[doc = $what]
+        #[doc = " of the last `window` numbers of a sequence."]
+        ///
+        /// This uses a monotonic queue of the candidates that can still become
+        /// the extreme of the window, so adding a sample and querying the
+        /// extreme take amortized O(1) operations, and at most `window` samples
+        /// are stored. NaN samples advance the window, but are otherwise
+        /// ignored.
+        ///
+        /// This requires the `alloc` feature.
+        ///
+        ///
+        /// ## Example
+        ///
+        /// ```
+        #[doc = $example]
+        /// ```
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+        pub struct $name {
+            /// Number of samples in the window.
+            window: u64,
+            /// Total number of added samples.
+            n: u64,
+            /// Candidates `(index, value)`, with the extreme at the front.
+            queue: VecDeque<(u64, f64)>,
+        }
+
+        impl $name {
+            /// Create a new estimator over a window of the given size.
+            ///
+            /// Panics if `window` is 0.
+            #[inline]
+            pub fn new(window: usize) -> $name {
+                assert!(window > 0, "window must be positive");
+                $name {
+                    window: window as u64,
+                    n: 0,
+                    queue: VecDeque::with_capacity(window),
+                }
+            }
+
+            /// Return the size of the window.
+            #[inline]
+            pub fn window(&self) -> usize {
+                self.window as usize
+            }
+
+            /// Reset the estimator to an empty sample.
+            ///
+            /// The window size is kept.
+            #[inline]
+            pub fn reset(&mut self) {
+                self.n = 0;
+                self.queue.clear();
+            }
+
+            /// Add an observation sampled from the population.
+            #[inline]
+            pub fn add(&mut self, x: f64) {
+                let i = self.n;
+                self.n += 1;
+                // Remove the candidates that left the window.
+                while let Some(&(j, _)) = self.queue.front() {
+                    if j + self.window <= i {
+                        self.queue.pop_front();
+                    } else {
+                        break;
+                    }
+                }
+                if x.is_nan() {
+                    return;
+                }
+                // Candidates older than `x` that are not more extreme can
+                // never become the extreme.
+                while let Some(&(_, y)) = self.queue.back() {
+                    if x $dominates y {
+                        self.queue.pop_back();
+                    } else {
+                        break;
+                    }
+                }
+                self.queue.push_back((i, x));
+            }
+
+            /// Return the number of samples in the window.
+            #[inline]
+            pub fn len(&self) -> u64 {
+                self.n.min(self.window)
+            }
+
+            /// Determine whether the window is empty.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.n == 0
+            }
+
+            #[doc = "Return the "]
+            #[doc = $what]
+            #[doc = " of the samples in the window."]
+            ///
+            #[doc = $empty_doc]
+            #[inline]
+            pub fn $method(&self) -> f64 {
+                match self.queue.front() {
+                    Some(&(_, x)) => x,
+                    None => $empty,
+                }
+            }
+        }
+
+        impl Estimate for $name {
+            #[inline]
+            fn add(&mut self, x: f64) {
+                self.add(x);
+            }
+
+            #[inline]
+            fn estimate(&self) -> f64 {
+                self.$method()
+            }
+        }
+    };
+}
+
+define_sliding_extreme!(SlidingMin, min, "minimum", <=, core::f64::INFINITY,
+    "Returns `inf` if the window is empty.",
+    "use average::SlidingMin;
+
+let mut a = SlidingMin::new(3);
+for &x in &[1., 5., 2., 3., 4.] {
+    a.add(x);
+}
+assert_eq!(a.min(), 2.);");
+
+define_sliding_extreme!(SlidingMax, max, "maximum", >=, core::f64::NEG_INFINITY,
+    "Returns `-inf` if the window is empty.",
+    "use average::SlidingMax;
+
+let mut a = SlidingMax::new(3);
+for &x in &[1., 5., 2., 3., 4.] {
+    a.add(x);
+}
+assert_eq!(a.max(), 4.);");
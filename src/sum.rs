@@ -0,0 +1,121 @@
+use core;
+
+use super::{Estimate, Merge};
+use kahan_mean::compensated_add;
+
+/// Calculate the sum of a sequence of numbers ("population") and its size.
+///
+/// The sum is accumulated using Neumaier's variant of Kahan summation, so the
+/// rounding error does not grow with the length of the sequence. Unlike
+/// [`Mean`], this does not need a division per observation or query.
+///
+/// [`Mean`]: ./struct.Mean.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Sum;
+///
+/// let a: Sum = (1..6).map(f64::from).collect();
+/// assert_eq!(a.sum(), 15.);
+/// assert_eq!(a.len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Sum {
+    /// Sum, without the compensation.
+    sum: f64,
+    /// Compensation term collecting the rounding errors of `sum`.
+    compensation: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl Sum {
+    /// Create a new sum estimator.
+    #[inline]
+    pub fn new() -> Sum {
+        Sum { sum: 0., compensation: 0., n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Sum::new();
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.n += 1;
+        compensated_add(&mut self.sum, &mut self.compensation, x);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the sum of the sample.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+impl core::default::Default for Sum {
+    fn default() -> Sum {
+        Sum::new()
+    }
+}
+
+impl Estimate for Sum {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    /// Return the sum.
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.sum()
+    }
+}
+
+impl Merge for Sum {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Sum, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let sum_total: Sum = sequence.iter().collect();
+    /// let mut sum_left: Sum = left.iter().collect();
+    /// let sum_right: Sum = right.iter().collect();
+    /// sum_left.merge(&sum_right);
+    /// assert_eq!(sum_total.sum(), sum_left.sum());
+    /// assert_eq!(sum_total.len(), sum_left.len());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Sum) {
+        self.n += other.n;
+        self.compensation += other.compensation;
+        compensated_add(&mut self.sum, &mut self.compensation, other.sum);
+    }
+}
+
+impl_from_iterator!(Sum);
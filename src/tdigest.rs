@@ -0,0 +1,355 @@
+use alloc::vec::Vec;
+use core;
+use core::cmp::Ordering;
+
+use float_ord::FloatOrd;
+use num_traits::ToPrimitive;
+
+use super::Merge;
+
+/// Estimate arbitrary quantiles of a sequence of numbers ("population") using
+/// a t-digest.
+///
+/// The samples are summarized by a sorted list of clusters ("centroids"),
+/// each with a mean and a weight. Clusters close to the extreme quantiles are
+/// kept small, so tail quantiles like the 99.9th percentile are estimated
+/// accurately, while the memory stays bounded. Unlike `Quantile`, arbitrary
+/// quantiles can be estimated from one t-digest, and the accuracy does not
+/// degrade if the samples are not in random order (for example sorted).
+///
+/// The `compression` parameter controls the trade-off between accuracy and
+/// memory: There are at most about `compression` centroids, and the error is
+/// roughly proportional to `1 / compression`. A value of 100 is a good
+/// default.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::TDigest;
+///
+/// let a: TDigest = (1..10_001).map(f64::from).collect();
+/// assert!((a.quantile(0.5) - 5000.5).abs() < 10.);
+/// assert!((a.quantile(0.999) - 9990.).abs() < 2.);
+/// ```
+// This implements the merging t-digest with the normalized `k_2` scale
+// function described here: https://arxiv.org/abs/1902.04023
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TDigest {
+    /// Compression parameter.
+    compression: f64,
+    /// Merged centroids `(mean, weight)`, sorted by their means.
+    centroids: Vec<(f64, f64)>,
+    /// Samples that were not merged into the centroids yet.
+    buffer: Vec<f64>,
+    /// Number of samples after which the buffer is merged.
+    buffer_capacity: usize,
+    /// Sample size.
+    len: u64,
+    /// Minimal sample.
+    min: f64,
+    /// Maximal sample.
+    max: f64,
+}
+
+impl TDigest {
+    /// Create a new t-digest with the given compression parameter.
+    ///
+    /// Panics if `compression` is not positive.
+    #[inline]
+    pub fn new(compression: f64) -> TDigest {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        assert!(compression > 0., "compression must be positive");
+        let buffer_capacity = (5. * compression).ceil().to_usize().unwrap().max(10);
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::with_capacity(buffer_capacity),
+            buffer_capacity,
+            len: 0,
+            min: core::f64::INFINITY,
+            max: core::f64::NEG_INFINITY,
+        }
+    }
+
+    /// Return the compression parameter.
+    #[inline]
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The compression parameter is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = TDigest::new(self.compression);
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if x < self.min {
+            self.min = x;
+        }
+        if x > self.max {
+            self.max = x;
+        }
+        self.len += 1;
+        self.buffer.push(x);
+        if self.buffer.len() >= self.buffer_capacity {
+            self.compress();
+        }
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of centroids, after merging all buffered samples.
+    #[inline]
+    pub fn centroids(&self) -> usize {
+        if self.buffer.is_empty() {
+            return self.centroids.len();
+        }
+        let mut merged = self.clone();
+        merged.compress();
+        merged.centroids.len()
+    }
+
+    /// Merge the buffered samples into the centroids.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut points = Vec::with_capacity(self.centroids.len() + self.buffer.len());
+        points.extend_from_slice(&self.centroids);
+        points.extend(self.buffer.iter().map(|&x| (x, 1.)));
+        self.buffer.clear();
+        self.centroids = merge_centroids(points, self.compression);
+    }
+
+    /// Estimate the `p`-quantile of the population.
+    ///
+    /// Within a centroid, the samples are assumed to be distributed such that
+    /// the quantiles can be interpolated linearly between the neighboring
+    /// centroids. The minimum and the maximum are tracked exactly.
+    ///
+    /// If samples were added since they were last merged into the centroids,
+    /// this has to merge a copy of the t-digest.
+    ///
+    /// Returns 0 for an empty sample. Panics if `p` is not between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        assert!((0. ..=1.).contains(&p), "p must be in [0, 1]");
+        if self.is_empty() {
+            return 0.;
+        }
+        if !self.buffer.is_empty() {
+            let mut merged = self.clone();
+            merged.compress();
+            return merged.quantile(p);
+        }
+
+        let c = &self.centroids[..];
+        let n = c.len();
+        let total = self.len.to_f64().unwrap();
+        let index = p * total;
+        if index < 1. {
+            return self.min;
+        }
+        if index > total - 1. {
+            return self.max;
+        }
+        // Between the minimum and the center of the first centroid.
+        let (first_mean, first_weight) = c[0];
+        if first_weight > 2. && index < first_weight / 2. {
+            return self.min + (index - 1.) / (first_weight / 2. - 1.)
+                * (first_mean - self.min);
+        }
+        // Between the center of the last centroid and the maximum.
+        let (last_mean, last_weight) = c[n - 1];
+        if last_weight > 2. && total - index <= last_weight / 2. {
+            return self.max - (total - index - 1.) / (last_weight / 2. - 1.)
+                * (self.max - last_mean);
+        }
+
+        // Between the centers of neighboring centroids.
+        let mut weight_so_far = first_weight / 2.;
+        for i in 0..n - 1 {
+            let (left_mean, left_weight) = c[i];
+            let (right_mean, right_weight) = c[i + 1];
+            let dw = (left_weight + right_weight) / 2.;
+            if weight_so_far + dw > index {
+                // Centroids with a single sample are not smeared out.
+                let mut left_unit = 0.;
+                if left_weight == 1. {
+                    if index - weight_so_far < 0.5 {
+                        return left_mean;
+                    }
+                    left_unit = 0.5;
+                }
+                let mut right_unit = 0.;
+                if right_weight == 1. {
+                    if weight_so_far + dw - index <= 0.5 {
+                        return right_mean;
+                    }
+                    right_unit = 0.5;
+                }
+                let z1 = index - weight_so_far - left_unit;
+                let z2 = weight_so_far + dw - index - right_unit;
+                return (left_mean * z2 + right_mean * z1) / (z1 + z2);
+            }
+            weight_so_far += dw;
+        }
+        last_mean
+    }
+}
+
+/// Calculate the largest quantile a centroid starting at quantile `q0` may
+/// reach.
+///
+/// A centroid may span one unit of the scale function. Two scale functions
+/// are combined: `k_1(q) = compression / (2 pi) * asin(2 q - 1)` keeps the
+/// centroids small around the median, while the scale function
+/// `k_2(q) = compression / z * ln(q / (1 - q))` with the normalization
+/// `z = 4 ln(n / compression) + 24` keeps them small in the tails.
+#[inline]
+fn quantile_limit(q0: f64, compression: f64, total: f64) -> f64 {
+    #[allow(unused_imports)]
+    use num_traits::Float;
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    let x = (2. * q0 - 1.).asin() + 2. * PI / compression;
+    let limit_1 = if x >= FRAC_PI_2 { 1. } else { (x.sin() + 1.) / 2. };
+    let z = 4. * (total / compression).max(1.).ln() + 24.;
+    let limit_2 = q0 / (q0 + (1. - q0) * (-z / compression).exp());
+    limit_1.min(limit_2)
+}
+
+/// Merge weighted points `(mean, weight)` into as few centroids as the scale
+/// function allows.
+fn merge_centroids(mut points: Vec<(f64, f64)>, compression: f64) -> Vec<(f64, f64)> {
+    #[allow(unused_imports)]
+    use num_traits::Float;
+    points.sort_by(|a, b| {
+        FloatOrd(a.0).partial_cmp(&FloatOrd(b.0)).unwrap_or(Ordering::Equal)
+    });
+    let total: f64 = points.iter().map(|&(_, w)| w).sum();
+    let mut result = Vec::with_capacity(compression.ceil().to_usize().unwrap());
+    let mut iter = points.into_iter();
+    let mut current = match iter.next() {
+        Some(c) => c,
+        None => return result,
+    };
+    let mut weight_so_far = 0.;
+    let mut q_limit = quantile_limit(0., compression, total);
+    for (mean, weight) in iter {
+        let q = (weight_so_far + current.1 + weight) / total;
+        if q <= q_limit {
+            current.1 += weight;
+            current.0 += (mean - current.0) * weight / current.1;
+        } else {
+            weight_so_far += current.1;
+            result.push(current);
+            let q0 = weight_so_far / total;
+            q_limit = quantile_limit(q0, compression, total);
+            current = (mean, weight);
+        }
+    }
+    result.push(current);
+    result
+}
+
+impl core::default::Default for TDigest {
+    /// Create a new t-digest with a compression parameter of 100.
+    fn default() -> TDigest {
+        TDigest::new(100.)
+    }
+}
+
+impl core::iter::FromIterator<f64> for TDigest {
+    /// Create a new t-digest with a compression parameter of 100 from samples.
+    fn from_iter<T>(iter: T) -> TDigest
+        where T: IntoIterator<Item=f64>
+    {
+        let mut a = TDigest::default();
+        for x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for TDigest {
+    /// Create a new t-digest with a compression parameter of 100 from samples.
+    fn from_iter<T>(iter: T) -> TDigest
+        where T: IntoIterator<Item=&'a f64>
+    {
+        let mut a = TDigest::default();
+        for &x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl<'a, 'b> core::iter::FromIterator<&'a &'b f64> for TDigest {
+    /// Create a new t-digest with a compression parameter of 100 from samples.
+    fn from_iter<T>(iter: T) -> TDigest
+        where T: IntoIterator<Item=&'a &'b f64>
+    {
+        let mut a = TDigest::default();
+        for &&x in iter {
+            a.add(x);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<f64> for TDigest {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=f64>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl Merge for TDigest {
+    /// Merge another sample into this one.
+    ///
+    /// The centroids of both t-digests are merged again, so the result is
+    /// about as accurate as a single t-digest of all samples. The compression
+    /// parameter of `self` is kept.
+    #[inline]
+    fn merge(&mut self, other: &TDigest) {
+        if other.is_empty() {
+            return;
+        }
+        let mut points = Vec::with_capacity(
+            self.centroids.len() + self.buffer.len()
+            + other.centroids.len() + other.buffer.len());
+        points.extend_from_slice(&self.centroids);
+        points.extend(self.buffer.iter().map(|&x| (x, 1.)));
+        points.extend_from_slice(&other.centroids);
+        points.extend(other.buffer.iter().map(|&x| (x, 1.)));
+        self.buffer.clear();
+        self.centroids = merge_centroids(points, self.compression);
+        self.len += other.len;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
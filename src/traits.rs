@@ -1,4 +1,36 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Estimate a statistic of a sequence of numbers ("population").
+///
+/// This is implemented by all estimators of a single variable, so generic
+/// code can add samples and query the result without knowing the concrete
+/// estimator. The result of `estimate` is the main statistic of the
+/// estimator, e.g. the mean for `Mean` and the population variance for
+/// `Variance`.
+/// Estimators of several variables like `CoVariance` take more than one
+/// value per observation, so they do not implement this trait.
+///
+/// The trait is object safe, so estimators of different types can be used
+/// together as trait objects.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Estimate, Max, Mean, Min};
+///
+/// let mut estimators: Vec<Box<dyn Estimate>> = vec![
+///     Box::new(Mean::new()), Box::new(Min::new()), Box::new(Max::new()),
+/// ];
+/// for &x in &[1., 2., 3.] {
+///     for e in &mut estimators {
+///         e.add(x);
+///     }
+/// }
+/// let results: Vec<f64> = estimators.iter().map(|e| e.estimate()).collect();
+/// assert_eq!(results, [2., 1., 3.]);
+/// ```
 pub trait Estimate {
     /// Add an observation sampled from the population.
     fn add(&mut self, x: f64);
@@ -64,6 +96,73 @@ pub trait Histogram:
             sum_inv: 1./(sum as f64)
         }
     }
+
+    /// Return the bins normalized to a probability density.
+    ///
+    /// Each bin is divided by the total count and the bin width, so the
+    /// density integrates to 1 over the range of the histogram. Samples out
+    /// of range are not taken into account. Returns zeros for an empty
+    /// histogram.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn density(&self) -> Vec<f64> {
+        let sum: u64 = self.bins().iter().sum();
+        let sum_inv = if sum == 0 { 0. } else { 1. / (sum as f64) };
+        self.into_iter()
+            .map(|((a, b), count)| (count as f64) * sum_inv / (b - a))
+            .collect()
+    }
+
+    /// Return the bins normalized by the total count.
+    ///
+    /// The result sums up to 1. Samples out of range are not taken into
+    /// account. Returns zeros for an empty histogram.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn normalized(&self) -> Vec<f64> {
+        let sum: u64 = self.bins().iter().sum();
+        let sum_inv = if sum == 0 { 0. } else { 1. / (sum as f64) };
+        self.bins().iter().map(|&count| (count as f64) * sum_inv).collect()
+    }
+
+    /// Return the cumulative counts of the bins, from left to right.
+    ///
+    /// Samples out of range are not taken into account, so the last value
+    /// is the number of samples in range. Histograms counting the samples out
+    /// of range provide `cumulative_with_underflow` to start from the samples
+    /// below the range instead.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn cumulative(&self) -> Vec<u64> {
+        self.bins().iter()
+            .scan(0, |sum, &count| {
+                *sum += count;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    /// Return the empirical cumulative distribution function at the upper
+    /// edges of the bins.
+    ///
+    /// This is `cumulative` normalized by the number of samples in range, so
+    /// the last value is 1. Samples out of range are not taken into account,
+    /// unlike in the `quantile` and `percentile_rank` methods of the
+    /// histograms counting them, so the values differ if there are any. Use
+    /// `cdf_with_out_of_range` of these histograms for values consistent with
+    /// `percentile_rank`. Returns zeros for an empty histogram.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn cdf(&self) -> Vec<f64> {
+        let cumulative = self.cumulative();
+        let sum = cumulative.last().cloned().unwrap_or(0);
+        let sum_inv = if sum == 0 { 0. } else { 1. / (sum as f64) };
+        cumulative.into_iter().map(|c| (c as f64) * sum_inv).collect()
+    }
 }
 
 /// Iterate over the bins normalized by bin width.
@@ -0,0 +1,244 @@
+use alloc::vec::Vec;
+
+use float_ord::sort as sort_floats;
+use num_traits::ToPrimitive;
+
+use super::{Estimate, Merge};
+
+/// Estimate the trimmed arithmetic mean of a sequence of numbers
+/// ("population").
+///
+/// The given fraction of the smallest and of the largest samples is discarded
+/// before averaging the remaining ones, which makes the estimate robust
+/// against outliers.
+///
+/// Unlike most other estimators, this has to store all samples, so it
+/// requires O(n) memory. Calculating the mean sorts a copy of the samples,
+/// which takes O(n log n) operations.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{TrimmedMean, Estimate};
+///
+/// let mut a = TrimmedMean::new(0.2);
+/// for &x in &[1., 2., 3., 4., 1000.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.mean(), 3.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TrimmedMean {
+    /// Fraction of samples discarded from each tail.
+    trim: f64,
+    /// All samples.
+    samples: Vec<f64>,
+}
+
+impl TrimmedMean {
+    /// Create a new trimmed mean estimator, discarding the fraction `trim` of
+    /// the samples from each tail.
+    ///
+    /// Panics if `trim` is not in [0, 0.5).
+    #[inline]
+    pub fn new(trim: f64) -> TrimmedMean {
+        assert!((0. ..0.5).contains(&trim), "trim must be in [0, 0.5)");
+        TrimmedMean { trim, samples: Vec::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The trim fraction is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Return the fraction of samples discarded from each tail.
+    #[inline]
+    pub fn trim(&self) -> f64 {
+        self.trim
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.samples.len().to_u64().unwrap()
+    }
+
+    /// Return the number of samples discarded from each tail.
+    ///
+    /// This is `trim * len()`, rounded down.
+    #[inline]
+    pub fn trimmed_len(&self) -> usize {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        (self.trim * self.samples.len().to_f64().unwrap()).floor().to_usize().unwrap()
+    }
+
+    /// Estimate the trimmed mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let mut sorted = self.samples.clone();
+        sort_floats(&mut sorted);
+        let k = self.trimmed_len();
+        let kept = &sorted[k..sorted.len() - k];
+        kept.iter().sum::<f64>() / kept.len().to_f64().unwrap()
+    }
+}
+
+impl Estimate for TrimmedMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.samples.push(x);
+    }
+
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for TrimmedMean {
+    /// Merge another sample into this one.
+    ///
+    /// This is exact. Panics if the trim fractions differ.
+    #[inline]
+    fn merge(&mut self, other: &TrimmedMean) {
+        assert_eq!(self.trim, other.trim, "Both estimators must have the same trim fraction");
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
+
+/// Estimate the winsorized arithmetic mean of a sequence of numbers
+/// ("population").
+///
+/// The given fraction of the smallest samples is replaced by the smallest
+/// remaining sample, and likewise for the largest samples, before averaging.
+/// Unlike `TrimmedMean`, this keeps the sample size constant while still
+/// limiting the influence of outliers.
+///
+/// This has to store all samples, so it requires O(n) memory. Calculating the
+/// mean sorts a copy of the samples, which takes O(n log n) operations.
+///
+/// This requires the `alloc` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{WinsorizedMean, Estimate};
+///
+/// let mut a = WinsorizedMean::new(0.2);
+/// for &x in &[1., 2., 3., 4., 1000.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.mean(), 3.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WinsorizedMean {
+    /// Fraction of samples clamped in each tail.
+    trim: f64,
+    /// All samples.
+    samples: Vec<f64>,
+}
+
+impl WinsorizedMean {
+    /// Create a new winsorized mean estimator, clamping the fraction `trim` of
+    /// the samples in each tail.
+    ///
+    /// Panics if `trim` is not in [0, 0.5).
+    #[inline]
+    pub fn new(trim: f64) -> WinsorizedMean {
+        assert!((0. ..0.5).contains(&trim), "trim must be in [0, 0.5)");
+        WinsorizedMean { trim, samples: Vec::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The trim fraction is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Return the fraction of samples clamped in each tail.
+    #[inline]
+    pub fn trim(&self) -> f64 {
+        self.trim
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.samples.len().to_u64().unwrap()
+    }
+
+    /// Return the number of samples clamped in each tail.
+    ///
+    /// This is `trim * len()`, rounded down.
+    #[inline]
+    pub fn trimmed_len(&self) -> usize {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        (self.trim * self.samples.len().to_f64().unwrap()).floor().to_usize().unwrap()
+    }
+
+    /// Estimate the winsorized mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let mut sorted = self.samples.clone();
+        sort_floats(&mut sorted);
+        let n = sorted.len();
+        let k = self.trimmed_len();
+        let (low, high) = (sorted[k], sorted[n - k - 1]);
+        let clamped = k.to_f64().unwrap() * (low + high);
+        (clamped + sorted[k..n - k].iter().sum::<f64>()) / n.to_f64().unwrap()
+    }
+}
+
+impl Estimate for WinsorizedMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.samples.push(x);
+    }
+
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for WinsorizedMean {
+    /// Merge another sample into this one.
+    ///
+    /// This is exact. Panics if the trim fractions differ.
+    #[inline]
+    fn merge(&mut self, other: &WinsorizedMean) {
+        assert_eq!(self.trim, other.trim, "Both estimators must have the same trim fraction");
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
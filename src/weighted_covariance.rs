@@ -0,0 +1,331 @@
+use core;
+
+use super::Merge;
+
+/// The interpretation of the weights of a weighted estimator.
+///
+/// This affects the normalization of the unbiased variance estimates. Both
+/// interpretations give the same unbiased estimates for unit weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum WeightKind {
+    /// The weights describe the relative importance of the observations, for
+    /// example their precisions.
+    ///
+    /// The sums of squares are normalized by `V_1 - V_2 / V_1`, where `V_1`
+    /// is the sum of the weights and `V_2` is the sum of the squared weights.
+    /// Scaling all weights by a constant does not change the result. This is
+    /// the default.
+    Reliability,
+    /// The weights are integer counts of how often the observations occured.
+    ///
+    /// The sums of squares are normalized by `V_1 - 1`, where `V_1` is the sum
+    /// of the weights, i.e. the number of observations. This gives the same
+    /// result as adding each observation as often as its weight with unit
+    /// weights.
+    Frequency,
+}
+
+impl core::default::Default for WeightKind {
+    fn default() -> WeightKind {
+        WeightKind::Reliability
+    }
+}
+
+/// Estimate the weighted arithmetic means, variances and covariance of a
+/// sequence of pairs of numbers ("population").
+///
+/// By default, the weights are interpreted as *reliability* weights, i.e. they
+/// describe the relative importance of the observations and not how often they
+/// occured. The variances and the covariance are then normalized by
+/// `V_1 - V_2 / V_1`, where `V_1` is the sum of the weights and `V_2` is the
+/// sum of the squared weights. With `with_weight_kind(WeightKind::Frequency)`,
+/// the weights are interpreted as counts instead, and the normalization is
+/// `V_1 - 1`. For unit weights, both reduce to the unbiased sample
+/// estimators.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedCoVariance;
+///
+/// let mut a = WeightedCoVariance::new();
+/// a.add(1., 2., 0.5);
+/// a.add(2., 3., 1.0);
+/// a.add(3., 5., 1.5);
+/// println!("The weighted covariance is {}.", a.weighted_covariance());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedCoVariance {
+    /// Sum of the weights.
+    weight_sum: f64,
+    /// Sum of the squares of the weights.
+    weight_sum_sq: f64,
+    /// Weighted X mean value.
+    weighted_avg_x: f64,
+    /// Weighted Y mean value.
+    weighted_avg_y: f64,
+    /// Intermediate weighted sum of products for calculating the covariance.
+    sum_2: f64,
+    /// Intermediate weighted sum of squares for calculating the X variance.
+    sum_2_x: f64,
+    /// Intermediate weighted sum of squares for calculating the Y variance.
+    sum_2_y: f64,
+    /// Interpretation of the weights.
+    weight_kind: WeightKind,
+}
+
+impl WeightedCoVariance {
+    /// Create a new weighted covariance estimator.
+    #[inline]
+    pub fn new() -> WeightedCoVariance {
+        WeightedCoVariance {
+            weight_sum: 0., weight_sum_sq: 0.,
+            weighted_avg_x: 0., weighted_avg_y: 0.,
+            sum_2: 0., sum_2_x: 0., sum_2_y: 0.,
+            weight_kind: WeightKind::Reliability,
+        }
+    }
+
+    /// Create a new weighted covariance estimator with the given
+    /// interpretation of the weights.
+    #[inline]
+    pub fn with_weight_kind(weight_kind: WeightKind) -> WeightedCoVariance {
+        WeightedCoVariance { weight_kind, ..WeightedCoVariance::new() }
+    }
+
+    /// Return the interpretation of the weights.
+    #[inline]
+    pub fn weight_kind(&self) -> WeightKind {
+        self.weight_kind
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The interpretation of the weights is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedCoVariance::with_weight_kind(self.weight_kind);
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample_x: f64, sample_y: f64, weight: f64) {
+        // The algorithm for the weighted mean was suggested by West in 1979.
+        //
+        // See
+        // https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
+        // and
+        // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
+        self.weight_sum += weight;
+        self.weight_sum_sq += weight*weight;
+        if self.weight_sum == 0. {
+            return;
+        }
+
+        let delta_x = sample_x - self.weighted_avg_x;
+        let delta_y = sample_y - self.weighted_avg_y;
+        let factor = weight / self.weight_sum;
+        self.weighted_avg_x += factor * delta_x;
+        self.weighted_avg_y += factor * delta_y;
+
+        self.sum_2 += weight * delta_x * (sample_y - self.weighted_avg_y);
+        self.sum_2_x += weight * delta_x * (sample_x - self.weighted_avg_x);
+        self.sum_2_y += weight * delta_y * (sample_y - self.weighted_avg_y);
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weight_sum == 0.
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Return the sum of the squared weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights_sq(&self) -> f64 {
+        self.weight_sum_sq
+    }
+
+    /// Calculate the effective sample size `(Σw)² / Σw²`.
+    ///
+    /// See `WeightedMeanWithError::effective_len`.
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        if self.weight_sum_sq == 0. {
+            return 0.;
+        }
+        self.weight_sum * self.weight_sum / self.weight_sum_sq
+    }
+
+    /// Estimate the weighted mean of the X population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn weighted_mean_x(&self) -> f64 {
+        self.weighted_avg_x
+    }
+
+    /// Estimate the weighted mean of the Y population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn weighted_mean_y(&self) -> f64 {
+        self.weighted_avg_y
+    }
+
+    /// Calculate the normalization of the sums of squares, see `WeightKind`.
+    #[inline]
+    fn normalization(&self) -> f64 {
+        if self.weight_sum == 0. {
+            return 0.;
+        }
+        match self.weight_kind {
+            WeightKind::Reliability => self.weight_sum - self.weight_sum_sq / self.weight_sum,
+            WeightKind::Frequency => {
+                let norm = self.weight_sum - 1.;
+                if norm > 0. { norm } else { 0. }
+            },
+        }
+    }
+
+    /// Estimate the weighted covariance of the population.
+    ///
+    /// This is an unbiased estimator for the interpretation of the weights
+    /// given at construction. Returns 0 if there are not enough samples.
+    #[inline]
+    pub fn weighted_covariance(&self) -> f64 {
+        let norm = self.normalization();
+        if norm == 0. {
+            return 0.;
+        }
+        self.sum_2 / norm
+    }
+
+    /// Estimate the weighted variance of the X population.
+    ///
+    /// This is an unbiased estimator for the interpretation of the weights
+    /// given at construction. Returns 0 if there are not enough samples.
+    #[inline]
+    pub fn weighted_variance_x(&self) -> f64 {
+        let norm = self.normalization();
+        if norm == 0. {
+            return 0.;
+        }
+        self.sum_2_x / norm
+    }
+
+    /// Estimate the weighted variance of the Y population.
+    ///
+    /// This is an unbiased estimator for the interpretation of the weights
+    /// given at construction. Returns 0 if there are not enough samples.
+    #[inline]
+    pub fn weighted_variance_y(&self) -> f64 {
+        let norm = self.normalization();
+        if norm == 0. {
+            return 0.;
+        }
+        self.sum_2_y / norm
+    }
+
+    /// Estimate the weighted Pearson correlation coefficient of the
+    /// population.
+    ///
+    /// The normalizations of the weighted covariance and variances cancel, so
+    /// this does not depend on the interpretation of the weights. Returns 0
+    /// if one of the weighted variances is 0.
+    #[inline]
+    pub fn weighted_correlation(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        let denominator = (self.sum_2_x * self.sum_2_y).sqrt();
+        if denominator == 0. {
+            return 0.;
+        }
+        self.sum_2 / denominator
+    }
+}
+
+impl core::default::Default for WeightedCoVariance {
+    fn default() -> WeightedCoVariance {
+        WeightedCoVariance::new()
+    }
+}
+
+impl Merge for WeightedCoVariance {
+    /// Merge another sample into this one.
+    ///
+    /// The sums of the weights and of the squared weights are added, and the
+    /// weighted means and sums of products are combined like in
+    /// `CoVariance::merge`, so the result is the same as if all samples had
+    /// been added to one estimator, up to rounding errors. Panics if the
+    /// weights are interpreted differently.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{WeightedCoVariance, Merge};
+    ///
+    /// let sequence: &[(f64, f64, f64)] = &[
+    ///     (1., 2., 0.5), (2., 3., 1.), (3., 5., 1.5), (4., 4., 2.)];
+    /// let (left, right) = sequence.split_at(2);
+    /// let mut total = WeightedCoVariance::new();
+    /// for &(x, y, w) in sequence {
+    ///     total.add(x, y, w);
+    /// }
+    /// let mut a = WeightedCoVariance::new();
+    /// for &(x, y, w) in left {
+    ///     a.add(x, y, w);
+    /// }
+    /// let mut b = WeightedCoVariance::new();
+    /// for &(x, y, w) in right {
+    ///     b.add(x, y, w);
+    /// }
+    /// a.merge(&b);
+    /// assert!((total.weighted_covariance() - a.weighted_covariance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &WeightedCoVariance) {
+        assert_eq!(self.weight_kind, other.weight_kind,
+                   "Both estimators must interpret the weights the same way");
+        if other.weight_sum == 0. {
+            self.weight_sum_sq += other.weight_sum_sq;
+            return;
+        }
+        if self.weight_sum == 0. {
+            let weight_sum_sq = self.weight_sum_sq;
+            *self = other.clone();
+            self.weight_sum_sq += weight_sum_sq;
+            return;
+        }
+        let weight_sum = self.weight_sum + other.weight_sum;
+        self.weight_sum_sq += other.weight_sum_sq;
+        // This algorithm was proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let delta_x = other.weighted_avg_x - self.weighted_avg_x;
+        let delta_y = other.weighted_avg_y - self.weighted_avg_y;
+        let factor = self.weight_sum * other.weight_sum / weight_sum;
+        self.sum_2 += other.sum_2 + factor * delta_x * delta_y;
+        self.sum_2_x += other.sum_2_x + factor * delta_x * delta_x;
+        self.sum_2_y += other.sum_2_y + factor * delta_y * delta_y;
+        let ratio = other.weight_sum / weight_sum;
+        self.weighted_avg_x += ratio * delta_x;
+        self.weighted_avg_y += ratio * delta_y;
+        self.weight_sum = weight_sum;
+    }
+}
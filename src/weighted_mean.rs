@@ -1,11 +1,13 @@
 use core;
 
-use super::{MeanWithError, Estimate, Merge};
+use super::{MeanWithError, Merge};
 
 
 /// Estimate the weighted and unweighted arithmetic mean of a sequence of
 /// numbers ("population").
 ///
+/// The sum of the squared weights is tracked for the effective sample size.
+///
 ///
 /// ## Example
 ///
@@ -23,29 +25,32 @@ pub struct WeightedMean  {
     weight_sum: f64,
     /// Weighted mean value.
     weighted_avg: f64,
+    /// Sum of the squares of the weights.
+    ///
+    /// This is missing in the serialized form of older versions.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    weight_sum_sq: f64,
 }
 
 impl WeightedMean {
     /// Create a new weighted and unweighted mean estimator.
     pub fn new() -> WeightedMean {
         WeightedMean {
-            weight_sum: 0., weighted_avg: 0.,
+            weight_sum: 0., weighted_avg: 0., weight_sum_sq: 0.,
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedMean::new();
+    }
+
     /// Add an observation sampled from the population.
     #[inline]
     pub fn add(&mut self, sample: f64, weight: f64) {
-        // The algorithm for the unweighted mean was suggested by Welford in 1962.
-        //
-        // See
-        // https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
-        // and
-        // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
-        self.weight_sum += weight;
-
-        let prev_avg = self.weighted_avg;
-        self.weighted_avg = prev_avg + (weight / self.weight_sum) * (sample - prev_avg);
+        self.weight_sum_sq += weight*weight;
+        add_weighted(&mut self.weight_sum, &mut self.weighted_avg, sample, weight);
     }
 
     /// Determine whether the sample is empty.
@@ -64,6 +69,26 @@ impl WeightedMean {
         self.weight_sum
     }
 
+    /// Return the sum of the squared weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights_sq(&self) -> f64 {
+        self.weight_sum_sq
+    }
+
+    /// Calculate the effective sample size `(Σw)² / Σw²`.
+    ///
+    /// See `WeightedMeanWithError::effective_len`. Returns 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        if self.weight_sum_sq == 0. {
+            return 0.
+        }
+        self.weight_sum * self.weight_sum / self.weight_sum_sq
+    }
+
     /// Estimate the weighted mean of the population.
     ///
     /// Returns 0 for an empty sample.
@@ -106,6 +131,9 @@ impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedMean {
 impl Merge for WeightedMean {
     /// Merge another sample into this one.
     ///
+    /// The weighted means are combined using the sums of the weights, so the
+    /// result is the same as if all samples had been added to one estimator.
+    ///
     ///
     /// ## Example
     ///
@@ -124,11 +152,71 @@ impl Merge for WeightedMean {
     /// ```
     #[inline]
     fn merge(&mut self, other: &WeightedMean) {
-        let total_weight_sum = self.weight_sum + other.weight_sum;
-        self.weighted_avg = (self.weight_sum * self.weighted_avg
-                             + other.weight_sum * other.weighted_avg)
-                            / total_weight_sum;
-        self.weight_sum = total_weight_sum;
+        self.weight_sum_sq += other.weight_sum_sq;
+        merge_weighted(&mut self.weight_sum, &mut self.weighted_avg,
+                       other.weight_sum, other.weighted_avg);
+    }
+}
+
+/// Add a weighted sample to the sum of the weights and the weighted mean.
+#[inline]
+fn add_weighted(weight_sum: &mut f64, weighted_avg: &mut f64, sample: f64, weight: f64) {
+    // The algorithm for the unweighted mean was suggested by Welford in 1962.
+    //
+    // See
+    // https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
+    // and
+    // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
+    *weight_sum += weight;
+    if *weight_sum == 0. {
+        return;
+    }
+
+    let prev_avg = *weighted_avg;
+    *weighted_avg = prev_avg + (weight / *weight_sum) * (sample - prev_avg);
+}
+
+/// Merge another sum of the weights and weighted mean into the given ones.
+#[inline]
+fn merge_weighted(weight_sum: &mut f64, weighted_avg: &mut f64,
+                  other_weight_sum: f64, other_weighted_avg: f64) {
+    let total_weight_sum = *weight_sum + other_weight_sum;
+    if total_weight_sum == 0. {
+        return;
+    }
+    *weighted_avg = (*weight_sum * *weighted_avg + other_weight_sum * other_weighted_avg)
+                    / total_weight_sum;
+    *weight_sum = total_weight_sum;
+}
+
+/// The weighted mean without the sum of the squared weights.
+///
+/// This is used by `WeightedMeanWithError`, which tracks the squared weights
+/// itself, to keep its serialized form.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct WeightedAverage {
+    /// Sum of the weights.
+    weight_sum: f64,
+    /// Weighted mean value.
+    weighted_avg: f64,
+}
+
+impl WeightedAverage {
+    #[inline]
+    fn new() -> WeightedAverage {
+        WeightedAverage { weight_sum: 0., weighted_avg: 0. }
+    }
+
+    #[inline]
+    fn add(&mut self, sample: f64, weight: f64) {
+        add_weighted(&mut self.weight_sum, &mut self.weighted_avg, sample, weight);
+    }
+
+    #[inline]
+    fn merge(&mut self, other: &WeightedAverage) {
+        merge_weighted(&mut self.weight_sum, &mut self.weighted_avg,
+                       other.weight_sum, other.weighted_avg);
     }
 }
 
@@ -153,7 +241,7 @@ pub struct WeightedMeanWithError {
     /// Sum of the squares of the weights.
     weight_sum_sq: f64,
     /// Estimator of the weighted mean.
-    weighted_avg: WeightedMean,
+    weighted_avg: WeightedAverage,
     /// Estimator of unweighted mean and its variance.
     unweighted_avg: MeanWithError,
 }
@@ -164,11 +252,17 @@ impl WeightedMeanWithError {
     pub fn new() -> WeightedMeanWithError {
         WeightedMeanWithError {
             weight_sum_sq: 0.,
-            weighted_avg: WeightedMean::new(),
+            weighted_avg: WeightedAverage::new(),
             unweighted_avg: MeanWithError::new(),
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedMeanWithError::new();
+    }
+
     /// Add an observation sampled from the population.
     #[inline]
     pub fn add(&mut self, sample: f64, weight: f64) {
@@ -195,7 +289,7 @@ impl WeightedMeanWithError {
     /// Returns 0 for an empty sample.
     #[inline]
     pub fn sum_weights(&self) -> f64 {
-        self.weighted_avg.sum_weights()
+        self.weighted_avg.weight_sum
     }
 
     /// Return the sum of the squared weights.
@@ -211,7 +305,7 @@ impl WeightedMeanWithError {
     /// Returns 0 for an empty sample.
     #[inline]
     pub fn weighted_mean(&self) -> f64 {
-        self.weighted_avg.mean()
+        self.weighted_avg.weighted_avg
     }
 
     /// Estimate the unweighted mean of the population.
@@ -228,13 +322,18 @@ impl WeightedMeanWithError {
         self.unweighted_avg.len()
     }
 
-    /// Calculate the effective sample size.
+    /// Calculate the effective sample size `(Σw)² / Σw²`.
+    ///
+    /// For reliability weights, this is the number of unweighted samples that
+    /// would give an estimate of the same precision, which is useful for
+    /// reporting the degrees of freedom. For equal weights, it is the sample
+    /// size. Returns 0 for an empty sample.
     #[inline]
     pub fn effective_len(&self) -> f64 {
         if self.is_empty() {
             return 0.
         }
-        let weight_sum = self.weighted_avg.sum_weights();
+        let weight_sum = self.weighted_avg.weight_sum;
         weight_sum * weight_sum / self.weight_sum_sq
     }
 
@@ -262,11 +361,13 @@ impl WeightedMeanWithError {
     /// drawn from the same population with constant variance.
     #[inline]
     pub fn error(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
         // This uses the same estimate as WinCross, which should provide better
         // results than the ones used by SPSS or Mentor.
         //
         // See http://www.analyticalgroup.com/download/WEIGHTED_VARIANCE.pdf.
-        let weight_sum = self.weighted_avg.sum_weights();
+        let weight_sum = self.weighted_avg.weight_sum;
         if weight_sum == 0. {
             return 0.;
         }
@@ -278,6 +379,10 @@ impl WeightedMeanWithError {
 impl Merge for WeightedMeanWithError {
     /// Merge another sample into this one.
     ///
+    /// Besides the weighted and unweighted means, the sums of the squared
+    /// weights are combined, so the effective sample size and the error are
+    /// the same as if all samples had been added to one estimator.
+    ///
     ///
     /// ## Example
     ///
@@ -0,0 +1,312 @@
+use core;
+
+/// Estimate the weighted arithmetic mean, the weighted variance and the
+/// weighted skewness of a sequence of numbers ("population").
+///
+/// The skewness is calculated like the one of `Skewness`, using the sum of
+/// the weights instead of the sample size. It does not depend on the scale of
+/// the weights, and for unit weights it is the same as the unweighted
+/// skewness.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedSkewness;
+///
+/// let a: WeightedSkewness = (1..6).zip(1..6)
+///     .map(|(x, w)| (f64::from(x), f64::from(w))).collect();
+/// println!("The weighted skewness is {}.", a.weighted_skewness());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedSkewness {
+    /// Sum of the weights.
+    weight_sum: f64,
+    /// Weighted mean value.
+    weighted_avg: f64,
+    /// Intermediate weighted sum of squares for calculating the variance.
+    sum_2: f64,
+    /// Intermediate weighted sum of cubes for calculating the skewness.
+    sum_3: f64,
+}
+
+impl WeightedSkewness {
+    /// Create a new weighted skewness estimator.
+    #[inline]
+    pub fn new() -> WeightedSkewness {
+        WeightedSkewness {
+            weight_sum: 0., weighted_avg: 0., sum_2: 0., sum_3: 0.,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedSkewness::new();
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample: f64, weight: f64) {
+        let delta = sample - self.weighted_avg;
+        let prev_weight_sum = self.weight_sum;
+        self.weight_sum += weight;
+        if self.weight_sum == 0. {
+            return;
+        }
+        self.add_inner(delta, weight, prev_weight_sum);
+    }
+
+    /// Add an observation given an already calculated difference from the
+    /// mean, assuming the sum of the weights was already updated.
+    #[inline]
+    fn add_inner(&mut self, delta: f64, weight: f64, prev_weight_sum: f64) {
+        // This generalizes the algorithm suggested by Terriberry to weights,
+        // following the formulas for combining samples by Pébay.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let delta_w = delta * weight / self.weight_sum;
+        let term = delta * delta_w * prev_weight_sum;
+        self.sum_3 += term * delta * (prev_weight_sum - weight) / self.weight_sum
+            - 3. * delta_w * self.sum_2;
+        self.sum_2 += term;
+        self.weighted_avg += delta_w;
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weight_sum == 0.
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn weighted_mean(&self) -> f64 {
+        self.weighted_avg
+    }
+
+    /// Calculate the weighted population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population. Returns
+    /// 0 for an empty sample.
+    #[inline]
+    pub fn weighted_population_variance(&self) -> f64 {
+        if self.weight_sum == 0. {
+            return 0.;
+        }
+        self.sum_2 / self.weight_sum
+    }
+
+    /// Estimate the weighted skewness of the population.
+    #[inline]
+    pub fn weighted_skewness(&self) -> f64 {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        if self.sum_3 == 0. {
+            return 0.;
+        }
+        let sum_2 = self.sum_2;
+        debug_assert_ne!(sum_2, 0.);
+        self.weight_sum.sqrt() * self.sum_3 / (sum_2*sum_2*sum_2).sqrt()
+    }
+}
+
+impl core::default::Default for WeightedSkewness {
+    fn default() -> WeightedSkewness {
+        WeightedSkewness::new()
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for WeightedSkewness {
+    fn from_iter<T>(iter: T) -> WeightedSkewness
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = WeightedSkewness::new();
+        for (i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedSkewness {
+    fn from_iter<T>(iter: T) -> WeightedSkewness
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = WeightedSkewness::new();
+        for &(i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+/// Estimate the weighted arithmetic mean, the weighted variance, the weighted
+/// skewness and the weighted kurtosis of a sequence of numbers ("population").
+///
+/// The kurtosis is calculated like the one of `Kurtosis`, using the sum of the
+/// weights instead of the sample size. Like there, this is the *excess*
+/// kurtosis, which is 0 for a normal distribution. It does not depend on the
+/// scale of the weights, and for unit weights it is the same as the
+/// unweighted kurtosis.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedKurtosis;
+///
+/// let a: WeightedKurtosis = (1..6).zip(1..6)
+///     .map(|(x, w)| (f64::from(x), f64::from(w))).collect();
+/// println!("The weighted kurtosis is {}.", a.weighted_kurtosis());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedKurtosis {
+    /// Estimator of weighted mean, variance and skewness.
+    avg: WeightedSkewness,
+    /// Intermediate weighted sum of terms to the fourth for calculating the
+    /// kurtosis.
+    sum_4: f64,
+}
+
+impl WeightedKurtosis {
+    /// Create a new weighted kurtosis estimator.
+    #[inline]
+    pub fn new() -> WeightedKurtosis {
+        WeightedKurtosis {
+            avg: WeightedSkewness::new(),
+            sum_4: 0.,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedKurtosis::new();
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample: f64, weight: f64) {
+        let delta = sample - self.avg.weighted_avg;
+        let prev_weight_sum = self.avg.weight_sum;
+        self.avg.weight_sum += weight;
+        if self.avg.weight_sum == 0. {
+            return;
+        }
+        self.add_inner(delta, weight, prev_weight_sum);
+    }
+
+    /// Add an observation given an already calculated difference from the
+    /// mean, assuming the sum of the weights was already updated.
+    #[inline]
+    fn add_inner(&mut self, delta: f64, weight: f64, prev_weight_sum: f64) {
+        // This generalizes the algorithm suggested by Terriberry to weights,
+        // following the formulas for combining samples by Pébay.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let weight_sum = self.avg.weight_sum;
+        let delta_w = delta * weight / weight_sum;
+        let term = delta * delta_w * prev_weight_sum;
+        let delta_sq = delta*delta;
+        self.sum_4 += term * delta_sq
+            * (prev_weight_sum*prev_weight_sum - prev_weight_sum*weight + weight*weight)
+            / (weight_sum*weight_sum)
+            + 6. * delta_w*delta_w * self.avg.sum_2
+            - 4. * delta_w * self.avg.sum_3;
+        self.avg.add_inner(delta, weight, prev_weight_sum);
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.avg.sum_weights()
+    }
+
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn weighted_mean(&self) -> f64 {
+        self.avg.weighted_mean()
+    }
+
+    /// Calculate the weighted population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population. Returns
+    /// 0 for an empty sample.
+    #[inline]
+    pub fn weighted_population_variance(&self) -> f64 {
+        self.avg.weighted_population_variance()
+    }
+
+    /// Estimate the weighted skewness of the population.
+    #[inline]
+    pub fn weighted_skewness(&self) -> f64 {
+        self.avg.weighted_skewness()
+    }
+
+    /// Estimate the weighted excess kurtosis of the population.
+    #[inline]
+    pub fn weighted_kurtosis(&self) -> f64 {
+        if self.sum_4 == 0. {
+            return 0.;
+        }
+        let sum_2 = self.avg.sum_2;
+        self.avg.weight_sum * self.sum_4 / (sum_2*sum_2) - 3.
+    }
+}
+
+impl core::default::Default for WeightedKurtosis {
+    fn default() -> WeightedKurtosis {
+        WeightedKurtosis::new()
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for WeightedKurtosis {
+    fn from_iter<T>(iter: T) -> WeightedKurtosis
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = WeightedKurtosis::new();
+        for (i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedKurtosis {
+    fn from_iter<T>(iter: T) -> WeightedKurtosis
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = WeightedKurtosis::new();
+        for &(i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
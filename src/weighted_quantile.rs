@@ -0,0 +1,275 @@
+use core;
+
+use num_traits::ToPrimitive;
+
+/// Sort the first `len` samples and their weights by the sample values.
+#[inline]
+fn sort_by_value(q: &mut [f64; 5], w: &mut [f64; 5], len: usize) {
+    // Insertion sort, there are at most 5 elements.
+    for i in 1..len {
+        let mut j = i;
+        while j > 0 && q[j - 1] > q[j] {
+            q.swap(j - 1, j);
+            w.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Estimate the weighted p-quantile of a sequence of numbers ("population").
+///
+/// The weights are interpreted as frequency weights: The weighted p-quantile
+/// is the smallest sample where the cumulative weight reaches `p` times the
+/// total weight.
+///
+/// Like `Quantile`, this uses constant space but does not provide any upper
+/// bound on the error. For unit weights, the results agree with those of
+/// `Quantile` up to rounding errors.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedQuantile;
+///
+/// let mut a = WeightedQuantile::new(0.5);
+/// a.add(1., 1.);
+/// a.add(2., 1.);
+/// a.add(3., 3.);
+/// assert_eq!(a.quantile(), 3.);
+/// ```
+// This uses a weighted variant of the P² algorithm introduced here:
+// http://www.cs.wustl.edu/~jain/papers/ftp/psqr.pdf
+//
+// The marker positions are cumulative weights instead of counts, and the
+// markers are moved in steps of the average weight.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedQuantile {
+    /// Marker heights.
+    q: [f64; 5],
+    /// Marker positions, or the weights of the samples if there are less than
+    /// 5 samples.
+    n: [f64; 5],
+    /// Increment in desired marker positions.
+    dm: [f64; 5],
+    /// Sample size.
+    len: u64,
+}
+
+impl WeightedQuantile {
+    /// Create a new weighted p-quantile estimator.
+    ///
+    /// Panics if `p` is not between 0 and 1.
+    #[inline]
+    pub fn new(p: f64) -> WeightedQuantile {
+        assert!((0. ..=1.).contains(&p));
+        WeightedQuantile {
+            q: [0.; 5],
+            n: [0.; 5],
+            dm: [0., p/2., p, (1. + p)/2., 1.],
+            len: 0,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The value of `p` is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = WeightedQuantile::new(self.p());
+    }
+
+    /// Return the value of `p` for this p-quantile.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.dm[2]
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        if self.len >= 5 {
+            return self.n[4];
+        }
+        self.n.iter().sum()
+    }
+
+    /// Parabolic prediction for marker height, moving the marker by `d`.
+    #[inline]
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        self.q[i] + d / (self.n[i + 1] - self.n[i - 1])
+            * ((self.n[i] - self.n[i - 1] + d)
+               * (self.q[i + 1] - self.q[i])
+               / (self.n[i + 1] - self.n[i])
+               + (self.n[i + 1] - self.n[i] - d)
+               * (self.q[i] - self.q[i - 1])
+               / (self.n[i] - self.n[i - 1]))
+    }
+
+    /// Linear prediction for marker height, moving the marker by `d`.
+    #[inline]
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let sum = if d < 0. { i - 1 } else { i + 1 };
+        self.q[i] + d * (self.q[sum] - self.q[i]) / (self.n[sum] - self.n[i])
+    }
+
+    /// Add an observation sampled from the population with the given weight.
+    ///
+    /// Samples with a weight of zero are ignored. The weight must not be
+    /// negative.
+    #[inline]
+    pub fn add(&mut self, x: f64, weight: f64) {
+        #[allow(unused_imports)]
+        use num_traits::Float;
+        debug_assert!(weight >= 0., "weight must not be negative");
+        if weight == 0. {
+            return;
+        }
+        if self.len < 5 {
+            let i = self.len.to_usize().unwrap();  // < 5
+            self.q[i] = x;
+            self.n[i] = weight;
+            self.len += 1;
+            if self.len == 5 {
+                sort_by_value(&mut self.q, &mut self.n, 5);
+                for i in 1..5 {
+                    self.n[i] += self.n[i - 1];
+                }
+            }
+            return;
+        }
+
+        // Find cell k.
+        let mut k: usize;
+        if x < self.q[0] {
+            self.q[0] = x;
+            k = 0;
+        } else {
+            k = 4;
+            for i in 1..5 {
+                if x < self.q[i] {
+                    k = i;
+                    break;
+                }
+            }
+            if self.q[4] < x {
+                self.q[4] = x;
+            }
+        };
+
+        // Increment all positions greater than k.
+        for i in k..5 {
+            self.n[i] += weight;
+        }
+        self.len += 1;
+
+        // The markers are moved in steps of the average weight, as often as an
+        // unweighted sample would move them for this weight.
+        let total = self.n[4];
+        let step = total / self.len.to_f64().unwrap();
+        let max_moves = (weight / step).round().max(1.);
+
+        // Adjust height of markers.
+        for i in 1..4 {
+            let desired = step + self.dm[i] * (total - step);
+            let mut moves = 0.;
+            while moves < max_moves {
+                let d = desired - self.n[i];
+                if !(d >= step && self.n[i + 1] - self.n[i] > step ||
+                     d <= -step && self.n[i - 1] - self.n[i] < -step) {
+                    break;
+                }
+                let d = d.signum() * step;
+                let q_new = self.parabolic(i, d);
+                if self.q[i - 1] < q_new && q_new < self.q[i + 1] {
+                    self.q[i] = q_new;
+                } else {
+                    self.q[i] = self.linear(i, d);
+                }
+                self.n[i] += d;
+                moves += 1.;
+            }
+        }
+    }
+
+    /// Estimate the weighted p-quantile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn quantile(&self) -> f64 {
+        if self.len >= 5 {
+            return self.q[2];
+        }
+
+        // Estimate quantile by sorting the sample.
+        if self.is_empty() {
+            return 0.;
+        }
+        let len = self.len.to_usize().unwrap();  // < 5
+        let mut heights = self.q;
+        let mut weights = self.n;
+        sort_by_value(&mut heights, &mut weights, len);
+        let target = self.p() * self.sum_weights();
+        let mut cumulative = 0.;
+        for i in 0..len {
+            cumulative += weights[i];
+            if cumulative == target && i < len - 1 {
+                // `heights[i]` and `heights[i + 1]` are equally valid
+                // estimates, by convention we take their average.
+                return 0.5*heights[i] + 0.5*heights[i + 1];
+            }
+            if cumulative >= target {
+                return heights[i];
+            }
+        }
+        heights[len - 1]
+    }
+}
+
+impl core::default::Default for WeightedQuantile {
+    /// Create a new weighted median estimator.
+    fn default() -> WeightedQuantile {
+        WeightedQuantile::new(0.5)
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for WeightedQuantile {
+    /// Create a new weighted median estimator from samples and weights.
+    fn from_iter<T>(iter: T) -> WeightedQuantile
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = WeightedQuantile::default();
+        for (x, w) in iter {
+            a.add(x, w);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedQuantile {
+    /// Create a new weighted median estimator from samples and weights.
+    fn from_iter<T>(iter: T) -> WeightedQuantile
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = WeightedQuantile::default();
+        for &(x, w) in iter {
+            a.add(x, w);
+        }
+        a
+    }
+}
@@ -0,0 +1,75 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate rand;
+extern crate rand_xoshiro;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use average::{AllMeans, GeometricMean, HarmonicMean, Mean, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = AllMeans::new();
+    assert!(a.is_empty());
+    assert!(a.satisfies_inequality());
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 3.);
+    assert_almost_eq!(a.geometric_mean(), 3., 1e-15);
+    assert_almost_eq!(a.harmonic_mean(), 3., 1e-15);
+    assert!(a.satisfies_inequality());
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn random_positive() {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(3);
+    for _ in 0..20 {
+        let samples: Vec<f64> = (0..1000).map(|_| rng.gen_range(0.01, 100.)).collect();
+        let a: AllMeans = samples.iter().collect();
+        let mean: Mean = samples.iter().collect();
+        let geometric: GeometricMean = samples.iter().collect();
+        let harmonic: HarmonicMean = samples.iter().collect();
+        assert_eq!(a.mean(), mean.mean());
+        assert_eq!(a.geometric_mean(), geometric.geometric_mean());
+        assert_eq!(a.harmonic_mean(), harmonic.harmonic_mean());
+        assert!(a.mean() > a.geometric_mean());
+        assert!(a.geometric_mean() > a.harmonic_mean());
+        assert!(a.satisfies_inequality());
+    }
+}
+
+#[test]
+fn not_positive() {
+    let a: AllMeans = [-4., 1., 2.].iter().collect();
+    assert!(!a.satisfies_inequality());
+}
+
+#[test]
+fn merge() {
+    let samples = [1., 2., 3., 4., 5., 6.];
+    let total: AllMeans = samples.iter().collect();
+    let mut a: AllMeans = samples[..2].iter().collect();
+    let b: AllMeans = samples[2..].iter().collect();
+    a.merge(&b);
+    assert_eq!(a.len(), total.len());
+    assert_almost_eq!(a.mean(), total.mean(), 1e-15);
+    assert_almost_eq!(a.geometric_mean(), total.geometric_mean(), 1e-14);
+    assert_almost_eq!(a.harmonic_mean(), total.harmonic_mean(), 1e-14);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: AllMeans = [1., 2., 4.].iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: AllMeans = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), 3);
+    assert_almost_eq!(c.harmonic_mean(), a.harmonic_mean(), 1e-15);
+}
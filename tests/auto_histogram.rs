@@ -0,0 +1,113 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{AutoHistogram, Histogram};
+
+#[test]
+fn trivial() {
+    let mut h = AutoHistogram::new(0., 10., 10);
+    assert!(h.is_empty());
+    assert_eq!(h.len(), 10);
+    h.add(3.5).unwrap();
+    assert_eq!(h.bins(), &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(h.expansions(), 0);
+    assert!(h.add(std::f64::INFINITY).is_err());
+    assert!(h.add(std::f64::NAN).is_err());
+    assert_eq!((h.range_min(), h.range_max()), (0., 10.));
+    h.reset();
+    assert!(h.is_empty());
+}
+
+#[test]
+fn expansions() {
+    let mut h = AutoHistogram::new(0., 4., 4);
+    for &x in &[0.5, 1.5, 2.5, 3.5, 0.2] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(h.bins(), &[2, 1, 1, 1]);
+
+    // One expansion upwards: [0, 8).
+    h.add(5.).unwrap();
+    assert_eq!(h.ranges(), &[0., 2., 4., 6., 8.]);
+    assert_eq!(h.bins(), &[3, 2, 1, 0]);
+
+    // One expansion downwards: [-8, 8).
+    h.add(-3.).unwrap();
+    assert_eq!(h.ranges(), &[-8., -4., 0., 4., 8.]);
+    assert_eq!(h.bins(), &[0, 1, 5, 1]);
+    assert_eq!(h.expansions(), 2);
+
+    // Several expansions at once.
+    h.add(100.).unwrap();
+    assert_eq!(h.expansions(), 5);
+    assert_eq!((h.range_min(), h.range_max()), (-8., 120.));
+    assert_eq!(h.bins(), &[7, 0, 0, 1]);
+    let total: u64 = h.bins().iter().sum();
+    assert_eq!(total, 8);
+}
+
+#[test]
+fn conserved_count() {
+    let mut h = AutoHistogram::new(-1., 1., 20);
+    let samples: Vec<f64> = (0..1000).map(|i| (f64::from(i) * 0.37).sin() * f64::from(i)).collect();
+    for &x in &samples {
+        h.add(x).unwrap();
+    }
+    assert!(h.expansions() >= 2);
+    let total: u64 = h.bins().iter().sum();
+    assert_eq!(total, 1000);
+    for ((a, b), count) in h.iter() {
+        let expected = samples.iter().filter(|&&x| a <= x && x < b).count() as u64;
+        assert_eq!(count, expected);
+    }
+}
+
+#[test]
+fn range_overflow() {
+    let mut h = AutoHistogram::new(0., 10., 10);
+    for &x in &[1.5, 3.5, 25.] {
+        h.add(x).unwrap();
+    }
+    let bins = h.bins().to_vec();
+    let ranges = h.ranges().to_vec();
+    let expansions = h.expansions();
+    assert!(h.add(std::f64::MAX).is_err());
+    assert!(h.add(-std::f64::MAX).is_err());
+    assert_eq!(h.bins(), &bins[..]);
+    assert_eq!(h.ranges(), &ranges[..]);
+    assert_eq!(h.expansions(), expansions);
+    h.add(-5.).unwrap();
+    let total: u64 = h.bins().iter().sum();
+    assert_eq!(total, 4);
+    assert_eq!((h.range_min(), h.range_max()), (-40., 40.));
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn infinite_width() {
+    AutoHistogram::new(-std::f64::MAX, std::f64::MAX, 2);
+}
+
+#[test]
+#[should_panic(expected = "even")]
+fn odd_bins() {
+    AutoHistogram::new(0., 1., 3);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut h = AutoHistogram::new(0., 4., 4);
+    for &x in &[0.5, 1.5, 7.] {
+        h.add(x).unwrap();
+    }
+    let b = serde_json::to_string(&h).unwrap();
+    let c: AutoHistogram = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.bins(), h.bins());
+    assert_eq!(c.ranges(), h.ranges());
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate rand;
+
+use average::{AutoCovariance, CoVariance};
+
+#[test]
+fn trivial() {
+    let mut a = AutoCovariance::new(2);
+    assert!(a.is_empty());
+    assert_eq!(a.max_lag(), 2);
+    assert_eq!(a.autocovariance(0), 0.);
+    assert_eq!(a.autocovariance(1), 0.);
+    assert_eq!(a.autocorrelation(0), 1.);
+    assert_eq!(a.autocorrelation(2), 0.);
+    a.add(1.);
+    a.add(2.);
+    // Only one pair for lag 1 so far.
+    assert_eq!(a.autocovariance(1), 0.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.max_lag(), 2);
+}
+
+#[test]
+fn pairs() {
+    let xs: Vec<f64> = (0..50).map(|i| (f64::from(i) * 0.7).sin() + 0.1 * f64::from(i)).collect();
+    let mut a = AutoCovariance::new(3);
+    for &x in &xs {
+        a.add(x);
+    }
+    assert_eq!(a.len(), 50);
+    for lag in 1..4 {
+        let mut expected = CoVariance::new();
+        for t in lag..xs.len() {
+            expected.add(xs[t], xs[t - lag]);
+        }
+        assert_eq!(a.autocovariance(lag), expected.sample_covariance());
+        assert_eq!(a.autocorrelation(lag), expected.pearson());
+    }
+}
+
+#[test]
+fn ar1() {
+    use rand::distributions::{Distribution, Normal};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let normal = Normal::new(0., 1.);
+    let phi = 0.7;
+    let mut a = AutoCovariance::new(2);
+    let mut x = 0.;
+    for _ in 0..100_000 {
+        x = phi * x + normal.sample(&mut rng);
+        a.add(x);
+    }
+    assert_almost_eq!(a.autocorrelation(1), phi, 0.01);
+    assert_almost_eq!(a.autocorrelation(2), phi * phi, 0.01);
+    // The variance of the process is 1 / (1 - phi²).
+    assert_almost_eq!(a.autocovariance(0), 1. / (1. - phi * phi), 0.05);
+}
+
+#[test]
+#[should_panic(expected = "maximal lag")]
+fn lag_too_large() {
+    let a = AutoCovariance::new(2);
+    a.autocovariance(3);
+}
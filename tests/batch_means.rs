@@ -0,0 +1,94 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+extern crate rand;
+extern crate rand_xoshiro;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use average::{BatchMeansMean, Estimate, MeanWithError};
+
+#[test]
+fn trivial() {
+    let mut a = BatchMeansMean::new(2);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.standard_error(), 0.);
+    a.add(1.);
+    assert_eq!(a.num_batches(), 0);
+    a.add(3.);
+    assert_eq!(a.num_batches(), 1);
+    assert_eq!(a.standard_error(), 0.);
+    a.add(5.);
+    a.add(7.);
+    a.add(100.);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.num_batches(), 2);
+    assert_eq!(a.mean(), 23.2);
+    assert_eq!(a.estimate(), 23.2);
+    assert_eq!(a.batch_means().mean(), 4.);
+    // The batch means are 2 and 6.
+    assert_eq!(a.standard_error(), 2.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.batch_size(), 2);
+}
+
+#[test]
+fn unit_batches() {
+    let mut a = BatchMeansMean::new(1);
+    let mut b = MeanWithError::new();
+    for i in 0..100 {
+        let x = f64::from(i).sin();
+        a.add(x);
+        b.add(x);
+    }
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(a.standard_error(), b.error(), 1e-14);
+}
+
+#[test]
+fn autocorrelated() {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(42);
+    let phi = 0.9;
+    let mut x = 0.;
+    let mut a = BatchMeansMean::new(1000);
+    let mut naive = MeanWithError::new();
+    for _ in 0..100_000 {
+        x = phi * x + rng.gen::<f64>() - 0.5;
+        a.add(x);
+        naive.add(x);
+    }
+    assert_eq!(a.num_batches(), 100);
+    assert_almost_eq!(a.mean(), naive.mean(), 1e-12);
+    // For an AR(1) process, the variance of the mean is larger by a factor of
+    // about (1 + phi) / (1 - phi) = 19 than for independent samples.
+    let ratio = a.standard_error() / naive.error();
+    assert!(ratio > 3. && ratio < 6., "ratio: {}", ratio);
+}
+
+#[test]
+#[should_panic]
+fn zero_batch_size() {
+    BatchMeansMean::new(0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = BatchMeansMean::new(2);
+    for &x in &[1., 3., 5.] {
+        a.add(x);
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let mut c: BatchMeansMean = serde_json::from_str(&b).unwrap();
+    c.add(7.);
+    assert_eq!(c.num_batches(), 2);
+    assert_eq!(c.mean(), 4.);
+    assert_eq!(c.standard_error(), 2.);
+}
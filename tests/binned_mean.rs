@@ -0,0 +1,151 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{BinnedMean, Merge};
+
+fn f(x: f64) -> f64 {
+    2. * x + 1.
+}
+
+fn data() -> Vec<(f64, f64)> {
+    (0..1000).map(|i| {
+        let x = f64::from(i) * 0.01;
+        (x, f(x))
+    }).collect()
+}
+
+#[test]
+fn trivial() {
+    let mut a = BinnedMean::with_uniform_bins(0., 1., 2);
+    assert_eq!(a.len(), 2);
+    assert!(a.is_empty());
+    assert_eq!(a.bin_mean(0), 0.);
+    assert_eq!(a.add(0.5, 3.), Ok(()));
+    assert_eq!(a.add(1., 3.), Err(()));
+    assert_eq!(a.add(-0.1, 3.), Err(()));
+    assert_eq!(a.add(std::f64::NAN, 3.), Err(()));
+    assert_eq!(a.bin_count(0), 0);
+    assert_eq!(a.bin_count(1), 1);
+    assert_eq!(a.bin_mean(1), 3.);
+    assert_eq!(a.bin(1).len(), 1);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.ranges(), &[0., 0.5, 1.]);
+}
+
+#[test]
+fn linear() {
+    let mut a = BinnedMean::with_uniform_bins(0., 10., 10);
+    for (x, y) in data() {
+        a.add(x, y).unwrap();
+    }
+    for i in 0..a.len() {
+        let center = 0.5 * (a.ranges()[i] + a.ranges()[i + 1]);
+        assert_eq!(a.bin_count(i), 100);
+        assert_almost_eq!(a.bin_mean(i), f(center), 0.02);
+    }
+}
+
+#[test]
+fn with_edges() {
+    assert!(BinnedMean::with_edges(&[1.]).is_err());
+    assert!(BinnedMean::with_edges(&[1., 0.]).is_err());
+    assert!(BinnedMean::with_edges(&[0., std::f64::NAN]).is_err());
+    let mut a = BinnedMean::with_edges(&[0., 1., 5., 10.]).unwrap();
+    for (x, y) in data() {
+        a.add(x, y).unwrap();
+    }
+    assert_eq!(a.bin_count(0), 100);
+    assert_eq!(a.bin_count(1), 400);
+    assert_eq!(a.bin_count(2), 500);
+    assert_almost_eq!(a.bin_mean(1), f(2.995), 1e-12);
+}
+
+#[test]
+fn merge() {
+    let data = data();
+    let mut total = BinnedMean::with_uniform_bins(0., 10., 7);
+    for &(x, y) in &data {
+        total.add(x, y).unwrap();
+    }
+    let (left, right) = data.split_at(321);
+    let mut a = BinnedMean::with_uniform_bins(0., 10., 7);
+    let mut b = a.clone();
+    for &(x, y) in left {
+        a.add(x, y).unwrap();
+    }
+    for &(x, y) in right {
+        b.add(x, y).unwrap();
+    }
+    a.merge(&b);
+    for i in 0..a.len() {
+        assert_eq!(a.bin_count(i), total.bin_count(i));
+        assert_almost_eq!(a.bin_mean(i), total.bin_mean(i), 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_uniform_bins_empty_range() {
+    BinnedMean::with_uniform_bins(1., 1., 3);
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_uniform_bins_infinite_range() {
+    BinnedMean::with_uniform_bins(0., std::f64::INFINITY, 3);
+}
+
+#[test]
+#[should_panic]
+fn merge_different_ranges() {
+    let mut a = BinnedMean::with_uniform_bins(0., 10., 7);
+    a.merge(&BinnedMean::with_uniform_bins(0., 10., 8));
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = BinnedMean::with_uniform_bins(0., 10., 10);
+    for (x, y) in data() {
+        a.add(x, y).unwrap();
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let c: BinnedMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.ranges(), a.ranges());
+    for i in 0..a.len() {
+        assert_eq!(c.bin_count(i), a.bin_count(i));
+        assert_almost_eq!(c.bin_mean(i), a.bin_mean(i), 1e-12);
+    }
+}
+
+#[test]
+fn errors() {
+    let mut a = BinnedMean::with_uniform_bins(0., 2., 2);
+    assert_eq!(a.bin_error(0), 0.);
+    a.add(0.5, 1.).unwrap();
+    assert_eq!(a.bin_error(0), 0.);
+    // A noisy, but deterministic signal.
+    let noise = |i: u32| (f64::from(i) * 1.7).sin();
+    let mut previous = std::f64::INFINITY;
+    let mut i = 0;
+    for &n in &[10, 100, 1000, 10000] {
+        while a.bin_count(1) < n {
+            a.add(1.5, 5. + noise(i)).unwrap();
+            i += 1;
+        }
+        let error = a.bin_error(1);
+        assert!(error > 0. && error < previous, "{} >= {}", error, previous);
+        previous = error;
+    }
+    assert!(previous < 0.01);
+    assert_almost_eq!(a.bin_mean(1), 5., 0.01);
+    assert_eq!(a.bin_error(1), a.bin(1).error());
+    assert_eq!(a.bin_error(0), 0.);
+}
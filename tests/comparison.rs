@@ -0,0 +1,54 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use average::{Variance, cohens_d, welch_t};
+
+fn groups() -> (Variance, Variance) {
+    let a: Variance = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+    let b: Variance = [1., 2., 3., 4., 5.].iter().collect();
+    (a, b)
+}
+
+#[test]
+fn cohens_d_reference() {
+    let (a, b) = groups();
+    // (5 - 3) / sqrt((7 * 32/7 + 4 * 2.5) / 11)
+    assert_almost_eq!(cohens_d(&a, &b), 1.023532631438318, 1e-14);
+    assert_almost_eq!(cohens_d(&b, &a), -1.023532631438318, 1e-14);
+    assert_eq!(cohens_d(&a, &a), 0.);
+}
+
+#[test]
+fn cohens_d_zero_variance() {
+    let a: Variance = [3., 3., 3.].iter().collect();
+    let b: Variance = [1., 1.].iter().collect();
+    assert_eq!(cohens_d(&a, &b), std::f64::INFINITY);
+    assert_eq!(cohens_d(&b, &a), std::f64::NEG_INFINITY);
+    assert_eq!(cohens_d(&a, &a), 0.);
+    assert_eq!(cohens_d(&Variance::new(), &Variance::new()), 0.);
+}
+
+#[test]
+fn welch_t_reference() {
+    let (a, b) = groups();
+    // Reference values from `scipy.stats.ttest_ind(a, b, equal_var=False)`.
+    let (t, df) = welch_t(&a, &b);
+    assert_almost_eq!(t, 1.9321835661585918, 1e-14);
+    assert_almost_eq!(df, 10.517529215358932, 1e-12);
+    let (t, df_swapped) = welch_t(&b, &a);
+    assert_almost_eq!(t, -1.9321835661585918, 1e-14);
+    assert_eq!(df, df_swapped);
+}
+
+#[test]
+fn welch_t_degenerate() {
+    let a: Variance = [3., 3., 3.].iter().collect();
+    let b: Variance = [1., 1.].iter().collect();
+    assert_eq!(welch_t(&a, &b), (std::f64::INFINITY, 3.));
+    assert_eq!(welch_t(&a, &a), (0., 4.));
+    let c: Variance = [1.].iter().collect();
+    assert_eq!(welch_t(&a, &c), (0., 0.));
+}
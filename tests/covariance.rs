@@ -0,0 +1,407 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{CoVariance, Mean, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = CoVariance::new();
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.pearson(), 0.0);
+    a.add(1.0, 2.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean_x(), 1.0);
+    assert_eq!(a.mean_y(), 2.0);
+    assert_eq!(a.sample_covariance(), 0.0);
+    assert_eq!(a.pearson(), 0.0);
+    a.add(1.0, 3.0);
+    assert_eq!(a.len(), 2);
+    // The X variance is zero.
+    assert_eq!(a.pearson(), 0.0);
+}
+
+#[test]
+fn pearson() {
+    let mut a = CoVariance::new();
+    for i in 1..6 {
+        let x = f64::from(i);
+        a.add(x, 3. * x - 1.);
+    }
+    assert_almost_eq!(a.pearson(), 1.0, 1e-15);
+
+    let mut a = CoVariance::new();
+    for i in 1..6 {
+        let x = f64::from(i);
+        a.add(x, -0.5 * x);
+    }
+    assert_almost_eq!(a.pearson(), -1.0, 1e-15);
+
+    let mut a = CoVariance::new();
+    for &(x, y) in &[(1., 2.), (2., 1.), (3., 4.), (4., 3.), (5., 5.)] {
+        a.add(x, y);
+    }
+    let expected = a.sample_covariance()
+        / (a.sample_variance_x().sqrt() * a.sample_variance_y().sqrt());
+    assert_almost_eq!(a.pearson(), expected, 1e-15);
+    assert_almost_eq!(a.pearson(), 0.8, 1e-15);
+}
+
+#[test]
+fn merge() {
+    let sequence: Vec<(f64, f64)> = (0..10_000)
+        .map(|i| {
+            let x = f64::from(i) * 0.01;
+            (x.sin() + x, (1.3 * x).cos() - 0.5 * x)
+        })
+        .collect();
+    let total = {
+        let mut a = CoVariance::new();
+        for &(x, y) in &sequence {
+            a.add(x, y);
+        }
+        a
+    };
+    for &mid in &[0, 1, 5_000, 7_321, sequence.len()] {
+        let (left, right) = sequence.split_at(mid);
+        let mut cov_left = CoVariance::new();
+        for &(x, y) in left {
+            cov_left.add(x, y);
+        }
+        let mut cov_right = CoVariance::new();
+        for &(x, y) in right {
+            cov_right.add(x, y);
+        }
+        cov_left.merge(&cov_right);
+        assert_eq!(total.len(), cov_left.len());
+        assert_almost_eq!(total.mean_x(), cov_left.mean_x(), 1e-10);
+        assert_almost_eq!(total.mean_y(), cov_left.mean_y(), 1e-10);
+        assert_almost_eq!(total.sample_covariance(), cov_left.sample_covariance(), 1e-10);
+        assert_almost_eq!(total.sample_variance_x(), cov_left.sample_variance_x(), 1e-10);
+        assert_almost_eq!(total.sample_variance_y(), cov_left.sample_variance_y(), 1e-10);
+    }
+}
+
+#[test]
+fn merge_empty() {
+    let mut a = CoVariance::new();
+    a.merge(&CoVariance::new());
+    assert!(a.is_empty());
+    assert_eq!(a.mean_x(), 0.0);
+    assert_eq!(a.sample_covariance(), 0.0);
+}
+
+#[test]
+fn linear_regression() {
+    let mut a = CoVariance::new();
+    assert_eq!(a.slope(), 0.0);
+    assert_eq!(a.intercept(), 0.0);
+    assert_eq!(a.predict(1.0), 0.0);
+    for i in 0..1000 {
+        let x = f64::from(i) * 0.1;
+        // Deterministic noise with zero mean over full periods.
+        let noise = 0.3 * (7.3 * x).sin();
+        a.add(x, 2.5 - 0.75 * x + noise);
+    }
+    assert_almost_eq!(a.slope(), -0.75, 1e-3);
+    assert_almost_eq!(a.intercept(), 2.5, 1e-2);
+    assert_almost_eq!(a.predict(10.), 2.5 - 7.5, 1e-2);
+}
+
+#[test]
+fn linear_regression_vertical() {
+    let mut a = CoVariance::new();
+    for i in 0..10 {
+        a.add(1.0, f64::from(i));
+    }
+    assert_eq!(a.slope(), 0.0);
+    assert_eq!(a.intercept(), 0.0);
+    assert_eq!(a.predict(1.0), 0.0);
+}
+
+#[test]
+fn r_squared() {
+    let mut a = CoVariance::new();
+    assert_eq!(a.r_squared(), 0.0);
+    a.add(1.0, 1.0);
+    assert_eq!(a.r_squared(), 0.0);
+
+    let mut a = CoVariance::new();
+    for i in 0..100 {
+        let x = f64::from(i);
+        a.add(x, 4. * x - 3.);
+    }
+    assert_almost_eq!(a.r_squared(), 1.0, 1e-14);
+
+    let mut a = CoVariance::new();
+    for &(x, y) in &[(-1., 1.), (0., -1.), (1., 1.), (0., 1.), (-1., -1.), (1., -1.)] {
+        a.add(x, y);
+    }
+    assert_almost_eq!(a.r_squared(), 0.0, 1e-15);
+}
+
+#[test]
+fn population_covariance() {
+    let a = CoVariance::new();
+    assert_eq!(a.population_covariance(), 0.0);
+    assert_eq!(a.population_variance_x(), 0.0);
+    assert_eq!(a.population_variance_y(), 0.0);
+
+    let mut a = CoVariance::new();
+    for &(x, y) in &[(1., 2.), (2., 1.), (3., 4.), (4., 3.), (5., 5.)] {
+        a.add(x, y);
+    }
+    let ratio = 4. / 5.;
+    assert_almost_eq!(a.population_covariance(), a.sample_covariance() * ratio, 1e-15);
+    assert_almost_eq!(a.population_variance_x(), a.sample_variance_x() * ratio, 1e-15);
+    assert_almost_eq!(a.population_variance_y(), a.sample_variance_y() * ratio, 1e-15);
+    assert_almost_eq!(a.population_covariance(), 1.6, 1e-15);
+    assert_almost_eq!(a.population_variance_x(), 2.0, 1e-15);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let sequence: Vec<(f64, f64)> = (0..100)
+        .map(|i| {
+            let x = f64::from(i);
+            (x, (0.1 * x).sin())
+        })
+        .collect();
+    let (first, second) = sequence.split_at(37);
+
+    let mut control = CoVariance::new();
+    for &(x, y) in &sequence {
+        control.add(x, y);
+    }
+
+    let mut a = CoVariance::new();
+    for &(x, y) in first {
+        a.add(x, y);
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let mut c: CoVariance = serde_json::from_str(&b).unwrap();
+    for &(x, y) in second {
+        c.add(x, y);
+    }
+    assert_eq!(c.len(), control.len());
+    assert_eq!(c.mean_x(), control.mean_x());
+    assert_eq!(c.mean_y(), control.mean_y());
+    assert_eq!(c.sample_covariance(), control.sample_covariance());
+    assert_eq!(c.sample_variance_x(), control.sample_variance_x());
+    assert_eq!(c.sample_variance_y(), control.sample_variance_y());
+}
+
+#[test]
+fn reset() {
+    let mut a = CoVariance::new();
+    for i in 0..10 {
+        a.add(f64::from(i), f64::from(i * i));
+    }
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.sample_covariance(), 0.0);
+    let mut expected = CoVariance::new();
+    for i in 0..5 {
+        a.add(f64::from(i), -f64::from(i));
+        expected.add(f64::from(i), -f64::from(i));
+    }
+    assert_eq!(a.len(), expected.len());
+    assert_eq!(a.mean_x(), expected.mean_x());
+    assert_eq!(a.mean_y(), expected.mean_y());
+    assert_eq!(a.sample_covariance(), expected.sample_covariance());
+}
+
+#[test]
+fn from_iter() {
+    let pairs: Vec<(f64, f64)> = (0..100)
+        .map(|i| {
+            let x = f64::from(i);
+            (x, (0.3 * x).sin() + 0.1 * x)
+        })
+        .collect();
+    let mut expected = CoVariance::new();
+    for &(x, y) in &pairs {
+        expected.add(x, y);
+    }
+
+    let a: CoVariance = pairs.iter().collect();
+    let b: CoVariance = pairs.clone().into_iter().collect();
+    let mut c: CoVariance = pairs[..50].iter().collect();
+    c.extend(pairs[50..].iter().cloned());
+    for e in &[a, b, c] {
+        assert_eq!(e.len(), expected.len());
+        assert_eq!(e.mean_x(), expected.mean_x());
+        assert_eq!(e.mean_y(), expected.mean_y());
+        assert_eq!(e.sample_covariance(), expected.sample_covariance());
+        assert_eq!(e.pearson(), expected.pearson());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn display() {
+    let a: CoVariance = [(1., 2.), (2., 1.), (3., 4.), (4., 3.), (5., 5.)].iter().collect();
+    assert_eq!(format!("{:.2}", a), "cov=2.00, corr=0.80 (n=5)");
+    let b = CoVariance::new();
+    assert_eq!(format!("{}", b), "cov=0, corr=0 (n=0)");
+}
+
+#[test]
+fn partial_eq() {
+    let pairs: Vec<(f64, f64)> = (0..100)
+        .map(|i| (f64::from(i), (f64::from(i) * 0.37).sin()))
+        .collect();
+    let a: CoVariance = pairs.iter().collect();
+    let b: CoVariance = pairs.iter().collect();
+    assert_eq!(a, b);
+    let mut c = CoVariance::new();
+    c.merge(&a);
+    assert_eq!(a, c);
+    let d: CoVariance = pairs[1..].iter().collect();
+    assert_ne!(a, d);
+}
+
+#[test]
+fn add_chained() {
+    let mut a = CoVariance::new();
+    a.add(1.0, 2.0).add(3.0, 4.0).add(2.0, 7.0);
+    let mut b = CoVariance::new();
+    b.add(1.0, 2.0);
+    b.add(3.0, 4.0);
+    b.add(2.0, 7.0);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn add_slice() {
+    let xs: Vec<f64> = (0..1000).map(|i| (f64::from(i) * 0.01).sin() * 100.).collect();
+    let ys: Vec<f64> = (0..1000).map(|i| f64::from(i) * 0.3 - 7.).collect();
+    let mut expected = CoVariance::new();
+    expected.add(1.5, -2.0);
+    for (&x, &y) in xs.iter().zip(&ys) {
+        expected.add(x, y);
+    }
+
+    let mut a = CoVariance::new();
+    a.add(1.5, -2.0);
+    a.add_slice(&xs[..300], &ys[..300]);
+    a.add_slice(&xs[300..], &ys[300..]);
+    a.add_slice(&[], &[]);
+    assert_eq!(a.len(), expected.len());
+    assert_almost_eq!(a.mean_x(), expected.mean_x(), 1e-10);
+    assert_almost_eq!(a.mean_y(), expected.mean_y(), 1e-10);
+    assert_almost_eq!(a.sample_covariance(), expected.sample_covariance(), 1e-10);
+    assert_almost_eq!(a.sample_variance_x(), expected.sample_variance_x(), 1e-10);
+    assert_almost_eq!(a.sample_variance_y(), expected.sample_variance_y(), 1e-10);
+}
+
+#[test]
+#[should_panic(expected = "same length")]
+fn add_slice_different_lengths() {
+    CoVariance::new().add_slice(&[1., 2.], &[1.]);
+}
+
+#[test]
+fn len_consistent() {
+    let mut a = CoVariance::new();
+    assert!(a.is_empty());
+    for i in 0..10 {
+        a.add(f64::from(i), f64::from(i * i));
+        assert_eq!(a.len(), (i + 1) as u64);
+        assert!(!a.is_empty());
+    }
+    a.add_slice(&[1., 2.], &[3., 4.]);
+    assert_eq!(a.len(), 12);
+    let b: CoVariance = (0..5).map(|i| (f64::from(i), 1.)).collect();
+    a.merge(&b);
+    assert_eq!(a.len(), 17);
+    a.reset();
+    assert_eq!(a.len(), 0);
+    assert!(a.is_empty());
+}
+
+#[test]
+fn from_means() {
+    let avg_x: Mean = [1., 2., 3.].iter().collect();
+    let avg_y: Mean = [4., 6., 8.].iter().collect();
+    let mut a = CoVariance::from_means(avg_x, avg_y);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean_x(), 2.);
+    assert_eq!(a.mean_y(), 6.);
+    // The cross terms of the previous samples are lost.
+    assert_eq!(a.sample_covariance(), 0.);
+    assert_eq!(a.sample_variance_x(), 0.);
+
+    // The previous samples behave like samples located at their means.
+    let mut b = CoVariance::new();
+    for _ in 0..3 {
+        b.add(2., 6.);
+    }
+    for &(x, y) in &[(0., 1.), (5., 9.), (3., 2.)] {
+        a.add(x, y);
+        b.add(x, y);
+    }
+    assert_eq!(a.len(), 6);
+    assert_almost_eq!(a.mean_x(), b.mean_x(), 1e-14);
+    assert_almost_eq!(a.mean_y(), b.mean_y(), 1e-14);
+    assert_almost_eq!(a.sample_covariance(), b.sample_covariance(), 1e-14);
+    assert_almost_eq!(a.sample_variance_y(), b.sample_variance_y(), 1e-14);
+    assert!(a.sample_covariance() > 0.);
+}
+
+#[test]
+#[should_panic(expected = "same sample size")]
+fn from_means_different_lengths() {
+    let avg_x: Mean = [1., 2.].iter().collect();
+    let avg_y: Mean = [1.].iter().collect();
+    CoVariance::from_means(avg_x, avg_y);
+}
+
+#[test]
+fn standard_deviation() {
+    let a: CoVariance = [(2., 1.), (4., 3.), (4., 2.), (4., 5.), (5., 4.), (5., 8.), (7., 6.), (9., 7.)]
+        .iter().collect();
+    assert_almost_eq!(a.population_standard_deviation_x(), 2., 1e-15);
+    assert_eq!(a.population_standard_deviation_x(), a.population_variance_x().sqrt());
+    assert_eq!(a.population_standard_deviation_y(), a.population_variance_y().sqrt());
+    assert_eq!(a.sample_standard_deviation_x(), a.sample_variance_x().sqrt());
+    assert_eq!(a.sample_standard_deviation_y(), a.sample_variance_y().sqrt());
+    assert_almost_eq!(a.sample_standard_deviation_y(), 6f64.sqrt(), 1e-15);
+
+    let mut b = CoVariance::new();
+    b.add(1., 2.);
+    assert_eq!(b.sample_standard_deviation_x(), 0.);
+    assert_eq!(b.population_standard_deviation_y(), 0.);
+}
+
+#[test]
+fn mean_estimators() {
+    let a: CoVariance = [(1., 10.), (2., 30.), (6., 20.)].iter().collect();
+    let x: &Mean = a.mean_x_estimator();
+    let y: &Mean = a.mean_y_estimator();
+    assert_eq!(x.mean(), a.mean_x());
+    assert_eq!(y.mean(), a.mean_y());
+    assert_eq!(x.len(), a.len());
+    assert_eq!(y.len(), a.len());
+    assert_eq!(x.mean(), 3.);
+    assert_eq!(y.mean(), 20.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn len_exhausted() {
+    let a: CoVariance = serde_json::from_str(
+        "{\"avg_x\":{\"avg\":1.0,\"n\":9007199254740992},\
+          \"avg_y\":{\"avg\":1.0,\"n\":9007199254740992},\
+          \"sum_2\":0.0,\"sum_2_x\":0.0,\"sum_2_y\":0.0}").unwrap();
+    assert!(!a.len_exhausted());
+    let mut b = a.clone();
+    b.add(1., 1.);
+    assert!(b.len_exhausted());
+    assert!(!CoVariance::new().len_exhausted());
+}
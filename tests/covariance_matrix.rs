@@ -0,0 +1,149 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{CoVariance, CovarianceMatrix, Merge};
+
+fn dataset() -> Vec<[f64; 3]> {
+    (0..200)
+        .map(|i| {
+            let t = f64::from(i) * 0.1;
+            [t.sin() + 0.3 * t, (1.7 * t).cos() - 0.1 * t, 2. * t.sin() - t.cos()]
+        })
+        .collect()
+}
+
+#[test]
+fn trivial() {
+    let mut a = CovarianceMatrix::new(2);
+    assert!(a.is_empty());
+    assert_eq!(a.dim(), 2);
+    assert_eq!(a.covariance(0, 1), 0.);
+    a.add(&[1., 2.]);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(0), 1.);
+    assert_eq!(a.mean(1), 2.);
+    assert_eq!(a.covariance(0, 1), 0.);
+    assert_eq!(a.correlation(0, 1), 0.);
+}
+
+#[test]
+fn versus_covariance() {
+    let rows = dataset();
+    let mut a = CovarianceMatrix::new(3);
+    for row in &rows {
+        a.add(row);
+    }
+    assert_eq!(a.len(), 200);
+    let covariance = a.covariance_matrix();
+    let correlation = a.correlation_matrix();
+    for i in 0..3 {
+        for j in 0..3 {
+            let b: CoVariance = rows.iter().map(|row| (row[i], row[j])).collect();
+            assert_almost_eq!(a.mean(i), b.mean_x(), 1e-14);
+            assert_almost_eq!(a.covariance(i, j), b.sample_covariance(), 1e-13);
+            assert_almost_eq!(a.correlation(i, j), b.pearson(), 1e-13);
+            assert_eq!(a.covariance(i, j), a.covariance(j, i));
+            assert_eq!(covariance[3 * i + j], a.covariance(i, j));
+            assert_eq!(correlation[3 * i + j], a.correlation(i, j));
+        }
+        assert_almost_eq!(a.variance(i), b_variance(&rows, i), 1e-13);
+        assert_almost_eq!(a.correlation(i, i), 1., 1e-15);
+    }
+}
+
+fn b_variance(rows: &[[f64; 3]], i: usize) -> f64 {
+    let b: average::Variance = rows.iter().map(|row| row[i]).collect();
+    b.sample_variance()
+}
+
+#[test]
+fn merge() {
+    let rows = dataset();
+    let mut total = CovarianceMatrix::new(3);
+    for row in &rows {
+        total.add(row);
+    }
+    for &mid in &[0, 1, 50, 199, 200] {
+        let (left, right) = rows.split_at(mid);
+        let mut a = CovarianceMatrix::new(3);
+        for row in left {
+            a.add(row);
+        }
+        let mut b = CovarianceMatrix::new(3);
+        for row in right {
+            b.add(row);
+        }
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        for i in 0..3 {
+            assert_almost_eq!(a.mean(i), total.mean(i), 1e-14);
+            for j in 0..3 {
+                assert_almost_eq!(a.covariance(i, j), total.covariance(i, j), 1e-13);
+            }
+        }
+    }
+}
+
+#[test]
+fn reset() {
+    let mut a = CovarianceMatrix::new(2);
+    a.add(&[1., 2.]);
+    a.add(&[3., 5.]);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.dim(), 2);
+    assert_eq!(a.mean(1), 0.);
+    assert_eq!(a.covariance(0, 1), 0.);
+}
+
+#[test]
+#[should_panic]
+fn wrong_row_length() {
+    let mut a = CovarianceMatrix::new(3);
+    a.add(&[1., 2.]);
+}
+
+#[test]
+#[should_panic]
+fn zero_dimension() {
+    CovarianceMatrix::new(0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = CovarianceMatrix::new(2);
+    a.add(&[1., 2.]);
+    a.add(&[3., 5.]);
+    let b = serde_json::to_string(&a).unwrap();
+    let c: CovarianceMatrix = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.covariance(0, 1), a.covariance(0, 1));
+}
+
+#[test]
+fn incremental_correlations() {
+    let mut a = CovarianceMatrix::new(4);
+    let mut pairs: Vec<CoVariance> = (0..16).map(|_| CoVariance::new()).collect();
+    for k in 0..100 {
+        let t = f64::from(k) * 0.05;
+        let base = t.sin();
+        let row = [base, 2. * base + 0.2 * (3. * t).cos(), -base + 0.1 * t, t.cos()];
+        a.add(&row);
+        for i in 0..4 {
+            for j in 0..4 {
+                let b = &mut pairs[4 * i + j];
+                b.add(row[i], row[j]);
+                assert_almost_eq!(a.correlation(i, j), b.pearson(), 1e-12);
+                assert_almost_eq!(a.covariance(i, j), b.sample_covariance(), 1e-12);
+            }
+        }
+    }
+    assert!(a.correlation(0, 1) > 0.9);
+    assert!(a.correlation(0, 2) < -0.5);
+}
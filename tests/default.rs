@@ -0,0 +1,56 @@
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+use average::{CoVariance, Kurtosis, Max, Mean, MeanWithError, Min, Quantile,
+              Quartiles, Range, Skewness, Variance, WeightedCoVariance,
+              WeightedMean, WeightedMeanWithError, WeightedQuantile};
+
+#[test]
+fn covariance() {
+    assert_eq!(CoVariance::default(), CoVariance::new());
+    assert_eq!(Mean::default(), Mean::new());
+    assert_eq!(Variance::default(), Variance::new());
+    assert_eq!(MeanWithError::default(), MeanWithError::new());
+}
+
+/// All estimators that can be created without parameters implement `Default`,
+/// so they can be used in structs deriving it.
+#[derive(Default)]
+struct Estimators {
+    mean: Mean,
+    variance: Variance,
+    mean_with_error: MeanWithError,
+    skewness: Skewness,
+    kurtosis: Kurtosis,
+    covariance: CoVariance,
+    weighted_covariance: WeightedCoVariance,
+    weighted_mean: WeightedMean,
+    weighted_mean_with_error: WeightedMeanWithError,
+    min: Min,
+    max: Max,
+    range: Range,
+    median: Quantile,
+    quartiles: Quartiles,
+    weighted_median: WeightedQuantile,
+}
+
+#[test]
+fn derive() {
+    let e = Estimators::default();
+    assert!(e.mean.is_empty());
+    assert!(e.variance.is_empty());
+    assert!(e.mean_with_error.is_empty());
+    assert!(e.skewness.is_empty());
+    assert!(e.kurtosis.is_empty());
+    assert_eq!(e.covariance.len(), 0);
+    assert!(e.weighted_covariance.is_empty());
+    assert!(e.weighted_mean.is_empty());
+    assert!(e.weighted_mean_with_error.is_empty());
+    assert_eq!(e.min.min(), std::f64::INFINITY);
+    assert_eq!(e.max.max(), std::f64::NEG_INFINITY);
+    assert!(e.range.is_empty());
+    assert_eq!(e.median.p(), 0.5);
+    assert_eq!(e.quartiles.len(), 0);
+    assert_eq!(e.weighted_median.p(), 0.5);
+}
@@ -0,0 +1,245 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+extern crate rand;
+
+use average::{DynHistogram, Histogram, Histogram10, Merge, OutOfRange};
+
+fn data() -> Vec<f64> {
+    (0..1000).map(|i| (f64::from(i) * 0.37).sin() * 1.2).collect()
+}
+
+#[test]
+fn with_uniform_bins() {
+    let n = "10".parse().unwrap();
+    let mut h = DynHistogram::with_uniform_bins(-1., 1., n);
+    let mut expected = Histogram10::with_const_width(-1., 1.);
+    assert_eq!(h.len(), 10);
+    assert!(h.is_empty());
+    for x in data() {
+        assert_eq!(h.add(x), expected.add(x));
+    }
+    assert!(!h.is_empty());
+    assert_eq!(h.ranges(), expected.ranges());
+    assert_eq!(h.bins(), expected.bins());
+    assert_eq!(h.underflow(), expected.underflow());
+    assert_eq!(h.overflow(), expected.overflow());
+    assert_eq!(h.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+    assert_eq!(h.range_min(), -1.);
+    assert_eq!(h.range_max(), 1.);
+    h.reset();
+    assert!(h.is_empty());
+}
+
+#[test]
+fn with_log_bins() {
+    let mut h = DynHistogram::with_log_bins(1e-6, 1., 6);
+    let ranges = h.ranges().to_vec();
+    for (i, &r) in ranges.iter().enumerate() {
+        assert!((r / 10f64.powi(i as i32 - 6) - 1.).abs() < 1e-12);
+    }
+    assert_eq!(h.range_max(), 1.);
+
+    // Values spread evenly in log space fill the bins uniformly.
+    for i in 0..6000 {
+        let x = 10f64.powf(-6. + (f64::from(i) + 0.5) / 1000.);
+        h.add(x).unwrap();
+    }
+    for &count in h.bins() {
+        assert!((999..=1001).contains(&count));
+    }
+    assert_eq!(h.add(1e-7), Err(()));
+    assert_eq!(h.add(2.), Err(()));
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_uniform_bins_empty_range() {
+    DynHistogram::with_uniform_bins(1., 1., 3);
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_uniform_bins_infinite_range() {
+    DynHistogram::with_uniform_bins(0., std::f64::INFINITY, 3);
+}
+
+#[test]
+#[should_panic(expected = "must be positive")]
+fn with_log_bins_zero() {
+    DynHistogram::with_log_bins(0., 1., 3);
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_log_bins_decreasing() {
+    DynHistogram::with_log_bins(10., 1., 3);
+}
+
+#[test]
+#[should_panic(expected = "finite and nonempty")]
+fn with_log_bins_infinite() {
+    DynHistogram::with_log_bins(1., std::f64::INFINITY, 3);
+}
+
+#[test]
+fn from_ranges() {
+    let mut h = DynHistogram::from_ranges([0., 0.1, 0.5, 2.].iter().cloned()).unwrap();
+    assert_eq!(h.len(), 3);
+    for &x in &[-1., 0., 0.05, 0.3, 1.0, 1.99, 2., 3.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.bins(), &[2, 1, 2]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.overflow(), 2);
+    assert_eq!(h.find(0.1), Ok(1));
+    assert_eq!(h.find(2.), Err(()));
+
+    assert!(DynHistogram::from_ranges([0.].iter().cloned()).is_err());
+    assert!(DynHistogram::from_ranges([0., 2., 1.].iter().cloned()).is_err());
+    assert!(DynHistogram::from_ranges([0., core::f64::NAN].iter().cloned()).is_err());
+}
+
+#[test]
+fn with_edges() {
+    let edges = [-100., -10., -1., -0.1, 0., 0.1, 1., 10., 100.];
+    let mut h = DynHistogram::with_edges(&edges).unwrap();
+    assert_eq!(h.len(), 8);
+    assert_eq!(h.ranges(), &edges[..]);
+    let values = [-50., -10., -5., -0.5, -0.05, 0., 0.05, 0.1, 0.5, 5., 99.9];
+    let expected_bins = [0, 1, 1, 2, 3, 4, 4, 5, 5, 6, 7];
+    for (&x, &i) in values.iter().zip(&expected_bins) {
+        assert_eq!(h.find(x), Ok(i));
+        h.add(x).unwrap();
+    }
+    assert_eq!(h.bins(), &[1, 2, 1, 1, 2, 2, 1, 1]);
+
+    assert!(DynHistogram::with_edges(&[]).is_err());
+    assert!(DynHistogram::with_edges(&[1.]).is_err());
+    assert!(DynHistogram::with_edges(&[0., 1., 0.5]).is_err());
+}
+
+#[test]
+fn merge() {
+    let data = data();
+    let mut total = DynHistogram::with_uniform_bins(-1., 1., 7);
+    for &x in &data {
+        let _ = total.add(x);
+    }
+    let (left, right) = data.split_at(300);
+    let mut h1 = DynHistogram::with_uniform_bins(-1., 1., 7);
+    for &x in left {
+        let _ = h1.add(x);
+    }
+    let mut h2 = DynHistogram::with_uniform_bins(-1., 1., 7);
+    for &x in right {
+        let _ = h2.add(x);
+    }
+    h1.merge(&h2);
+    assert_eq!(h1.bins(), total.bins());
+    assert_eq!(h1.underflow(), total.underflow());
+    assert_eq!(h1.overflow(), total.overflow());
+}
+
+#[test]
+#[should_panic(expected = "same ranges")]
+fn merge_different_ranges() {
+    let mut h1 = DynHistogram::with_uniform_bins(0., 1., 4);
+    let h2 = DynHistogram::with_uniform_bins(0., 1., 5);
+    h1.merge(&h2);
+}
+
+#[test]
+#[should_panic(expected = "same out-of-range mode")]
+fn merge_different_out_of_range() {
+    let mut h1 = DynHistogram::with_uniform_bins(0., 1., 4);
+    let h2 = DynHistogram::with_uniform_bins(0., 1., 4)
+        .with_out_of_range(OutOfRange::Clamp);
+    h1.merge(&h2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_csv() {
+    let mut h = DynHistogram::with_edges(&[0., 1., 10.]).unwrap();
+    for &x in &[-3., 0.5, 2., 5., 10.] {
+        let _ = h.add(x);
+    }
+    let mut out: Vec<u8> = Vec::new();
+    h.write_csv(&mut out).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let expected = "bin_lower,bin_upper,count\n-inf,0,1\n0,1,1\n1,10,2\n10,inf,1\n";
+    assert_eq!(csv, expected);
+}
+
+#[test]
+fn out_of_range_modes() {
+    let samples = [-5., 0.5, 2., 10., 11.];
+
+    let mut h = DynHistogram::with_edges(&[0., 1., 10.]).unwrap();
+    for &x in &samples {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.bins(), &[1, 1]);
+    assert_eq!((h.underflow(), h.overflow()), (1, 2));
+
+    let mut h = DynHistogram::with_edges(&[0., 1., 10.]).unwrap()
+        .with_out_of_range(OutOfRange::Clamp);
+    for &x in &samples {
+        assert!(h.add(x).is_ok());
+    }
+    assert_eq!(h.bins(), &[2, 3]);
+    assert_eq!((h.underflow(), h.overflow()), (0, 0));
+    assert_eq!(h.out_of_range(), OutOfRange::Clamp);
+}
+
+#[test]
+fn percentile_rank() {
+    use rand::distributions::{Distribution, Uniform};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut h = DynHistogram::with_uniform_bins(0., 100., 10);
+    assert_eq!(h.percentile_rank(50.), 0.);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let uniform = Uniform::new(0., 100.);
+    for _ in 0..100_000 {
+        h.add(uniform.sample(&mut rng)).unwrap();
+    }
+    assert_almost_eq!(h.percentile_rank(50.), 0.5, 0.01);
+    assert_almost_eq!(h.percentile_rank(25.), 0.25, 0.01);
+    assert_almost_eq!(h.percentile_rank(h.quantile(0.3)), 0.3, 1e-12);
+    assert_eq!(h.percentile_rank(-1.), 0.);
+    assert_eq!(h.percentile_rank(0.), 0.);
+    assert_eq!(h.percentile_rank(100.), 1.);
+    assert_eq!(h.percentile_rank(1000.), 1.);
+
+    let mut h = DynHistogram::with_uniform_bins(0., 10., 10);
+    for &x in &[-1., 0.5, 1.5, 11.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.percentile_rank(0.), 0.25);
+    assert_eq!(h.percentile_rank(1.5), 0.625);
+    assert_eq!(h.percentile_rank(9.), 0.75);
+}
+
+#[test]
+fn cumulative_with_out_of_range() {
+    let mut h = DynHistogram::with_uniform_bins(0., 10., 10);
+    assert_eq!(h.cumulative_with_underflow(), vec![0; 10]);
+    assert_eq!(h.cdf_with_out_of_range(), vec![0.; 10]);
+    for &x in &[-1., 0.5, 1.5, 1.7, 4., 9.5, 9.9, 12.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.cumulative(), vec![1, 3, 3, 3, 4, 4, 4, 4, 4, 6]);
+    assert_eq!(h.cumulative_with_underflow(), vec![2, 4, 4, 4, 5, 5, 5, 5, 5, 7]);
+    let cdf = h.cdf_with_out_of_range();
+    for (i, &c) in cdf[..9].iter().enumerate() {
+        assert_eq!(c, h.percentile_rank(h.ranges()[i + 1]));
+    }
+    assert_eq!(cdf[9], 7. / 8.);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+use average::{
+    Estimate, Mean, Variance, Skewness, Kurtosis, Min, Max, Quantile, KahanMean,
+    GeometricMean, HarmonicMean, ExpMovingMean, Mode,
+};
+
+fn estimate_all<E: Estimate + Default>(samples: &[f64]) -> f64 {
+    let mut e = E::default();
+    for &x in samples {
+        e.add(x);
+    }
+    e.estimate()
+}
+
+#[test]
+fn generic() {
+    let samples = [1., 2., 3., 4., 5.];
+    assert_eq!(estimate_all::<Mean>(&samples), 3.);
+    assert_eq!(estimate_all::<Variance>(&samples), 2.);
+    assert_eq!(estimate_all::<Min>(&samples), 1.);
+    assert_eq!(estimate_all::<Max>(&samples), 5.);
+}
+
+#[test]
+fn trait_objects() {
+    let mut estimators: Vec<Box<dyn Estimate>> = vec![
+        Box::new(Mean::new()),
+        Box::new(KahanMean::new()),
+        Box::new(Variance::new()),
+        Box::new(Skewness::new()),
+        Box::new(Kurtosis::new()),
+        Box::new(Min::new()),
+        Box::new(Max::new()),
+        Box::new(Quantile::new(0.5)),
+        Box::new(GeometricMean::new()),
+        Box::new(HarmonicMean::new()),
+        Box::new(ExpMovingMean::new(1.)),
+        Box::new(Mode::new()),
+    ];
+    let samples = [1., 2., 2., 4.];
+    for e in &mut estimators {
+        for &x in &samples {
+            e.add(x);
+        }
+    }
+    let expected: Vec<f64> = vec![
+        samples.iter().collect::<Mean>().mean(),
+        samples.iter().collect::<KahanMean>().mean(),
+        samples.iter().collect::<Variance>().population_variance(),
+        samples.iter().collect::<Skewness>().skewness(),
+        samples.iter().collect::<Kurtosis>().kurtosis(),
+        1.,
+        4.,
+        2.,
+        samples.iter().collect::<GeometricMean>().geometric_mean(),
+        samples.iter().collect::<HarmonicMean>().harmonic_mean(),
+        4.,
+        2.,
+    ];
+    assert_eq!(estimators.len(), expected.len());
+    for (e, &x) in estimators.iter().zip(&expected) {
+        assert_eq!(e.estimate(), x);
+    }
+}
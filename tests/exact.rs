@@ -0,0 +1,33 @@
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+use average::{exact_mean, exact_variance, Variance};
+
+#[test]
+fn trivial() {
+    assert_eq!(exact_mean(&[]), 0.);
+    assert_eq!(exact_variance(&[]), 0.);
+    assert_eq!(exact_mean(&[2.]), 2.);
+    assert_eq!(exact_variance(&[2.]), 0.);
+    assert_eq!(exact_mean(&[1., 2., 3., 4., 5.]), 3.);
+    assert_eq!(exact_variance(&[1., 2., 3., 4., 5.]), 2.5);
+}
+
+#[test]
+fn ill_conditioned() {
+    // Large offset, small spread.
+    let offset = 1e9;
+    let n = 100_000;
+    let xs: Vec<f64> = (0..n).map(|i| offset + [4., 7., 13., 16.][i % 4]).collect();
+    let expected_mean = offset + 10.;
+    let expected_variance = 22.5 * (n as f64) / ((n - 1) as f64);
+
+    assert_eq!(exact_mean(&xs), expected_mean);
+    let error_exact = (exact_variance(&xs) - expected_variance).abs();
+    let streaming: Variance = xs.iter().collect();
+    let error_streaming = (streaming.sample_variance() - expected_variance).abs();
+    assert!(error_exact < 1e-10);
+    assert!(error_exact < error_streaming,
+            "exact error {} >= streaming error {}", error_exact, error_streaming);
+}
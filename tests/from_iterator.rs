@@ -0,0 +1,69 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use core::iter::FromIterator;
+
+use average::{Estimate, GeometricMean, KahanMean, Kurtosis, Max, Mean, MeanWithError, Min,
+              Quantile, Skewness, Sum, Variance};
+
+/// Collect the estimator from values, references and references of
+/// references, and check that the estimates are identical.
+fn check<E>()
+    where E: Estimate
+        + FromIterator<f64>
+        + for<'a> FromIterator<&'a f64>
+        + for<'a, 'b> FromIterator<&'a &'b f64>
+{
+    let values = vec![1., 3., 2., 7., 5.5, 4.];
+    let refs: Vec<&f64> = values.iter().collect();
+    let from_refs: E = values.iter().collect();
+    let from_copied: E = values.iter().copied().collect();
+    let from_ref_refs: E = refs.iter().collect();
+    let from_values: E = values.into_iter().collect();
+    assert_eq!(from_refs.estimate(), from_values.estimate());
+    assert_eq!(from_copied.estimate(), from_values.estimate());
+    assert_eq!(from_ref_refs.estimate(), from_values.estimate());
+}
+
+#[test]
+fn scalar_estimators() {
+    check::<Mean>();
+    check::<MeanWithError>();
+    check::<Variance>();
+    check::<Skewness>();
+    check::<Kurtosis>();
+    check::<KahanMean>();
+    check::<Sum>();
+    check::<GeometricMean>();
+    check::<Min>();
+    check::<Max>();
+    check::<Quantile>();
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn alloc_estimators() {
+    use average::{MeanAbsoluteDeviation, Mode, TDigest};
+    check::<Mode>();
+    let values = [1., 3., 2.];
+    let refs: Vec<&f64> = values.iter().collect();
+    let a: TDigest = refs.iter().collect();
+    let b: TDigest = values.iter().collect();
+    assert_eq!(a.quantile(0.5), b.quantile(0.5));
+    let a: MeanAbsoluteDeviation = refs.iter().collect();
+    let b: MeanAbsoluteDeviation = values.iter().collect();
+    assert_eq!(a.mean_absolute_deviation(), b.mean_absolute_deviation());
+}
+
+#[test]
+fn concatenated() {
+    concatenate!(MinMax, [Min, min], [Max, max]);
+    let values = [1., 3., 2.];
+    let refs: Vec<&f64> = values.iter().collect();
+    let a: MinMax = refs.iter().collect();
+    assert_eq!(a.min(), 1.);
+    assert_eq!(a.max(), 3.);
+}
@@ -0,0 +1,70 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use average::{GeometricMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = GeometricMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.geometric_mean(), 0.0);
+    a.add(2.0);
+    assert_eq!(a.len(), 1);
+    assert_almost_eq!(a.geometric_mean(), 2.0, 1e-15);
+}
+
+#[test]
+fn simple() {
+    let values = [1.5, 2., 3.25, 7., 11.];
+    let a: GeometricMean = values.iter().collect();
+    let product: f64 = values.iter().product();
+    assert_eq!(a.len(), 5);
+    assert_almost_eq!(a.geometric_mean(), product.powf(1. / 5.), 1e-14);
+    let a: GeometricMean = [1., 2., 4.].iter().collect();
+    assert_almost_eq!(a.geometric_mean(), 2.0, 1e-15);
+}
+
+#[test]
+fn non_positive() {
+    for &x in &[0., -2., std::f64::NAN] {
+        for &sample in &[[x, 1., 4.], [1., x, 4.], [1., 4., x]] {
+            let a: GeometricMean = sample.iter().collect();
+            assert_eq!(a.len(), 3);
+            assert!(a.geometric_mean().is_nan());
+        }
+        let a: GeometricMean = [x].iter().collect();
+        assert!(a.geometric_mean().is_nan());
+    }
+}
+
+#[test]
+fn merge_non_positive() {
+    let positive: GeometricMean = [1., 4.].iter().collect();
+    let zero: GeometricMean = [0., 2.].iter().collect();
+    let mut a = positive.clone();
+    a.merge(&zero);
+    assert!(a.geometric_mean().is_nan());
+    let mut b = zero.clone();
+    b.merge(&positive);
+    assert!(b.geometric_mean().is_nan());
+    let mut c = zero;
+    c.merge(&GeometricMean::new());
+    assert!(c.geometric_mean().is_nan());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 1..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: GeometricMean = sequence.iter().collect();
+        let mut a: GeometricMean = left.iter().collect();
+        let b: GeometricMean = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(total.len(), a.len());
+        assert_almost_eq!(total.geometric_mean(), a.geometric_mean(), 1e-14);
+    }
+}
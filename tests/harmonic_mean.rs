@@ -0,0 +1,83 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use average::{HarmonicMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = HarmonicMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.harmonic_mean(), 0.0);
+    a.add(2.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.harmonic_mean(), 2.0);
+}
+
+#[test]
+fn simple() {
+    // Average speed for the same distance at 40 and 60 km/h.
+    let a: HarmonicMean = [40., 60.].iter().collect();
+    assert_almost_eq!(a.harmonic_mean(), 48.0, 1e-13);
+    let a: HarmonicMean = [1., 4., 4.].iter().collect();
+    assert_almost_eq!(a.harmonic_mean(), 2.0, 1e-15);
+}
+
+#[test]
+fn non_positive() {
+    for &x in &[0., -2., std::f64::NAN] {
+        for &sample in &[[x, 1., 4.], [1., x, 4.], [1., 4., x]] {
+            let a: HarmonicMean = sample.iter().collect();
+            assert_eq!(a.len(), 3);
+            assert!(a.harmonic_mean().is_nan());
+        }
+        let a: HarmonicMean = [x].iter().collect();
+        assert!(a.harmonic_mean().is_nan());
+    }
+}
+
+#[test]
+fn merge_non_positive() {
+    let positive: HarmonicMean = [1., 4.].iter().collect();
+    let zero: HarmonicMean = [0., 2.].iter().collect();
+    let mut a = positive.clone();
+    a.merge(&zero);
+    assert!(a.harmonic_mean().is_nan());
+    let mut b = zero.clone();
+    b.merge(&positive);
+    assert!(b.harmonic_mean().is_nan());
+    let mut c = zero;
+    c.merge(&HarmonicMean::new());
+    assert!(c.harmonic_mean().is_nan());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 1..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: HarmonicMean = sequence.iter().collect();
+        let mut a: HarmonicMean = left.iter().collect();
+        let b: HarmonicMean = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(total.len(), a.len());
+        assert_almost_eq!(total.harmonic_mean(), a.harmonic_mean(), 1e-14);
+    }
+}
+
+#[test]
+fn concatenate() {
+    use average::{GeometricMean, Mean};
+
+    concatenate!(Means,
+        [Mean, mean],
+        [GeometricMean, geometric_mean],
+        [HarmonicMean, harmonic_mean]);
+
+    let a: Means = [1., 2., 4.].iter().cloned().collect();
+    assert_almost_eq!(a.mean(), 7. / 3., 1e-15);
+    assert_almost_eq!(a.geometric_mean(), 2.0, 1e-15);
+    assert_almost_eq!(a.harmonic_mean(), 12. / 7., 1e-15);
+}
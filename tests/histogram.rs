@@ -11,7 +11,7 @@ extern crate serde_json;
 
 use core::iter::Iterator;
 use rand::distributions::Distribution;
-use rand::FromEntropy;
+use rand::SeedableRng;
 
 use average::{Histogram, Merge};
 
@@ -140,6 +140,33 @@ fn out_of_range() {
     assert_eq!(h.add(100.1), Err(()));
 }
 
+#[test]
+fn underflow_overflow() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.underflow(), 0);
+    assert_eq!(h.overflow(), 0);
+    for &x in &[-3., -0.5, 0., 2.5, 2.7, 9.99, 10., 11., 42.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.underflow(), 2);
+    assert_eq!(h.overflow(), 3);
+    let counts: Vec<u64> = h.iter().map(|(_, count)| count).collect();
+    assert_eq!(counts, vec![1, 0, 2, 0, 0, 0, 0, 0, 0, 1]);
+    let ranges: Vec<(f64, f64)> = h.iter().map(|(range, _)| range).collect();
+    assert_eq!(ranges[2], (2., 3.));
+
+    let mut h2 = h.clone();
+    h2.merge(&h);
+    assert_eq!(h2.underflow(), 4);
+    assert_eq!(h2.overflow(), 6);
+    h2 *= 2;
+    assert_eq!(h2.underflow(), 8);
+    h2 += &h;
+    assert_eq!(h2.overflow(), 15);
+    h2.reset();
+    assert_eq!(h2.underflow(), 0);
+    assert_eq!(h2.overflow(), 0);
+}
 
 #[test]
 fn reset() {
@@ -198,7 +225,7 @@ fn mul() {
 fn variance() {
     let mut h = Histogram10::with_const_width(-3., 3.);
     let normal = rand::distributions::Normal::new(0., 1.);
-    let mut rng = rand::rngs::SmallRng::from_entropy();
+    let mut rng = rand::rngs::SmallRng::from_seed([3; 16]);
     for _ in 0..1000000 {
         let _ = h.add(normal.sample(&mut rng));
     }
@@ -235,18 +262,251 @@ fn merge() {
     assert_eq!(h.bins(), h1.bins());
 }
 
+#[test]
+fn merge_disjoint_halves() {
+    let data: Vec<f64> = (0..1000).map(|i| (f64::from(i) * 0.37).sin() * 1.2).collect();
+    let mut total = Histogram10::with_const_width(-1., 1.);
+    for &x in &data {
+        let _ = total.add(x);
+    }
+    let (left, right) = data.split_at(400);
+    let mut h1 = Histogram10::with_const_width(-1., 1.);
+    for &x in left {
+        let _ = h1.add(x);
+    }
+    let mut h2 = Histogram10::with_const_width(-1., 1.);
+    for &x in right {
+        let _ = h2.add(x);
+    }
+    h1.merge(&h2);
+    assert_eq!(h1.bins(), total.bins());
+    assert!(total.underflow() > 0);
+    assert!(total.overflow() > 0);
+    assert_eq!(h1.underflow(), total.underflow());
+    assert_eq!(h1.overflow(), total.overflow());
+}
+
+#[test]
+#[should_panic(expected = "same ranges")]
+fn merge_different_ranges() {
+    let mut h1 = Histogram10::with_const_width(0., 10.);
+    let h2 = Histogram10::with_const_width(0., 20.);
+    h1.merge(&h2);
+}
+
+#[test]
+#[should_panic(expected = "same out-of-range mode")]
+fn merge_different_out_of_range() {
+    let mut h1 = Histogram10::with_const_width(0., 10.);
+    let h2 = Histogram10::with_const_width(0., 10.)
+        .with_out_of_range(average::OutOfRange::Clamp);
+    h1.merge(&h2);
+}
+
+#[test]
+fn debug() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h.add(-1.).unwrap_err();
+    h.add(11.).unwrap_err();
+    h.add(12.).unwrap_err();
+    let s = format!("{:?}", h);
+    assert!(s.starts_with("Histogram { range: [0.0, 1.0, "));
+    assert!(s.ends_with("underflow: 1, overflow: 2, out_of_range: Separate }"));
+}
+
+#[test]
+fn quantile() {
+    use rand::distributions::Uniform;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.quantile(0.5), 0.);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let uniform = Uniform::new(0., 100.);
+    for _ in 0..100_000 {
+        h.add(uniform.sample(&mut rng)).unwrap();
+    }
+    assert_almost_eq!(h.quantile(0.5), 50., 10.);
+    assert_almost_eq!(h.quantile(0.25), 25., 10.);
+    assert_eq!(h.quantile(0.), 0.);
+    assert_eq!(h.quantile(1.), 100.);
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in &[-1., -1., 0.5, 1.5, 2.5, 11.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.quantile(0.2), 0.);
+    assert_eq!(h.quantile(0.5), 1.);
+    assert_eq!(h.quantile(0.9), 10.);
+}
+
+#[test]
+fn percentile_rank() {
+    use rand::distributions::Uniform;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.percentile_rank(50.), 0.);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let uniform = Uniform::new(0., 100.);
+    for _ in 0..100_000 {
+        h.add(uniform.sample(&mut rng)).unwrap();
+    }
+    assert_almost_eq!(h.percentile_rank(50.), 0.5, 0.01);
+    assert_almost_eq!(h.percentile_rank(25.), 0.25, 0.01);
+    assert_almost_eq!(h.percentile_rank(h.quantile(0.3)), 0.3, 1e-12);
+    assert_eq!(h.percentile_rank(-1.), 0.);
+    assert_eq!(h.percentile_rank(0.), 0.);
+    assert_eq!(h.percentile_rank(100.), 1.);
+    assert_eq!(h.percentile_rank(1000.), 1.);
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in &[-1., 0.5, 1.5, 11.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.percentile_rank(0.), 0.25);
+    assert_eq!(h.percentile_rank(1.5), 0.625);
+    assert_eq!(h.percentile_rank(9.), 0.75);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn density() {
+    let h = Histogram10::from_ranges(
+        [0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, 2.0].iter().cloned()).unwrap();
+    assert_eq!(h.density(), vec![0.; 10]);
+    assert_eq!(h.normalized(), vec![0.; 10]);
+
+    let mut h = h;
+    for &x in &[0.05, 0.15, 0.15, 0.55, 0.6, 0.99, 1.5, 1.7] {
+        h.add(x).unwrap();
+    }
+    let density = h.density();
+    let integral: f64 = density.iter().zip(h.widths()).map(|(d, w)| d * w).sum();
+    assert_almost_eq!(integral, 1.0, 1e-14);
+    assert_almost_eq!(density[1], 2. / 8. / 0.1, 1e-12);
+    assert_almost_eq!(density[9], 2. / 8., 1e-14);
+    let normalized = h.normalized();
+    assert_almost_eq!(normalized.iter().sum::<f64>(), 1.0, 1e-14);
+    assert_eq!(normalized[5], 2. / 8.);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn cumulative() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.cumulative(), vec![0; 10]);
+    assert_eq!(h.cdf(), vec![0.; 10]);
+    for &x in &[-1., 0.5, 1.5, 1.7, 4., 9.5, 9.9, 12.] {
+        let _ = h.add(x);
+    }
+    let cumulative = h.cumulative();
+    assert_eq!(cumulative, vec![1, 3, 3, 3, 4, 4, 4, 4, 4, 6]);
+    let in_range: u64 = h.bins().iter().sum();
+    assert_eq!(*cumulative.last().unwrap(), in_range);
+    let cdf = h.cdf();
+    assert_eq!(cdf[1], 0.5);
+    assert_eq!(*cdf.last().unwrap(), 1.);
+}
+
+#[test]
+fn cumulative_with_out_of_range() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.cumulative_with_underflow(), [0; 10]);
+    assert_eq!(h.cdf_with_out_of_range(), [0.; 10]);
+    for &x in &[-1., 0.5, 1.5, 1.7, 4., 9.5, 9.9, 12.] {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.cumulative_with_underflow(), [2, 4, 4, 4, 5, 5, 5, 5, 5, 7]);
+    let cdf = h.cdf_with_out_of_range();
+    for (i, &c) in cdf[..9].iter().enumerate() {
+        assert_eq!(c, h.percentile_rank(h.ranges()[i + 1]));
+    }
+    assert_eq!(cdf[9], 7. / 8.);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_csv() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in &[-1., 0.5, 1.5, 1.7, 4., 9.5, 12., 13.] {
+        let _ = h.add(x);
+    }
+    let mut out: Vec<u8> = Vec::new();
+    h.write_csv(&mut out).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("bin_lower,bin_upper,count"));
+    let rows: Vec<(f64, f64, u64)> = lines.map(|line| {
+        let fields: Vec<&str> = line.split(',').collect();
+        assert_eq!(fields.len(), 3);
+        (fields[0].parse().unwrap(), fields[1].parse().unwrap(), fields[2].parse().unwrap())
+    }).collect();
+    assert_eq!(rows.len(), 12);
+    assert_eq!(rows[0], (std::f64::NEG_INFINITY, 0., 1));
+    assert_eq!(rows[11], (10., std::f64::INFINITY, 2));
+    for (row, ((a, b), count)) in rows[1..11].iter().zip(h.iter()) {
+        assert_eq!(*row, (a, b, count));
+    }
+    assert_eq!(rows[2], (1., 2., 2));
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn simple_serde() {
-    /*
     let mut a = Histogram10::from_ranges(
         [0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, 2.0].iter().cloned()).unwrap();
-    for &i in &[0.05, 0.7, 1.0, 1.5] {
-        h.add(i).unwrap();
+    for &i in &[0.05, 0.7, 1.0, 1.5, -1., 3.] {
+        let _ = a.add(i);
     }
     let b = serde_json::to_string(&a).unwrap();
-    assert_eq!(&b, "{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":10.0}");
-    let c: Histogram10 = serde_json::from_str(&b).unwrap();
-    assert_eq!(h.bins(), &[1, 0, 0, 0, 0, 0, 1, 0, 0, 2]);
-    */
+    let mut c: Histogram10 = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.bins(), &[1, 0, 0, 0, 0, 0, 1, 0, 0, 2]);
+    assert_eq!(c.ranges(), a.ranges());
+    assert_eq!(c.underflow(), 1);
+    assert_eq!(c.overflow(), 1);
+    for &i in &[0.15, 0.75, 1.99, 2.5] {
+        let _ = a.add(i);
+        let _ = c.add(i);
+    }
+    assert_eq!(c.bins(), a.bins());
+    assert_eq!(c.underflow(), a.underflow());
+    assert_eq!(c.overflow(), a.overflow());
+
+    // Histograms serialized before the out-of-range fields existed.
+    let d: Histogram10 = serde_json::from_str(
+        "{\"range\":[0,1,2,3,4,5,6,7,8,9,10],\"bin\":[0,1,0,0,0,0,0,0,0,0]}").unwrap();
+    assert_eq!(d.bins(), &[0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(d.underflow(), 0);
+    assert_eq!(d.out_of_range(), average::OutOfRange::Separate);
+}
+
+#[test]
+fn out_of_range_modes() {
+    let samples = [-5., -0.1, 0., 3.5, 9.99, 10., 100.];
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.out_of_range(), average::OutOfRange::Separate);
+    for &x in &samples {
+        let _ = h.add(x);
+    }
+    assert_eq!(h.bins(), &[1, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(h.underflow(), 2);
+    assert_eq!(h.overflow(), 2);
+
+    let mut h = Histogram10::with_const_width(0., 10.)
+        .with_out_of_range(average::OutOfRange::Clamp);
+    assert_eq!(h.out_of_range(), average::OutOfRange::Clamp);
+    for &x in &samples {
+        assert!(h.add(x).is_ok());
+    }
+    assert_eq!(h.bins(), &[3, 0, 0, 1, 0, 0, 0, 0, 0, 3]);
+    assert_eq!(h.underflow(), 0);
+    assert_eq!(h.overflow(), 0);
+    h.reset();
+    assert_eq!(h.out_of_range(), average::OutOfRange::Clamp);
 }
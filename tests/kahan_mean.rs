@@ -0,0 +1,81 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{KahanMean, Mean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = KahanMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 1.0);
+    a.add(2.0);
+    assert_eq!(a.mean(), 1.5);
+}
+
+#[test]
+fn simple() {
+    let a: KahanMean = (1..6).map(f64::from).collect();
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.mean(), 3.0);
+    assert_eq!(a.estimate(), 3.0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: KahanMean = (1..6).map(f64::from).collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: KahanMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.mean(), 3.0);
+    assert_eq!(c.len(), 5);
+}
+
+#[test]
+fn alternating_magnitudes() {
+    let n = 10_000_000;
+    let large = 1e9;
+    let small = 1e-3;
+    let values = (0..n).map(|i| if i % 2 == 0 { large } else { small });
+    let mut a = KahanMean::new();
+    let mut b = Mean::new();
+    for x in values {
+        a.add(x);
+        b.add(x);
+    }
+    let exact = 0.5 * (large + small);
+    let error_kahan = (a.mean() - exact).abs();
+    let error_plain = (b.mean() - exact).abs();
+    assert!(error_kahan < error_plain,
+            "kahan: {:e}, plain: {:e}", error_kahan, error_plain);
+    assert_almost_eq!(a.mean(), exact, 1e-7);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: KahanMean = sequence.iter().collect();
+        let mut avg_left: KahanMean = left.iter().collect();
+        let avg_right: KahanMean = right.iter().collect();
+        avg_left.merge(&avg_right);
+        assert_eq!(avg_total.len(), avg_left.len());
+        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-15);
+    }
+}
+
+#[test]
+fn reset() {
+    let mut a: KahanMean = (1..6).map(f64::from).collect();
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+}
@@ -1,14 +1,16 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
 extern crate core;
+extern crate rand;
 #[cfg(feature = "serde1")]
 extern crate serde_json;
 
 use core::iter::Iterator;
 
 use average::{Kurtosis, Estimate, Merge};
+use average::{Mean, Skewness, Variance};
 
 #[test]
 fn trivial() {
@@ -76,3 +78,100 @@ fn merge() {
         assert_almost_eq!(avg_total.kurtosis(), avg_left.kurtosis(), 1e-14);
     }
 }
+
+#[test]
+fn merge_empty() {
+    let mut a = Kurtosis::new();
+    a.merge(&Kurtosis::new());
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+    assert_eq!(a.kurtosis(), 0.0);
+    let mut b: Kurtosis = (1..6).map(f64::from).collect();
+    b.merge(&a);
+    assert_eq!(b.mean(), 3.0);
+    assert_almost_eq!(b.kurtosis(), -1.3, 1e-15);
+}
+
+#[test]
+fn jarque_bera() {
+    use rand::distributions::{Distribution, Exp, Normal};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let a: Kurtosis = (1..4).map(f64::from).collect();
+    assert_eq!(a.jarque_bera(), 0.0);
+    let a: Kurtosis = [1., 2., 3., 10.].iter().collect();
+    let (s, k) = (a.skewness(), a.kurtosis());
+    assert_almost_eq!(a.jarque_bera(), 4. / 6. * (s*s + k*k / 4.), 1e-14);
+
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let normal = Normal::new(2.0, 3.0);
+    let a: Kurtosis = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+    // The 99% quantile of the chi-squared distribution with 2 degrees of
+    // freedom is about 9.2.
+    assert!(a.jarque_bera() < 9.2);
+    let exp = Exp::new(2.0);
+    let a: Kurtosis = (0..100_000).map(|_| exp.sample(&mut rng)).collect();
+    assert!(a.jarque_bera() > 1000.);
+}
+
+#[test]
+fn shared_pass() {
+    use rand::distributions::{Distribution, Exp};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let exp = Exp::new(2.);
+    let sample: Vec<f64> = (0..1000).map(|_| exp.sample(&mut rng)).collect();
+
+    let a: Kurtosis = sample.iter().collect();
+    let mean: Mean = sample.iter().collect();
+    let variance: Variance = sample.iter().collect();
+    let skewness: Skewness = sample.iter().collect();
+    assert_eq!(a.len(), 1000);
+    assert_almost_eq!(a.mean(), mean.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), variance.sample_variance(), 1e-14);
+    assert_almost_eq!(a.population_variance(), variance.population_variance(), 1e-14);
+    assert_almost_eq!(a.error_mean(), variance.error(), 1e-14);
+    assert_almost_eq!(a.skewness(), skewness.skewness(), 1e-13);
+}
+
+#[test]
+fn central_moments() {
+    let data = [1., 2., 2., 4., 6.];
+    let a: Kurtosis = data.iter().collect();
+    let mean = 3.;
+    let moment = |k: i32| data.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / 5.;
+    assert_almost_eq!(a.central_moment_2(), a.population_variance(), 1e-15);
+    assert_almost_eq!(a.central_moment_2(), moment(2), 1e-15);
+    assert_almost_eq!(a.central_moment_3(), moment(3), 1e-14);
+    assert_almost_eq!(a.central_moment_4(), moment(4), 1e-14);
+    assert_almost_eq!(a.kurtosis(), a.central_moment_4() / moment(2).powi(2) - 3., 1e-14);
+
+    let b = Kurtosis::new();
+    assert_eq!(b.central_moment_2(), 0.);
+    assert_eq!(b.central_moment_3(), 0.);
+    assert_eq!(b.central_moment_4(), 0.);
+}
+
+#[test]
+fn merge_shards() {
+    // A skewed, heavy-tailed sequence far from zero.
+    let values: Vec<f64> = (0..100_000)
+        .map(|i| 100. + (3. * (f64::from(i) * 0.7).sin()).exp())
+        .collect();
+    let single: Kurtosis = values.iter().collect();
+    let mut merged: Kurtosis = values[..20_000].iter().collect();
+    let middle: Kurtosis = values[20_000..55_555].iter().collect();
+    let last: Kurtosis = values[55_555..].iter().collect();
+    merged.merge(&middle);
+    merged.merge(&last);
+    assert_eq!(merged.len(), single.len());
+    assert_almost_eq!(merged.mean(), single.mean(), 1e-8);
+    assert_almost_eq!(merged.sample_variance(), single.sample_variance(), 1e-8);
+    assert_almost_eq!(merged.skewness(), single.skewness(), 1e-8);
+    assert_almost_eq!(merged.kurtosis(), single.kurtosis(), 1e-8);
+}
@@ -0,0 +1,83 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{CoVariance, LogCoVariance, Merge};
+
+fn prices() -> Vec<(f64, f64)> {
+    (1..200)
+        .map(|i| {
+            let t = f64::from(i);
+            (100. + 10. * (0.3 * t).sin() + t, 50. + 5. * (0.3 * t + 0.5).sin())
+        })
+        .collect()
+}
+
+#[test]
+fn trivial() {
+    let mut a = LogCoVariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.sample_covariance(), 0.);
+    a.add(1., 1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean_x(), 0.);
+    assert_eq!(a.mean_y(), 0.);
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn manual_logs() {
+    let data = prices();
+    let a: LogCoVariance = data.iter().collect();
+    let b: CoVariance = data.iter().map(|&(x, y)| (x.ln(), y.ln())).collect();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.rejected(), 0);
+    assert_eq!(a.mean_x(), b.mean_x());
+    assert_eq!(a.mean_y(), b.mean_y());
+    assert_eq!(a.sample_covariance(), b.sample_covariance());
+    assert_eq!(a.sample_variance_x(), b.sample_variance_x());
+    assert_eq!(a.sample_variance_y(), b.sample_variance_y());
+    assert_eq!(a.population_covariance(), b.population_covariance());
+    assert_eq!(a.population_variance_x(), b.population_variance_x());
+    assert_eq!(a.population_variance_y(), b.population_variance_y());
+    assert_eq!(a.pearson(), b.pearson());
+    assert_eq!(a.covariance().len(), b.len());
+}
+
+#[test]
+fn non_positive() {
+    let mut a = LogCoVariance::new();
+    a.add(1., 2.).add(0., 1.).add(1., -1.).add(std::f64::NAN, 1.).add(2., 3.);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.rejected(), 3);
+    assert!(a.sample_covariance().is_finite());
+}
+
+#[test]
+fn merge() {
+    let data = prices();
+    let total: LogCoVariance = data.iter().collect();
+    let (left, right) = data.split_at(77);
+    let mut a: LogCoVariance = left.iter().collect();
+    let mut b: LogCoVariance = right.iter().collect();
+    b.add(-1., 1.);
+    a.merge(&b);
+    assert_eq!(a.len(), total.len());
+    assert_eq!(a.rejected(), 1);
+    assert_almost_eq!(a.sample_covariance(), total.sample_covariance(), 1e-14);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: LogCoVariance = prices().iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: LogCoVariance = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), a.len());
+    assert_almost_eq!(c.sample_covariance(), a.sample_covariance(), 1e-14);
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+#![allow(clippy::float_cmp)]
 
 #[macro_use] extern crate average;
 
@@ -52,3 +52,66 @@ fn concatenate_moments() {
     assert_eq!(e.sample_variance(), 2.5);
     assert_eq!(e.quantile(), 3.0);
 }
+
+#[test]
+fn concatenate_merge() {
+    use average::{Mean, Variance, Merge};
+
+    concatenate!(Estimator,
+        [Mean, mean, mean],
+        [Variance, variance, sample_variance]);
+
+    let sequence: Vec<f64> = (0..100).map(|i| (f64::from(i) * 0.3).sin()).collect();
+    let (left, right) = sequence.split_at(37);
+    let total: Estimator = sequence.iter().collect();
+    let mut e: Estimator = left.iter().collect();
+    e.merge(&right.iter().collect());
+    assert_almost_eq!(e.mean(), total.mean(), 1e-15);
+    assert_almost_eq!(e.sample_variance(), total.sample_variance(), 1e-15);
+}
+
+#[test]
+fn concatenate_without_merge() {
+    #[derive(Default)]
+    struct Last {
+        x: f64,
+    }
+
+    impl Last {
+        fn add(&mut self, x: f64) {
+            self.x = x;
+        }
+
+        fn last(&self) -> f64 {
+            self.x
+        }
+    }
+
+    // `Last` does not implement `Merge`, so neither does the concatenated
+    // estimator. This should still compile.
+    concatenate!(Estimator, [Last, last], [Max, max]);
+
+    let e: Estimator = [3., 5., 1.].iter().collect();
+    assert_eq!(e.last(), 1.0);
+    assert_eq!(e.max(), 5.0);
+}
+
+mod public {
+    use average::{Estimate, Min, Max};
+
+    concatenate!(
+        /// A public estimator of the minimum and the maximum.
+        #[derive(Debug, Clone)]
+        pub MinMax,
+        [Min, min],
+        [Max, max]);
+}
+
+#[test]
+fn concatenate_attributes() {
+    let a: public::MinMax = [3., 5., 1.].iter().collect();
+    let b = a.clone();
+    assert_eq!(b.min(), 1.0);
+    assert_eq!(b.max(), 5.0);
+    assert!(format!("{:?}", b).starts_with("MinMax { min: "));
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 extern crate average;
 
@@ -50,3 +50,72 @@ fn merge() {
         assert_eq!(max_total.max(), max_left.max());
     }
 }
+
+#[test]
+fn merge_several() {
+    let shards: &[&[f64]] = &[&[1., 5., -3.], &[9., 2.], &[4., 0.5, 7.]];
+    let mut total = Max::new();
+    for shard in shards {
+        total.merge(&shard.iter().collect());
+    }
+    assert_eq!(total.max(), 9.);
+}
+
+#[test]
+fn merge_empty() {
+    let mut a = Max::new();
+    let b: Max = (1..6).map(f64::from).collect();
+    a.merge(&b);
+    assert_eq!(a.max(), b.max());
+    a.merge(&Max::new());
+    assert_eq!(a.max(), b.max());
+}
+
+#[test]
+fn reset() {
+    let mut m: Max = (1..6).map(f64::from).collect();
+    m.reset();
+    assert_eq!(m.max(), Max::new().max());
+    m.add(3.);
+    assert_eq!(m.max(), 3.);
+}
+
+#[test]
+fn indexed() {
+    use average::IndexedMax;
+
+    let a = IndexedMax::new();
+    assert!(a.is_empty());
+    assert_eq!(a.index(), None);
+
+    let values: Vec<f64> = (0..100)
+        .map(|i| -(f64::from(i) - 42.).powi(2))
+        .collect();
+    let a: IndexedMax = values.iter().collect();
+    assert_eq!(a.len(), 100);
+    assert_eq!(a.max(), 0.);
+    assert_eq!(a.index(), Some(42));
+
+    // The first index wins on ties.
+    let a: IndexedMax = [1., 3., 2., 3.].iter().collect();
+    assert_eq!(a.index(), Some(1));
+
+    let a: IndexedMax = [core::f64::NAN, core::f64::NEG_INFINITY].iter().collect();
+    assert_eq!(a.max(), core::f64::NEG_INFINITY);
+    assert_eq!(a.index(), Some(1));
+}
+
+#[test]
+fn indexed_merge() {
+    use average::IndexedMax;
+
+    let values: &[f64] = &[1., 7., 3., 9., 2., 9., 4.];
+    for mid in 0..values.len() {
+        let (left, right) = values.split_at(mid);
+        let mut a: IndexedMax = left.iter().collect();
+        a.merge(&right.iter().collect());
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.max(), 9.);
+        assert_eq!(a.index(), Some(3));
+    }
+}
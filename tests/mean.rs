@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -8,7 +8,7 @@ extern crate serde_json;
 
 use core::iter::Iterator;
 
-use average::{MeanWithError, Estimate, Merge};
+use average::{MeanWithError, Merge};
 
 #[test]
 fn trivial() {
@@ -73,3 +73,377 @@ fn merge() {
         assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     }
 }
+
+#[test]
+fn merge_empty() {
+    let mut a = MeanWithError::new();
+    a.merge(&MeanWithError::new());
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+    assert_eq!(a.sample_variance(), 0.0);
+    let mut b: MeanWithError = (1..6).map(f64::from).collect();
+    b.merge(&a);
+    assert_eq!(b.mean(), 3.0);
+    assert_eq!(b.sample_variance(), 2.5);
+}
+
+#[test]
+fn add_slice() {
+    let values: Vec<f64> = (0..1_000_000)
+        .map(|i| 2. + 3. * (f64::from(i) * 0.37).sin() + 1e-3 * f64::from(i % 7))
+        .collect();
+    let expected: MeanWithError = values.iter().collect();
+    let mut a = MeanWithError::new();
+    a.add_slice(&values);
+    assert_eq!(a.len(), expected.len());
+    assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+    assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-12);
+
+    let mut b = average::Mean::new();
+    for chunk in values.chunks(1000) {
+        b.add_slice(chunk);
+    }
+    assert_eq!(b.len(), expected.len());
+    assert_almost_eq!(b.mean(), expected.mean(), 1e-12);
+
+    let (left, right) = values.split_at(12345);
+    let mut c: MeanWithError = left.iter().collect();
+    c.add_slice(right);
+    c.add_slice(&[]);
+    assert_eq!(c.len(), expected.len());
+    assert_almost_eq!(c.mean(), expected.mean(), 1e-12);
+    assert_almost_eq!(c.sample_variance(), expected.sample_variance(), 1e-12);
+}
+
+#[test]
+fn remove() {
+    let values: Vec<f64> = (0..100).map(|i| (f64::from(i) * 0.7).sin() * 10. + 5.).collect();
+    let (first, second) = values.split_at(50);
+    let mut a: MeanWithError = values.iter().collect();
+    let mut b: average::Mean = values.iter().collect();
+    for &x in first {
+        a.remove(x);
+        b.remove(x);
+    }
+    let expected: MeanWithError = second.iter().collect();
+    assert_eq!(a.len(), 50);
+    assert_eq!(b.len(), 50);
+    assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+    assert_almost_eq!(b.mean(), expected.mean(), 1e-12);
+    assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-11);
+
+    for &x in second {
+        a.remove(x);
+        b.remove(x);
+    }
+    assert!(a.is_empty());
+    assert!(b.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(b.mean(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn remove_empty() {
+    let mut a = MeanWithError::new();
+    a.remove(1.);
+}
+
+#[test]
+fn reset() {
+    let mut a: MeanWithError = (1..6).map(f64::from).collect();
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+    assert_eq!(a.sample_variance(), 0.0);
+    for i in 10..20 {
+        a.add(f64::from(i));
+    }
+    let expected: MeanWithError = (10..20).map(f64::from).collect();
+    assert_eq!(a.len(), expected.len());
+    assert_eq!(a.mean(), expected.mean());
+    assert_eq!(a.sample_variance(), expected.sample_variance());
+}
+
+#[test]
+fn coefficient_of_variation() {
+    let mut a = MeanWithError::new();
+    assert_eq!(a.coefficient_of_variation(), 0.0);
+    a.add(0.0);
+    assert!(a.coefficient_of_variation().is_nan());
+    let a: MeanWithError = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+    // mean = 5, sample variance = 32 / 7
+    assert_almost_eq!(a.coefficient_of_variation(), (32f64 / 7.).sqrt() / 5., 1e-15);
+}
+
+#[test]
+fn standard_error_of_mean() {
+    let mut a = MeanWithError::new();
+    assert_eq!(a.standard_error_of_mean(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.standard_error_of_mean(), 0.0);
+    // Reference values from `scipy.stats.sem`.
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    assert_almost_eq!(a.standard_error_of_mean(), f64::sqrt(0.5), 1e-15);
+    let a: MeanWithError = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+    assert_almost_eq!(a.standard_error_of_mean(), 0.7559289460184544, 1e-15);
+    assert_eq!(a.standard_error_of_mean(), a.error());
+}
+
+#[test]
+fn confidence_interval() {
+    let mut a = MeanWithError::new();
+    a.add(3.0);
+    assert_eq!(a.confidence_interval(0.95), (3.0, 3.0));
+    // Reference values of the t-distribution from `scipy.stats.t.ppf`.
+    let cases: &[(usize, f64, f64)] = &[
+        (2, 0.95, 12.706204736174698),
+        (3, 0.95, 4.302652729749464),
+        (4, 0.95, 3.182446305284263),
+        (5, 0.95, 2.7764451051977987),
+        (11, 0.95, 2.2281388519862744),
+        (101, 0.95, 1.9839715184496334),
+        (5, 0.90, 2.13184678632665),
+        (5, 0.99, 4.604094871415897),
+    ];
+    let values = [2., 4., 4., 4., 5., 5., 7., 9., 1., 3., 8.];
+    for &(n, confidence, t) in cases {
+        let a: MeanWithError = values.iter().cycle().take(n).collect();
+        let (lower, upper) = a.confidence_interval(confidence);
+        assert_almost_eq!(0.5 * (lower + upper), a.mean(), 1e-14);
+        let width = upper - lower;
+        let expected = 2. * t * a.standard_error_of_mean();
+        assert!((width - expected).abs() < 1e-6 * expected,
+                "n: {}, width: {}, expected: {}", n, width, expected);
+    }
+}
+
+#[test]
+fn generic_float() {
+    use average::Mean;
+
+    let values: Vec<f64> = (0..1000).map(|i| f64::from(i).sin() * 10.).collect();
+    let values_f32: Vec<f32> = values.iter().map(|&x| x as f32).collect();
+
+    let a: Mean<f64> = values.iter().collect();
+    let b: Mean<f32> = values_f32.iter().collect();
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(f64::from(b.mean()), a.mean(), 1e-5);
+
+    let a: MeanWithError<f64> = values.iter().collect();
+    let mut b = MeanWithError::<f32>::default();
+    for &x in &values_f32 {
+        b.add(x);
+    }
+    assert_almost_eq!(f64::from(b.mean()), a.mean(), 1e-5);
+    assert_almost_eq!(f64::from(b.sample_variance()), a.sample_variance(), 1e-3);
+    assert_almost_eq!(f64::from(b.error()), a.error(), 1e-5);
+
+    let (left, right) = values_f32.split_at(300);
+    let mut c: MeanWithError<f32> = left.iter().collect();
+    c.merge(&right.iter().collect());
+    assert_almost_eq!(f64::from(c.mean()), a.mean(), 1e-5);
+    assert_almost_eq!(f64::from(c.sample_variance()), a.sample_variance(), 1e-3);
+}
+
+#[test]
+fn standardize() {
+    let a = MeanWithError::new();
+    assert_eq!(a.standardize(3.0), 3.0);
+    let a: MeanWithError = [2.0, 2.0].iter().collect();
+    assert_eq!(a.standardize(5.0), 3.0);
+
+    let mut values: Vec<f64> = (0..1000)
+        .map(|i| 5. + 3. * (f64::from(i) * 0.7).sin())
+        .collect();
+    let a: MeanWithError = values.iter().collect();
+    let x = values[17];
+    let z = a.standardize(x);
+    a.standardize_slice(&mut values);
+    assert_eq!(values[17], z);
+    let b: MeanWithError = values.iter().collect();
+    assert_almost_eq!(b.mean(), 0.0, 1e-14);
+    assert_almost_eq!(b.sample_variance(), 1.0, 1e-14);
+}
+
+#[test]
+fn sums() {
+    let a = MeanWithError::new();
+    assert_eq!(a.sum(), 0.0);
+    assert_eq!(a.sum_of_squares_centered(), 0.0);
+
+    let values: Vec<f64> = (0..1000)
+        .map(|i| 2. + 3. * (f64::from(i) * 0.37).sin())
+        .collect();
+    let a: MeanWithError = values.iter().collect();
+    let sum: f64 = values.iter().sum();
+    assert_almost_eq!(a.sum(), sum, 1e-10);
+    let b: average::Mean = values.iter().collect();
+    assert_almost_eq!(b.sum(), sum, 1e-10);
+    let mean = sum / 1000.;
+    let sum_2: f64 = values.iter().map(|x| (x - mean) * (x - mean)).sum();
+    assert_almost_eq!(a.sum_of_squares_centered(), sum_2, 1e-10);
+    assert_almost_eq!(a.sum_of_squares_centered() / 999., a.sample_variance(), 1e-15);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn display() {
+    let a: MeanWithError = [1., 2., 3., 4., 6.].iter().collect();
+    assert_eq!(format!("{}", a), format!("mean=3.2 ± {} (n=5)", a.error()));
+    assert_eq!(format!("{:.2}", a), "mean=3.20 ± 0.86 (n=5)");
+    let b: average::Mean = [1., 2., 3., 4., 6.].iter().collect();
+    assert_eq!(format!("{}", b), "mean=3.2 (n=5)");
+    assert_eq!(format!("{:.3}", b), "mean=3.200 (n=5)");
+    let c = MeanWithError::new();
+    assert_eq!(format!("{}", c), "mean=0 ± 0 (n=0)");
+}
+
+#[test]
+fn partial_eq() {
+    let values: Vec<f64> = (0..100).map(|i| (f64::from(i) * 0.37).sin()).collect();
+    let a: MeanWithError = values.iter().collect();
+    let b: MeanWithError = values.iter().collect();
+    assert_eq!(a, b);
+    let mut c = MeanWithError::new();
+    c.merge(&a);
+    assert_eq!(a, c);
+    let d: MeanWithError = values[1..].iter().collect();
+    assert_ne!(a, d);
+
+    let a: average::Mean = values.iter().collect();
+    let b: average::Mean = values.iter().collect();
+    assert_eq!(a, b);
+    assert_ne!(a, average::Mean::new());
+}
+
+#[test]
+fn add_chained() {
+    use average::Mean;
+
+    let mut a = Mean::new();
+    a.add(1.).add(2.).add(4.);
+    let mut b = Mean::new();
+    b.add(1.);
+    b.add(2.);
+    b.add(4.);
+    assert_eq!(a, b);
+
+    let mut c = MeanWithError::new();
+    c.add(1.).add(2.).add(4.);
+    let d: MeanWithError = [1., 2., 4.].iter().collect();
+    assert_eq!(c, d);
+}
+
+#[test]
+fn pairwise() {
+    use average::Mean;
+
+    let n = 10_000_000u64;
+    let values = || (0..n).map(|i| 1e9 + 0.1 * (i % 7) as f64);
+    // The numbers above are not exactly representable, so we calculate the
+    // exact mean of their floating point representation with integers.
+    let exact_sum: u128 = (0..7u64)
+        .map(|r| {
+            let x = 1e9 + 0.1 * r as f64;
+            // x is a multiple of 2^-23.
+            let scaled = (x * f64::from(1 << 23)) as u128;
+            scaled * u128::from((n - r + 6) / 7)
+        })
+        .sum();
+    let exact = exact_sum as f64 / n as f64 / f64::from(1 << 23);
+
+    let naive: Mean = values().collect();
+    let pairwise = Mean::from_iter_pairwise(values());
+    assert_eq!(naive.len(), n);
+    assert_eq!(pairwise.len(), n);
+    let error_naive = (naive.mean() - exact).abs();
+    let error_pairwise = (pairwise.mean() - exact).abs();
+    assert!(error_pairwise < error_naive,
+            "pairwise error {} >= naive error {}", error_pairwise, error_naive);
+
+    let empty = Mean::from_iter_pairwise(Vec::<f64>::new());
+    assert!(empty.is_empty());
+    let few = Mean::from_iter_pairwise(vec![1., 2., 3., 4., 5.]);
+    assert_eq!(few.mean(), 3.);
+}
+
+#[test]
+fn len_exhausted() {
+    use average::Mean;
+
+    let mut a = Mean::<f32>::default();
+    for _ in 0..(1 << 24) {
+        a.add(1.);
+    }
+    assert!(!a.len_exhausted());
+    a.add(1.);
+    assert!(a.len_exhausted());
+    a.reset();
+    assert!(!a.len_exhausted());
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn len_exhausted_serde() {
+    use average::Mean;
+
+    let a: Mean = serde_json::from_str("{\"avg\":1.0,\"n\":9007199254740992}").unwrap();
+    assert!(!a.len_exhausted());
+    let b: Mean = serde_json::from_str("{\"avg\":1.0,\"n\":9007199254740993}").unwrap();
+    assert!(b.len_exhausted());
+    let c: MeanWithError = serde_json::from_str(
+        "{\"avg\":{\"avg\":1.0,\"n\":9007199254740993},\"sum_2\":0.0}").unwrap();
+    assert!(c.len_exhausted());
+}
+
+#[test]
+fn relative_standard_error() {
+    let mut a = MeanWithError::new();
+    assert_eq!(a.relative_standard_error(), std::f64::INFINITY);
+    assert!(!a.has_converged(0.1));
+    a.add(5.);
+    assert_eq!(a.relative_standard_error(), std::f64::INFINITY);
+    assert!(!a.has_converged(0.1));
+    a.add(5.);
+    assert_eq!(a.relative_standard_error(), 0.);
+    assert!(a.has_converged(0.));
+
+    let a: MeanWithError = [-1., 1.].iter().collect();
+    assert_eq!(a.relative_standard_error(), std::f64::INFINITY);
+
+    let a: MeanWithError = [1., 3.].iter().collect();
+    assert_eq!(a.relative_standard_error(), 0.5);
+}
+
+#[test]
+fn has_converged() {
+    // For the stream 0, 2, 0, 2, ... with an even number `n` of samples, the
+    // mean is 1 and the standard error is `1 / sqrt(n - 1)`, so the relative
+    // error drops below 0.1 for the first time after 102 samples.
+    let mut a = MeanWithError::new();
+    let mut converged_at = None;
+    for i in 1..1000 {
+        a.add(if i % 2 == 0 { 2. } else { 0. });
+        if a.has_converged(0.1) {
+            converged_at = Some(i);
+            break;
+        }
+    }
+    assert_eq!(converged_at, Some(102));
+    assert!(a.relative_standard_error() <= 0.1);
+}
+
+#[test]
+fn standard_deviation() {
+    let a: MeanWithError = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+    assert_eq!(a.population_standard_deviation(), 2.);
+    assert_eq!(a.population_standard_deviation(), a.population_variance().sqrt());
+    assert_eq!(a.sample_standard_deviation(), a.sample_variance().sqrt());
+    assert_almost_eq!(a.sample_standard_deviation(), (32f64 / 7.).sqrt(), 1e-15);
+
+    let b: MeanWithError = [3.].iter().collect();
+    assert_eq!(b.sample_standard_deviation(), 0.);
+    assert_eq!(b.population_standard_deviation(), 0.);
+}
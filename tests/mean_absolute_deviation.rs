@@ -0,0 +1,55 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+use average::{Estimate, MeanAbsoluteDeviation, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = MeanAbsoluteDeviation::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mean_absolute_deviation(), 0.);
+    assert_eq!(a.gini_mean_difference(), 0.);
+    assert_eq!(a.gini_coefficient(), 0.);
+    a.add(2.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean_absolute_deviation(), 0.);
+    assert_eq!(a.gini_mean_difference(), 0.);
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn hand_computed() {
+    // The mean is 5 and the absolute deviations are 3, 1, 1, 1, 0, 0, 2, 4.
+    let a: MeanAbsoluteDeviation = [2., 4., 4., 4., 5., 5., 7., 9.].iter().collect();
+    assert_eq!(a.mean(), 5.);
+    assert_eq!(a.mean_absolute_deviation(), 1.5);
+    assert_eq!(a.estimate(), 1.5);
+    // The sum of the absolute differences over all 28 pairs is 68.
+    assert_almost_eq!(a.gini_mean_difference(), 68. / 28., 1e-15);
+    assert_almost_eq!(a.gini_coefficient(), 68. / 280., 1e-15);
+}
+
+#[test]
+fn gini_coefficient() {
+    let equal: MeanAbsoluteDeviation = [3., 3., 3., 3.].iter().collect();
+    assert_eq!(equal.gini_coefficient(), 0.);
+    // One sample has everything, so the coefficient is maximal.
+    let unequal: MeanAbsoluteDeviation = [0., 0., 0., 0., 10.].iter().collect();
+    assert_almost_eq!(unequal.gini_coefficient(), 1., 1e-15);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[2., 4., 4., 4., 5., 5., 7., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut a: MeanAbsoluteDeviation = left.iter().collect();
+        let b: MeanAbsoluteDeviation = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), 8);
+        assert_eq!(a.mean_absolute_deviation(), 1.5);
+    }
+}
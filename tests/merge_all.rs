@@ -0,0 +1,54 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use average::{Estimate, Mean, Merge, Min, Variance, exact_mean, exact_variance, merge_all};
+
+#[test]
+fn empty() {
+    let a: Variance = merge_all(Vec::new());
+    assert!(a.is_empty());
+    let b: Mean = merge_all(vec![Mean::new()]);
+    assert!(b.is_empty());
+}
+
+#[test]
+fn many_shards() {
+    let values: Vec<f64> = (0..100_000).map(|i| 1e4 + (f64::from(i) * 0.1).sin()).collect();
+    let single: Variance = values.iter().collect();
+    let shards: Vec<Variance> = values.chunks(100).map(|c| c.iter().collect()).collect();
+    assert_eq!(shards.len(), 1000);
+    let mut folded = Variance::new();
+    for s in &shards {
+        folded.merge(s);
+    }
+    let merged: Variance = merge_all(shards);
+    assert_eq!(merged.len(), single.len());
+    let mean = exact_mean(&values);
+    assert_almost_eq!(merged.mean(), mean, 1e-11);
+    assert!((merged.mean() - mean).abs() <= (folded.mean() - mean).abs());
+    assert!((merged.mean() - mean).abs() <= (single.mean() - mean).abs());
+    assert_almost_eq!(merged.sample_variance(), exact_variance(&values), 1e-9);
+}
+
+#[test]
+fn all_sizes() {
+    for n in 1..40 {
+        let shards: Vec<Min> = (0..n).map(|i| {
+            let mut m = Min::new();
+            m.add(f64::from(n - i));
+            m
+        }).collect();
+        let merged: Min = merge_all(shards);
+        assert_eq!(merged.min(), 1.);
+    }
+    for n in 1..40 {
+        let shards: Vec<Mean> = (0..n).map(|i| [f64::from(i)].iter().collect()).collect();
+        let merged: Mean = merge_all(shards);
+        assert_eq!(merged.len(), n as u64);
+        assert_almost_eq!(merged.mean(), f64::from(n - 1) / 2., 1e-14);
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 extern crate average;
 
@@ -50,3 +50,68 @@ fn merge() {
         assert_eq!(min_total.min(), min_left.min());
     }
 }
+
+#[test]
+fn merge_several() {
+    let shards: &[&[f64]] = &[&[1., 5., -3.], &[9., 2.], &[4., 0.5, 7.]];
+    let mut total = Min::new();
+    for shard in shards {
+        total.merge(&shard.iter().collect());
+    }
+    assert_eq!(total.min(), -3.);
+}
+
+#[test]
+fn merge_empty() {
+    let mut a = Min::new();
+    let b: Min = (1..6).map(f64::from).collect();
+    a.merge(&b);
+    assert_eq!(a.min(), b.min());
+    a.merge(&Min::new());
+    assert_eq!(a.min(), b.min());
+}
+
+#[test]
+fn reset() {
+    let mut m: Min = (1..6).map(f64::from).collect();
+    m.reset();
+    assert_eq!(m.min(), Min::new().min());
+    m.add(3.);
+    assert_eq!(m.min(), 3.);
+}
+
+#[test]
+fn indexed() {
+    use average::IndexedMin;
+
+    let a = IndexedMin::new();
+    assert!(a.is_empty());
+    assert_eq!(a.index(), None);
+
+    let values: Vec<f64> = (0..100)
+        .map(|i| (f64::from(i) - 17.).powi(2) + 1.)
+        .collect();
+    let a: IndexedMin = values.iter().collect();
+    assert_eq!(a.len(), 100);
+    assert_eq!(a.min(), 1.);
+    assert_eq!(a.index(), Some(17));
+
+    // The first index wins on ties.
+    let a: IndexedMin = [3., 1., 2., 1.].iter().collect();
+    assert_eq!(a.index(), Some(1));
+}
+
+#[test]
+fn indexed_merge() {
+    use average::IndexedMin;
+
+    let values: &[f64] = &[4., 2., 8., 0.5, 3., 0.5, 6.];
+    for mid in 0..values.len() {
+        let (left, right) = values.split_at(mid);
+        let mut a: IndexedMin = left.iter().collect();
+        a.merge(&right.iter().collect());
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.min(), 0.5);
+        assert_eq!(a.index(), Some(3));
+    }
+}
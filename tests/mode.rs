@@ -0,0 +1,63 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+use average::{Estimate, Merge, Mode};
+
+#[test]
+fn trivial() {
+    let mut a = Mode::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mode(), 0.);
+    assert_eq!(a.mode_count(), 0);
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mode(), 1.);
+    assert_eq!(a.mode_count(), 1);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.distinct(), 0);
+}
+
+#[test]
+fn dominant() {
+    let mut a = Mode::new();
+    for i in 0..1000 {
+        // Every third sample is 7, the other ones are spread out.
+        a.add(if i % 3 == 0 { 7. } else { f64::from(i % 100) });
+    }
+    assert_eq!(a.len(), 1000);
+    assert_eq!(a.mode(), 7.);
+    // 334 multiples of 3, and 7 other samples with `i % 100 == 7`.
+    assert_eq!(a.mode_count(), 341);
+    assert_eq!(a.count(7.), 341);
+    assert_eq!(a.estimate(), 7.);
+    assert_eq!(a.distinct(), 100);
+    assert_eq!(a.count(1000.), 0);
+}
+
+#[test]
+fn ties() {
+    let a: Mode = [3., 1., 2., 3., 1.].iter().collect();
+    assert_eq!(a.mode(), 1.);
+    assert_eq!(a.mode_count(), 2);
+    assert_eq!(a.count(2.), 1);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 5., 5., 2., 1., 5., 3., 1., 1.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Mode = sequence.iter().collect();
+        let mut a: Mode = left.iter().collect();
+        let b: Mode = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.distinct(), total.distinct());
+        assert_eq!(a.mode(), 1.);
+        assert_eq!(a.mode_count(), 4);
+        assert_eq!(a.count(5.), 3);
+    }
+}
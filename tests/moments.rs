@@ -1,14 +1,19 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
 extern crate core;
+extern crate num_traits;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_derive;
 #[cfg(feature = "serde1")]
 extern crate serde_json;
 
 use core::iter::Iterator;
 
-use average::{Moments4, Merge};
+use average::{Moments4, Merge, Skewness};
+
+define_moments!(Moments6, 6);
 
 #[test]
 fn trivial() {
@@ -94,3 +99,27 @@ fn merge() {
         assert_almost_eq!(avg_total.central_moment(4), avg_left.central_moment(4), 1e-12);
     }
 }
+
+#[test]
+fn higher_moments() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let a: Moments6 = sequence.iter().collect();
+    let expected: Skewness = sequence.iter().collect();
+    assert_almost_eq!(a.central_moment(2), expected.population_variance(), 1e-14);
+    assert_almost_eq!(a.standardized_moment(3), expected.skewness(), 1e-14);
+
+    // Compare with the two-pass calculation.
+    let n = sequence.len() as f64;
+    let mean = sequence.iter().sum::<f64>() / n;
+    for p in 2..7 {
+        let moment = sequence.iter().map(|x| (x - mean).powi(p as i32)).sum::<f64>() / n;
+        assert_almost_eq!(a.central_moment(p), moment, 1e-9 * moment.abs());
+    }
+}
+
+#[test]
+#[should_panic(expected = "too large")]
+fn central_moment_too_large() {
+    let a: Moments4 = [1., 2., 3.].iter().collect();
+    a.central_moment(5);
+}
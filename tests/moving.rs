@@ -0,0 +1,197 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+extern crate rand;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_derive;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_big_array;
+
+use average::{ExpMovingMean, ExpMovingVariance, Mean, Estimate};
+
+#[test]
+fn exp_moving_mean_trivial() {
+    let mut a = ExpMovingMean::new(0.1);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.0);
+    a.add(5.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 5.0);
+    a.add(15.0);
+    assert_eq!(a.mean(), 6.0);
+}
+
+#[test]
+fn exp_moving_mean_constant() {
+    let mut a = ExpMovingMean::new(0.3);
+    for _ in 0..100 {
+        a.add(42.0);
+    }
+    assert_eq!(a.mean(), 42.0);
+}
+
+#[test]
+fn exp_moving_mean_step() {
+    let alpha: f64 = 0.2;
+    let mut a = ExpMovingMean::new(alpha);
+    for _ in 0..10 {
+        a.add(0.0);
+    }
+    for k in 1..50 {
+        a.add(1.0);
+        let expected = 1. - (1. - alpha).powi(k);
+        assert_almost_eq!(a.mean(), expected, 1e-14);
+    }
+}
+
+#[test]
+fn exp_moving_mean_bias_corrected() {
+    let alpha: f64 = 0.1;
+    let mut a = ExpMovingMean::new(alpha);
+    assert_eq!(a.bias_corrected_mean(), 0.);
+    // A moving mean initialized with zero is biased towards zero.
+    let mut naive = 0.;
+    for t in 1..100 {
+        a.add(42.);
+        naive += alpha * (42. - naive);
+        assert_almost_eq!(a.bias_corrected_mean(), 42., 1e-13);
+        if t < 20 {
+            assert!(naive < 40.);
+        }
+    }
+
+    // The bias-corrected mean weights earlier observations exponentially.
+    let samples = [3., -1., 4., 1., -5., 9.];
+    let mut a = ExpMovingMean::new(alpha);
+    for &x in &samples {
+        a.add(x);
+    }
+    let weights: Vec<f64> = (0..samples.len())
+        .map(|i| (1. - alpha).powi((samples.len() - 1 - i) as i32))
+        .collect();
+    let expected = samples.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>()
+        / weights.iter().sum::<f64>();
+    assert_almost_eq!(a.bias_corrected_mean(), expected, 1e-14);
+    assert!((a.mean() - expected).abs() > 0.1);
+
+    a.reset();
+    assert_eq!(a.bias_corrected_mean(), 0.);
+}
+
+#[test]
+fn exp_moving_mean_half_life() {
+    let mut a = ExpMovingMean::with_half_life(10.);
+    assert_almost_eq!(a.alpha(), 1. - 0.5f64.powf(0.1), 1e-16);
+    a.add(0.0);
+    for _ in 0..10 {
+        a.add(1.0);
+    }
+    assert_almost_eq!(a.mean(), 0.5, 1e-14);
+    for _ in 0..10 {
+        a.add(1.0);
+    }
+    assert_almost_eq!(a.mean(), 0.75, 1e-14);
+}
+
+#[test]
+#[should_panic]
+fn exp_moving_mean_invalid_alpha() {
+    ExpMovingMean::new(0.0);
+}
+
+#[test]
+fn exp_moving_variance_trivial() {
+    let mut a = ExpMovingVariance::new(0.5);
+    assert!(a.is_empty());
+    assert_eq!(a.variance(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.mean(), 1.0);
+    assert_eq!(a.variance(), 0.0);
+    a.add(3.0);
+    assert_eq!(a.mean(), 2.0);
+    // (1 - 0.5) * (0 + 2 * 1)
+    assert_eq!(a.variance(), 1.0);
+    assert_eq!(a.standard_deviation(), 1.0);
+}
+
+#[test]
+fn exp_moving_variance_normal() {
+    use rand::distributions::{Normal, Distribution};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    let normal = Normal::new(2.0, 3.0);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let alpha = 0.01;
+    let mut a = ExpMovingVariance::new(alpha);
+    for _ in 0..1_000 {
+        a.add(normal.sample(&mut rng));
+    }
+    // Average over the fluctuations of the estimate.
+    let mut variance = Mean::new();
+    for _ in 0..100_000 {
+        a.add(normal.sample(&mut rng));
+        variance.add(a.variance());
+    }
+    let bias = 2. * (1. - alpha) / (2. - alpha);
+    assert_almost_eq!(variance.mean(), 9.0 * bias, 0.1);
+    assert_almost_eq!(a.mean(), 2.0, 1.0);
+}
+
+define_sliding_mean!(window10, 10);
+define_sliding_mean!(window100, 100);
+
+#[test]
+fn sliding_mean_trivial() {
+    let mut a = window10::SlidingMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.capacity(), 10);
+    assert_eq!(a.mean(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 1.0);
+    a.add(2.0);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.mean(), 1.5);
+}
+
+#[test]
+fn sliding_mean_window() {
+    let values: Vec<f64> = (0..1000).map(|i| f64::from(i).sin() * 100.).collect();
+    let mut a = window10::SlidingMean::new();
+    let mut b = window100::SlidingMean::new();
+    for (i, &x) in values.iter().enumerate() {
+        a.add(x);
+        b.add(x);
+        let start = if i >= 10 { i - 9 } else { 0 };
+        let expected: Mean = values[start..i + 1].iter().collect();
+        assert_eq!(a.len() as u64, expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+        let start = if i >= 100 { i - 99 } else { 0 };
+        let expected: Mean = values[start..i + 1].iter().collect();
+        assert_almost_eq!(b.mean(), expected.mean(), 1e-12);
+    }
+}
+
+#[test]
+fn sliding_mean_cancelation() {
+    // Without recalculating the sum, the small values would be lost.
+    let mut a: window10::SlidingMean = (0..1_000_000).map(|i| {
+        if i % 2 == 0 { 1e16 } else { 0.1 }
+    }).collect();
+    for _ in 0..10 {
+        a.add(2.);
+    }
+    assert_eq!(a.mean(), 2.);
+}
+
+#[test]
+fn sliding_mean_debug() {
+    let a: window10::SlidingMean = (0..12).map(f64::from).collect();
+    assert_eq!(format!("{:?}", a),
+               "SlidingMean { buf: [2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0], sum: 65.0 }");
+    let a: window10::SlidingMean = (0..3).map(f64::from).collect();
+    assert_eq!(format!("{:?}", a), "SlidingMean { buf: [0.0, 1.0, 2.0], sum: 3.0 }");
+}
@@ -0,0 +1,52 @@
+#![cfg(all(feature = "alloc", feature = "ndarray"))]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+extern crate ndarray;
+
+use ndarray::arr2;
+
+use average::{Mean, Variance, estimate_columns, estimate_rows};
+
+#[test]
+fn columns() {
+    let a = arr2(&[[1., 10., -1.],
+                   [2., 25., -1.],
+                   [4., 30., -1.],
+                   [9., 47., -1.]]);
+    let v: Vec<Variance> = estimate_columns(a.view());
+    assert_eq!(v.len(), 3);
+    for (j, est) in v.iter().enumerate() {
+        let mut manual = Variance::new();
+        for i in 0..4 {
+            manual.add(a[[i, j]]);
+        }
+        assert_eq!(est.len(), 4);
+        assert_eq!(est.mean(), manual.mean());
+        assert_eq!(est.sample_variance(), manual.sample_variance());
+    }
+    assert_eq!(v[2].sample_variance(), 0.);
+}
+
+#[test]
+fn rows() {
+    let a = arr2(&[[1., 2., 3.],
+                   [10., 20., 30.]]);
+    let m: Vec<Mean> = estimate_rows(a.view());
+    assert_eq!(m.len(), 2);
+    assert_eq!(m[0].mean(), 2.);
+    assert_eq!(m[1].mean(), 20.);
+}
+
+#[test]
+fn transposed() {
+    let a = arr2(&[[1., 2., 3.],
+                   [10., 20., 30.]]);
+    let columns: Vec<Mean> = estimate_columns(a.t());
+    let rows: Vec<Mean> = estimate_rows(a.view());
+    assert_eq!(columns.len(), rows.len());
+    for (c, r) in columns.iter().zip(&rows) {
+        assert_eq!(c.mean(), r.mean());
+    }
+}
@@ -0,0 +1,72 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{MeanWithError, Merge, PairedMean};
+
+const BEFORE: [f64; 8] = [12.1, 10.4, 14.2, 9.8, 11.5, 13.0, 10.9, 12.7];
+const AFTER: [f64; 8] = [12.9, 11.0, 14.1, 10.9, 12.6, 13.4, 11.8, 13.9];
+
+fn paired() -> PairedMean {
+    BEFORE.iter().cloned().zip(AFTER.iter().cloned()).collect()
+}
+
+#[test]
+fn trivial() {
+    let mut a = PairedMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mean_difference(), 0.);
+    assert_eq!(a.t_statistic(), 0.);
+    a.add(1., 3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean_difference(), 2.);
+    assert_eq!(a.variance_of_difference(), 0.);
+    assert_eq!(a.t_statistic(), 0.);
+    a.add(2., 4.);
+    assert_eq!(a.t_statistic(), std::f64::INFINITY);
+    a.add(5., 3.).add(0., 0.);
+    assert_eq!(a.len(), 4);
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn versus_differences() {
+    let a = paired();
+    let d: MeanWithError = BEFORE.iter().zip(&AFTER).map(|(x, y)| y - x).collect();
+    assert_eq!(a.len(), 8);
+    assert_almost_eq!(a.mean_difference(), d.mean(), 1e-15);
+    assert_almost_eq!(a.variance_of_difference(), d.sample_variance(), 1e-15);
+    assert_almost_eq!(a.error(), d.error(), 1e-15);
+    assert_almost_eq!(a.t_statistic(), d.mean() / d.error(), 1e-13);
+    assert_almost_eq!(a.mean_difference(), 0.75, 1e-14);
+    // The paired differences are consistently positive.
+    assert!(a.t_statistic() > 4.);
+    assert_eq!(a.differences().len(), 8);
+}
+
+#[test]
+fn merge() {
+    let mut a: PairedMean = BEFORE[..3].iter().cloned().zip(AFTER[..3].iter().cloned()).collect();
+    let b: PairedMean = BEFORE[3..].iter().cloned().zip(AFTER[3..].iter().cloned()).collect();
+    a.merge(&b);
+    let c = paired();
+    assert_eq!(a.len(), c.len());
+    assert_almost_eq!(a.mean_difference(), c.mean_difference(), 1e-15);
+    assert_almost_eq!(a.variance_of_difference(), c.variance_of_difference(), 1e-14);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a = paired();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: PairedMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), 8);
+    assert_almost_eq!(c.mean_difference(), a.mean_difference(), 1e-15);
+    assert_almost_eq!(c.t_statistic(), a.t_statistic(), 1e-14);
+}
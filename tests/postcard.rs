@@ -0,0 +1,107 @@
+#![cfg(feature = "serde1")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+extern crate postcard;
+
+use average::{CoVariance, Estimate, Kurtosis, Max, Mean, MeanWithError, Min, Quantile,
+              Variance, WeightedMeanWithError};
+
+#[test]
+fn mean() {
+    let a: Mean = (1..6).map(f64::from).collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    // The layout is the mean as little-endian `f64`, followed by the sample
+    // size as little-endian `u64`. Changing it breaks stored checkpoints.
+    assert_eq!(&bytes[..], &[0, 0, 0, 0, 0, 0, 8, 64, 5, 0, 0, 0, 0, 0, 0, 0]);
+    let b: Mean = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn variance() {
+    let a: Variance = (1..6).map(f64::from).collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    assert_eq!(&bytes[..], &[
+        0, 0, 0, 0, 0, 0, 8, 64,
+        5, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 36, 64]);
+    let b: Variance = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: MeanWithError = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.error(), b.error());
+}
+
+#[test]
+fn variance_f32() {
+    let a: Variance<f32> = (1..6).map(|x| x as f32).collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    assert_eq!(bytes.len(), 16);
+    let b: Variance<f32> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn covariance() {
+    let a: CoVariance = (1..6).map(|x| (f64::from(x), f64::from(x * x))).collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: CoVariance = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn kurtosis() {
+    let a: Kurtosis = [1., 2., 3., 4., 11.].iter().collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: Kurtosis = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.sample_variance(), b.sample_variance());
+    assert_eq!(a.skewness(), b.skewness());
+    assert_eq!(a.kurtosis(), b.kurtosis());
+}
+
+#[test]
+fn quantile() {
+    let mut a = Quantile::new(0.5);
+    for x in 0..100 {
+        a.add(f64::from((x * 37) % 101));
+    }
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let mut b: Quantile = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a.p(), b.p());
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.quantile(), b.quantile());
+    // The deserialized estimator can be used for further samples.
+    a.add(1000.);
+    b.add(1000.);
+    assert_eq!(a.quantile(), b.quantile());
+}
+
+#[test]
+fn minmax() {
+    let a: Min = [3., 1., 2.].iter().collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: Min = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a.min(), b.min());
+
+    let a: Max = [3., 1., 2.].iter().collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: Max = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a.max(), b.max());
+}
+
+#[test]
+fn weighted_mean() {
+    let a: WeightedMeanWithError = [(1., 0.5), (2., 1.), (4., 2.)].iter().collect();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: WeightedMeanWithError = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a.weighted_mean(), b.weighted_mean());
+    assert_eq!(a.effective_len(), b.effective_len());
+    assert_eq!(a.error(), b.error());
+}
@@ -0,0 +1,56 @@
+#![allow(clippy::float_cmp)]
+
+extern crate core;
+extern crate average;
+extern crate num_traits;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_derive;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_big_array;
+
+use average::prelude::*;
+
+concatenate!(MinMax, [Min, min], [Max, max]);
+define_histogram!(hist, 4);
+define_moments!(Moments5, 5);
+define_quantiles!(quartiles, 3);
+define_sliding_mean!(window, 2);
+
+#[test]
+fn prelude() {
+    let mut a: Mean = (1..6).map(f64::from).collect();
+    a.add(6.);
+    assert_eq!(a.estimate(), 3.5);
+    let mut b: Variance = (1..6).map(f64::from).collect();
+    b.merge(&Variance::new());
+    assert_eq!(b.sample_variance(), 2.5);
+    let c = CoVariance::new();
+    assert!(c.is_empty());
+    let d: MinMax = (1..6).map(f64::from).collect();
+    assert_eq!(d.min(), 1.);
+    assert_eq!(d.max(), 5.);
+    let e: Sum = (1..6).map(f64::from).collect();
+    assert_eq!(e.sum(), 15.);
+}
+
+#[test]
+fn macros() {
+    let mut h = hist::Histogram::with_const_width(0., 4.);
+    for i in 0..4 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.bins(), &[1, 1, 1, 1]);
+
+    let m: Moments5 = (1..6).map(f64::from).collect();
+    assert_eq!(m.mean(), 3.);
+    assert_eq!(m.central_moment(2), 2.);
+
+    let mut q = quartiles::Quantiles::new(&[0.25, 0.5, 0.75]);
+    for i in 1..6 {
+        q.add(f64::from(i));
+    }
+    assert_eq!(q.quantile(0.5), Some(3.));
+
+    let w: window::SlidingMean = (1..6).map(f64::from).collect();
+    assert_eq!(w.mean(), 4.5);
+}
@@ -1,7 +1,7 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
-#[macro_use] extern crate average;
-#[macro_use] extern crate proptest;
+extern crate average;
+extern crate proptest;
 
 extern crate core;
 
@@ -17,8 +17,8 @@ proptest! {
     fn reasonable_bounds(s in prop::collection::vec(
         f64::POSITIVE | f64::NEGATIVE | f64::SUBNORMAL | f64::ZERO, 1..100usize)) {
         // See https://hypothesis.works/articles/calculating-the-mean/.
-        let max = s.iter().cloned().fold(0./0., f64::max);
-        let min = s.iter().cloned().fold(0./0., f64::min);
+        let max = s.iter().cloned().fold(core::f64::NAN, f64::max);
+        let min = s.iter().cloned().fold(core::f64::NAN, f64::min);
         let a: Mean = s.iter().collect();
         let mean = a.mean();
         println!("min: {}  mean: {}  max: {}", min, mean, max);
@@ -1,11 +1,17 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
-extern crate average;
+#[macro_use] extern crate average;
+extern crate core;
 #[cfg(feature = "serde1")]
 extern crate serde_json;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_derive;
+#[cfg(feature = "serde1")]
+#[macro_use] extern crate serde_big_array;
 extern crate quantiles;
+extern crate rand;
 
-use average::{Estimate, Quantile};
+use average::{Estimate, Merge, Quantile, Quartiles};
 use quantiles::ckms::CKMS;
 
 #[test]
@@ -386,3 +392,166 @@ fn percentile_99() {
     assert!((q.quantile() - 9.).abs() < TOL);
 }
 
+#[test]
+fn reset() {
+    let mut q = Quantile::new(0.9);
+    for i in 0..100 {
+        q.add(f64::from(i));
+    }
+    q.reset();
+    assert!(q.is_empty());
+    assert_eq!(q.p(), 0.9);
+    assert_eq!(q.quantile(), 0.);
+    let mut expected = Quantile::new(0.9);
+    for i in 0..20 {
+        q.add(f64::from(i).sqrt());
+        expected.add(f64::from(i).sqrt());
+    }
+    assert_eq!(q.len(), expected.len());
+    assert_eq!(q.quantile(), expected.quantile());
+}
+
+fn quantile_of<'a, I: IntoIterator<Item = &'a f64>>(p: f64, values: I) -> Quantile {
+    let mut q = Quantile::new(p);
+    for &x in values {
+        q.add(x);
+    }
+    q
+}
+
+#[test]
+fn merge_few_observations() {
+    let sequence: &[f64] = &[3., 1., 4., 1., 5., 9., 2., 6.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        if left.len() >= 5 && right.len() >= 5 {
+            continue;
+        }
+        let mut q = quantile_of(0.5, left);
+        q.merge(&quantile_of(0.5, right));
+        // The observations of the smaller sample are added to the other one.
+        let expected = if right.len() < 5 {
+            quantile_of(0.5, left.iter().chain(right))
+        } else {
+            quantile_of(0.5, right.iter().chain(left))
+        };
+        assert_eq!(q.len(), expected.len());
+        assert_eq!(q.quantile(), expected.quantile());
+    }
+}
+
+#[test]
+fn merge_normal() {
+    use rand::distributions::{Distribution, Normal};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let normal = Normal::new(2.0, 3.0);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let mut values: Vec<f64> = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+    let (left, right) = values.split_at(50_000);
+    let mut q = quantile_of(0.5, left);
+    q.merge(&quantile_of(0.5, right));
+    assert_eq!(q.len(), 100_000);
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = 0.5 * (values[49_999] + values[50_000]);
+    assert!((q.quantile() - median).abs() < 0.05);
+
+    // Adding more observations after merging still works.
+    for _ in 0..100_000 {
+        q.add(normal.sample(&mut rng));
+    }
+    assert!((q.quantile() - 2.0).abs() < 0.05);
+}
+
+#[test]
+#[should_panic]
+fn merge_different_p() {
+    let mut q = Quantile::new(0.5);
+    q.merge(&Quantile::new(0.9));
+}
+
+#[test]
+fn collect_median() {
+    let values: Vec<f64> = (0..100).map(|i| f64::from((i * 37) % 100)).collect();
+    let mut expected = Quantile::new(0.5);
+    for &x in &values {
+        expected.add(x);
+    }
+    let a: Quantile = values.iter().collect();
+    assert_eq!(a.p(), 0.5);
+    assert_eq!(a.len(), 100);
+    assert_eq!(a.quantile(), expected.quantile());
+    let b: Quantile = values.into_iter().collect();
+    assert_eq!(b.quantile(), expected.quantile());
+}
+
+define_quantiles!(percentiles, 5);
+
+#[test]
+fn quantiles() {
+    let ps = [0.05, 0.25, 0.5, 0.75, 0.95];
+    let mut q = percentiles::Quantiles::new(&ps);
+    assert!(q.is_empty());
+    let mut expected: Vec<Quantile> = ps.iter().map(|&p| Quantile::new(p)).collect();
+    for i in 0..1000 {
+        let x = f64::from((i * 7919) % 1000).sqrt();
+        q.add(x);
+        for e in &mut expected {
+            e.add(x);
+        }
+    }
+    assert_eq!(q.len(), 1000);
+    for (e, &p) in expected.iter().zip(ps.iter()) {
+        assert_eq!(q.quantile(p), Some(e.quantile()));
+    }
+    assert_eq!(q.estimators().len(), 5);
+    assert_eq!(q.quantile(0.1), None);
+
+    q.reset();
+    assert!(q.is_empty());
+    assert_eq!(q.estimators()[4].p(), 0.95);
+}
+
+#[test]
+#[should_panic]
+fn quantiles_wrong_len() {
+    percentiles::Quantiles::new(&[0.5]);
+}
+
+#[test]
+fn quartiles() {
+    let a = Quartiles::new();
+    assert!(a.is_empty());
+    assert_eq!(a.iqr(), 0.);
+    let values: Vec<f64> = (1..101).map(f64::from).collect();
+    let a: Quartiles = values.iter().collect();
+    assert_eq!(a.len(), 100);
+    let q1 = quantile_of(0.25, &values);
+    let q2 = quantile_of(0.5, &values);
+    let q3 = quantile_of(0.75, &values);
+    assert_eq!(a.q1(), q1.quantile());
+    assert_eq!(a.median(), q2.quantile());
+    assert_eq!(a.estimate(), q2.quantile());
+    assert_eq!(a.q3(), q3.quantile());
+    assert_eq!(a.iqr(), q3.quantile() - q1.quantile());
+}
+
+#[test]
+fn quartiles_uniform() {
+    use rand::distributions::{Distribution, Uniform};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let uniform = Uniform::new(0., 1.);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let a: Quartiles = (0..100_000).map(|_| uniform.sample(&mut rng)).collect();
+    assert!((a.q1() - 0.25).abs() < 0.01);
+    assert!((a.median() - 0.5).abs() < 0.01);
+    assert!((a.q3() - 0.75).abs() < 0.01);
+    assert!((a.iqr() - 0.5).abs() < 0.01);
+    assert_eq!(a.iqr(), a.q3() - a.q1());
+}
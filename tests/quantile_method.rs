@@ -0,0 +1,138 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate rand;
+extern crate rand_xoshiro;
+
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use average::{Estimate, Merge, Quantile, QuantileEstimator, QuantileMethod};
+
+const SAMPLE: [f64; 10] = [7., 1., 9., 4., 3., 10., 2., 8., 6., 5.];
+
+#[test]
+fn exact() {
+    let mut a = QuantileEstimator::new(QuantileMethod::Exact, 0.5);
+    assert!(a.is_empty());
+    assert_eq!(a.quantile(0.5), Some(0.));
+    for &x in &SAMPLE {
+        a.add(x);
+    }
+    assert_eq!(a.len(), 10);
+    assert_eq!(a.quantile(0.), Some(1.));
+    assert_eq!(a.quantile(0.5), Some(5.5));
+    assert_eq!(a.quantile(0.25), Some(3.25));
+    assert_eq!(a.quantile(0.9), Some(9.1));
+    assert_eq!(a.quantile(1.), Some(10.));
+    assert_eq!(a.estimate(), 5.5);
+}
+
+#[test]
+fn exact_vs_p2() {
+    let p = 0.25;
+    let mut exact = QuantileEstimator::new(QuantileMethod::Exact, p);
+    let mut p2 = QuantileEstimator::new(QuantileMethod::P2, p);
+    let mut reference = Quantile::new(p);
+    for &x in &SAMPLE {
+        exact.add(x);
+        p2.add(x);
+        reference.add(x);
+    }
+    assert_eq!(exact.quantile(p), Some(3.25));
+    let approx = p2.quantile(p).unwrap();
+    assert_eq!(approx, reference.quantile());
+    assert_ne!(approx, 3.25);
+    assert_almost_eq!(approx, 3.25, 1.);
+    assert_eq!(p2.quantile(0.5), None);
+}
+
+#[test]
+fn histogram() {
+    let method = QuantileMethod::Histogram { start: 0., end: 10., bins: 10 };
+    let mut a = QuantileEstimator::new(method, 0.5);
+    assert_eq!(a.method(), method);
+    for &x in &SAMPLE {
+        a.add(x - 0.5);
+    }
+    assert_eq!(a.len(), 10);
+    assert_eq!(a.quantile(0.5), Some(5.));
+    assert_eq!(a.quantile(0.25), Some(2.5));
+    a.add(20.);
+    assert_eq!(a.len(), 11);
+    assert_eq!(a.quantile(1.), Some(10.));
+}
+
+#[test]
+fn merge() {
+    for &method in &[QuantileMethod::Exact,
+                     QuantileMethod::Histogram { start: 0., end: 10., bins: 10 }] {
+        let (left, right) = SAMPLE.split_at(4);
+        let mut total = QuantileEstimator::new(method, 0.5);
+        let mut a = QuantileEstimator::new(method, 0.5);
+        let mut b = QuantileEstimator::new(method, 0.5);
+        for &x in &SAMPLE {
+            total.add(x);
+        }
+        for &x in left {
+            a.add(x);
+        }
+        for &x in right {
+            b.add(x);
+        }
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.quantile(0.5), total.quantile(0.5));
+        assert_eq!(a.quantile(0.1), total.quantile(0.1));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Both estimators must use the same method")]
+fn merge_different_methods() {
+    let mut a = QuantileEstimator::new(QuantileMethod::Exact, 0.5);
+    let b = QuantileEstimator::new(QuantileMethod::P2, 0.5);
+    a.merge(&b);
+}
+
+#[test]
+fn reset() {
+    let mut a = QuantileEstimator::new(QuantileMethod::Exact, 0.5);
+    a.add(1.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.method(), QuantileMethod::Exact);
+    assert_eq!(a.p(), 0.5);
+}
+
+#[test]
+fn with_error() {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(7);
+    let samples: Vec<f64> = (0..200_000).map(|_| {
+        let x: f64 = rng.gen();
+        x * x * 100.
+    }).collect();
+    let mut sorted = samples.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let epsilon = 0.01;
+    for &p in &[0.001, 0.1, 0.25, 0.5, 0.9, 0.999] {
+        let mut a = QuantileEstimator::with_error(p, epsilon);
+        assert_eq!(a.method(), QuantileMethod::TDigest { compression: 315. });
+        for &x in &samples {
+            a.add(x);
+        }
+        assert_eq!(a.len(), samples.len() as u64);
+        let q = a.estimate();
+        let rank = sorted.iter().filter(|&&x| x <= q).count() as f64
+            / sorted.len() as f64;
+        assert!((rank - p).abs() <= epsilon, "p = {}, rank = {}", p, rank);
+    }
+}
+
+#[test]
+#[should_panic(expected = "epsilon must be in (0, 1)")]
+fn with_error_invalid() {
+    QuantileEstimator::with_error(0.5, 0.);
+}
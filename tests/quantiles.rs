@@ -0,0 +1,71 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{Estimate, Merge, Quantile, Quantiles};
+
+#[test]
+fn quantiles() {
+    let ps = [0.05, 0.25, 0.5, 0.75, 0.95];
+    let mut q = Quantiles::new(&ps);
+    assert!(q.is_empty());
+    let mut expected: Vec<Quantile> = ps.iter().map(|&p| Quantile::new(p)).collect();
+    for i in 0..1000 {
+        let x = f64::from((i * 7919) % 1000).sqrt();
+        q.add(x);
+        for e in &mut expected {
+            e.add(x);
+        }
+    }
+    assert_eq!(q.len(), 1000);
+    for (e, &p) in expected.iter().zip(ps.iter()) {
+        assert_eq!(q.quantile(p), Some(e.quantile()));
+    }
+    assert_eq!(q.estimators().len(), 5);
+    assert_eq!(q.quantile(0.1), None);
+
+    q.reset();
+    assert!(q.is_empty());
+    assert_eq!(q.estimators()[4].p(), 0.95);
+}
+
+#[test]
+fn merge() {
+    let ps = [0.1, 0.5, 0.9];
+    let mut a = Quantiles::new(&ps);
+    a.extend((0..100).map(f64::from));
+    let mut b = Quantiles::new(&ps);
+    b.extend((100..200).map(f64::from));
+    a.merge(&b);
+    assert_eq!(a.len(), 200);
+    assert!((a.quantile(0.5).unwrap() - 99.5).abs() < 10.);
+}
+
+#[test]
+#[should_panic(expected = "at least one probability")]
+fn no_probabilities() {
+    Quantiles::new(&[]);
+}
+
+#[test]
+#[should_panic(expected = "same probabilities")]
+fn merge_different_len() {
+    let mut a = Quantiles::new(&[0.5]);
+    a.merge(&Quantiles::new(&[0.5, 0.9]));
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = Quantiles::new(&[0.25, 0.75]);
+    a.extend((0..20).map(f64::from));
+    let b = serde_json::to_string(&a).unwrap();
+    let c: Quantiles = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), 20);
+    assert_eq!(c.quantile(0.75), a.quantile(0.75));
+}
@@ -0,0 +1,63 @@
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{Range, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = Range::new();
+    assert!(a.is_empty());
+    assert_eq!(a.range(), 0.);
+    a.add(2.);
+    assert!(!a.is_empty());
+    assert_eq!(a.min(), 2.);
+    assert_eq!(a.max(), 2.);
+    assert_eq!(a.range(), 0.);
+}
+
+#[test]
+fn simple() {
+    let a: Range = [3., -1.5, 4., 1., -5., 9., 2., 6.].iter().collect();
+    assert_eq!(a.min(), -5.);
+    assert_eq!(a.max(), 9.);
+    assert_eq!(a.range(), 14.);
+    assert_eq!(a.estimate(), 14.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: Range = [3., -1.5, 4.].iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    assert_eq!(&b, "{\"min\":{\"x\":-1.5},\"max\":{\"x\":4.0}}");
+    let c: Range = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.range(), 5.5);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Range = sequence.iter().collect();
+        let mut a: Range = left.iter().collect();
+        a.merge(&right.iter().collect());
+        assert_eq!(total.min(), a.min());
+        assert_eq!(total.max(), a.max());
+        assert_eq!(total.range(), a.range());
+    }
+}
+
+#[test]
+fn reset() {
+    let mut a: Range = (1..6).map(f64::from).collect();
+    a.reset();
+    assert!(a.is_empty());
+    a.add(3.);
+    assert_eq!(a.range(), 0.);
+}
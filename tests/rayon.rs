@@ -0,0 +1,57 @@
+#![cfg(feature = "rayon")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate rand;
+extern crate rayon;
+
+use rayon::prelude::*;
+
+use average::{Kurtosis, Mean, MeanWithError, Max, Min, par_reduce};
+
+/// Create a random vector by sampling from a normal distribution.
+fn initialize_vec(size: usize) -> Vec<f64> {
+    use rand::distributions::{Normal, Distribution};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    let normal = Normal::new(2.0, 3.0);
+    let mut values = Vec::with_capacity(size);
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    for _ in 0..size {
+        values.push(normal.sample(&mut rng));
+    }
+    values
+}
+
+#[test]
+fn variance() {
+    let values = initialize_vec(1_000_000);
+    let a: MeanWithError = values.iter().collect();
+    let b: MeanWithError = par_reduce(values.par_iter().cloned());
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-12);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-12);
+}
+
+#[test]
+fn other_estimators() {
+    let values = initialize_vec(100_000);
+    let a: Mean = par_reduce(values.par_iter().cloned());
+    assert_almost_eq!(a.mean(), values.iter().collect::<Mean>().mean(), 1e-12);
+    let a: Kurtosis = par_reduce(values.par_iter().cloned());
+    assert_almost_eq!(a.kurtosis(), values.iter().collect::<Kurtosis>().kurtosis(), 1e-12);
+    let a: Min = par_reduce(values.par_iter().cloned());
+    assert_eq!(a.min(), values.iter().collect::<Min>().min());
+    let a: Max = par_reduce(values.par_iter().cloned());
+    assert_eq!(a.max(), values.iter().collect::<Max>().max());
+}
+
+#[test]
+fn empty() {
+    let a: MeanWithError = par_reduce(Vec::<f64>::new().into_par_iter());
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+}
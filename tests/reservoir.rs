@@ -0,0 +1,75 @@
+#![cfg(all(feature = "alloc", feature = "rand_core"))]
+
+extern crate average;
+
+extern crate rand;
+
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use average::Reservoir;
+
+fn rng() -> SmallRng {
+    SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn trivial() {
+    let mut a = Reservoir::new(3, rng());
+    assert!(a.is_empty());
+    assert_eq!(a.capacity(), 3);
+    a.add(1);
+    a.add(2);
+    assert_eq!(a.samples(), &[1, 2]);
+    a.add(3);
+    assert_eq!(a.samples(), &[1, 2, 3]);
+    assert_eq!(a.len(), 3);
+    a.reset();
+    assert!(a.is_empty());
+    assert!(a.samples().is_empty());
+}
+
+#[test]
+fn reference() {
+    let mut a = Reservoir::new(5, rng());
+    a.extend(0..100);
+    assert_eq!(a.len(), 100);
+    assert_eq!(a.samples(), &[10, 41, 67, 7, 92]);
+}
+
+#[test]
+fn from_seed() {
+    let seed = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+    let mut a: Reservoir<_, SmallRng> = Reservoir::from_seed(5, seed);
+    let mut b: Reservoir<_, SmallRng> = Reservoir::from_seed(5, seed);
+    a.extend(0..1000);
+    b.extend(0..1000);
+    assert_eq!(a.samples(), b.samples());
+
+    let mut c = Reservoir::new(5, rng());
+    c.extend(0..1000);
+    assert_eq!(a.samples(), c.samples());
+
+    let mut d: Reservoir<_, SmallRng> = Reservoir::from_seed(5, [42; 16]);
+    d.extend(0..1000);
+    assert_ne!(a.samples(), d.samples());
+}
+
+#[test]
+fn uniform() {
+    let (k, n, runs) = (4, 20, 20_000);
+    let mut counts = [0u32; 20];
+    let mut rng = rng();
+    for _ in 0..runs {
+        let mut a = Reservoir::new(k, &mut rng);
+        a.extend(0..n);
+        for &i in a.samples() {
+            counts[i] += 1;
+        }
+    }
+    // Each item should be retained with probability k / n.
+    let expected = f64::from(runs) * (k as f64) / (n as f64);
+    for &c in &counts {
+        assert!((f64::from(c) - expected).abs() < 0.05 * expected);
+    }
+}
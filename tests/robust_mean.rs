@@ -0,0 +1,81 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+extern crate rand;
+extern crate rand_xoshiro;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use average::{Estimate, Mean, RobustMean};
+
+#[test]
+fn trivial() {
+    let mut a = RobustMean::new(0.1, 0.9);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.warm_up(), 10);
+    for i in 0..10 {
+        a.add(f64::from(i));
+    }
+    assert_eq!(a.len(), 10);
+    assert_eq!(a.rejected(), 0);
+    assert_eq!(a.mean(), 4.5);
+    assert_eq!(a.estimate(), 4.5);
+    a.add(1000.);
+    assert_eq!(a.total(), 11);
+    assert_eq!(a.rejected(), 1);
+    assert_eq!(a.mean(), 4.5);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.warm_up(), 10);
+    assert_eq!(RobustMean::new(0.4, 0.6).warm_up(), 5);
+}
+
+#[test]
+fn outliers() {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(42);
+    let mut robust = RobustMean::new(0.01, 0.99);
+    let mut plain = Mean::new();
+    for i in 0..100_000 {
+        let x = if i % 500 == 250 {
+            1e6
+        } else {
+            10. + rng.gen::<f64>() - 0.5
+        };
+        robust.add(x);
+        plain.add(x);
+    }
+    assert!((plain.mean() - 10.).abs() > 1000.);
+    assert_almost_eq!(robust.mean(), 10., 0.01);
+    assert!(robust.lower_quantile() > 9.4 && robust.lower_quantile() < 9.6);
+    assert!(robust.upper_quantile() > 10.4 && robust.upper_quantile() < 10.6);
+    let fraction = robust.rejected() as f64 / robust.total() as f64;
+    assert!(fraction > 0.015 && fraction < 0.025, "fraction: {}", fraction);
+}
+
+#[test]
+#[should_panic]
+fn invalid_quantiles() {
+    RobustMean::new(0.9, 0.1);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = RobustMean::new(0.1, 0.9);
+    for i in 0..20 {
+        a.add(f64::from(i));
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let mut c: RobustMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), a.len());
+    assert_almost_eq!(c.mean(), a.mean(), 1e-14);
+    a.add(100.);
+    c.add(100.);
+    assert_eq!(c.rejected(), a.rejected());
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -73,3 +73,48 @@ fn merge() {
         assert_almost_eq!(avg_total.skewness(), avg_left.skewness(), 1e-14);
     }
 }
+
+#[test]
+fn standard_error() {
+    let mut a: Skewness = [1., 2.].iter().collect();
+    assert_eq!(a.standard_error(), 0.0);
+    assert_eq!(a.z_score(), 0.0);
+    a.add(4.);
+    // sqrt(6 * 3 * 2 / (1 * 4 * 6))
+    assert_almost_eq!(a.standard_error(), f64::sqrt(1.5), 1e-15);
+    let a: Skewness = (0..100).map(|x| f64::from(x).powi(2)).collect();
+    let n: f64 = 100.;
+    let expected = (6. * n * (n - 1.) / ((n - 2.) * (n + 1.) * (n + 3.))).sqrt();
+    assert_almost_eq!(a.standard_error(), expected, 1e-15);
+    assert_almost_eq!(a.z_score(), a.skewness() / expected, 1e-14);
+    assert!(a.z_score() > 2.);
+}
+
+#[test]
+fn central_moments() {
+    let data = [1., 2., 2., 4., 6.];
+    let a: Skewness = data.iter().collect();
+    let moment = |k: i32| data.iter().map(|x| (x - 3.).powi(k)).sum::<f64>() / 5.;
+    assert_eq!(a.central_moment_2(), a.population_variance());
+    assert_almost_eq!(a.central_moment_3(), moment(3), 1e-14);
+    assert_almost_eq!(a.skewness(), a.central_moment_3() / moment(2).powf(1.5), 1e-14);
+    assert_eq!(Skewness::new().central_moment_3(), 0.);
+}
+
+#[test]
+fn merge_shards() {
+    // A skewed sequence far from zero.
+    let values: Vec<f64> = (0..100_000)
+        .map(|i| 100. + (3. * (f64::from(i) * 0.7).sin()).exp())
+        .collect();
+    let single: Skewness = values.iter().collect();
+    let mut merged: Skewness = values[..20_000].iter().collect();
+    let middle: Skewness = values[20_000..55_555].iter().collect();
+    let last: Skewness = values[55_555..].iter().collect();
+    merged.merge(&middle);
+    merged.merge(&last);
+    assert_eq!(merged.len(), single.len());
+    assert_almost_eq!(merged.mean(), single.mean(), 1e-8);
+    assert_almost_eq!(merged.sample_variance(), single.sample_variance(), 1e-8);
+    assert_almost_eq!(merged.skewness(), single.skewness(), 1e-8);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{Estimate, Mean, SlidingMean};
+
+#[test]
+fn trivial() {
+    let mut a = SlidingMean::new(10);
+    assert!(a.is_empty());
+    assert_eq!(a.window(), 10);
+    assert_eq!(a.mean(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 1.0);
+    a.add(2.0);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.estimate(), 1.5);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.window(), 10);
+}
+
+#[test]
+fn window() {
+    let values: Vec<f64> = (0..1000).map(|i| f64::from(i).sin() * 100.).collect();
+    for &window in &[1, 10, 100] {
+        let mut a = SlidingMean::new(window);
+        for (i, &x) in values.iter().enumerate() {
+            a.add(x);
+            let start = if i >= window { i + 1 - window } else { 0 };
+            let expected: Mean = values[start..i + 1].iter().collect();
+            assert_eq!(a.len(), expected.len());
+            assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+        }
+    }
+}
+
+#[test]
+fn cancelation() {
+    // Without recalculating the sum, the small values would be lost.
+    let mut a = SlidingMean::new(10);
+    for i in 0..1_000_000 {
+        a.add(if i % 2 == 0 { 1e16 } else { 0.1 });
+    }
+    for _ in 0..10 {
+        a.add(2.);
+    }
+    assert_eq!(a.mean(), 2.);
+}
+
+#[test]
+fn debug() {
+    let mut a = SlidingMean::new(3);
+    for i in 0..5 {
+        a.add(f64::from(i));
+    }
+    assert_eq!(format!("{:?}", a), "SlidingMean { window: 3, buf: [2.0, 3.0, 4.0], sum: 9.0 }");
+}
+
+#[test]
+#[should_panic(expected = "window must be positive")]
+fn zero_window() {
+    SlidingMean::new(0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = SlidingMean::new(3);
+    for i in 0..5 {
+        a.add(f64::from(i));
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let mut c: SlidingMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.mean(), 3.);
+    c.add(5.);
+    a.add(5.);
+    assert_eq!(c.mean(), a.mean());
+}
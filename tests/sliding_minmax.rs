@@ -0,0 +1,94 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+extern crate average;
+
+extern crate rand;
+extern crate rand_xoshiro;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use average::{SlidingMin, SlidingMax};
+
+#[test]
+fn trivial() {
+    let mut a = SlidingMax::new(2);
+    assert!(a.is_empty());
+    assert_eq!(a.max(), std::f64::NEG_INFINITY);
+    a.add(3.);
+    assert_eq!(a.max(), 3.);
+    a.add(1.);
+    assert_eq!(a.max(), 3.);
+    assert_eq!(a.len(), 2);
+    a.add(2.);
+    assert_eq!(a.max(), 2.);
+    assert_eq!(a.len(), 2);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.window(), 2);
+
+    let mut b = SlidingMin::new(2);
+    assert_eq!(b.min(), std::f64::INFINITY);
+    b.add(1.);
+    b.add(3.);
+    assert_eq!(b.min(), 1.);
+    b.add(2.);
+    assert_eq!(b.min(), 2.);
+}
+
+#[test]
+fn brute_force() {
+    let mut rng = Xoshiro256StarStar::seed_from_u64(42);
+    // Few distinct values, so that there are many ties.
+    let samples: Vec<f64> = (0..1000).map(|_| f64::from(rng.gen_range(0, 20))).collect();
+    for &window in &[1, 2, 3, 10, 100, 2000] {
+        let mut max = SlidingMax::new(window);
+        let mut min = SlidingMin::new(window);
+        for (i, &x) in samples.iter().enumerate() {
+            max.add(x);
+            min.add(x);
+            let start = (i + 1).saturating_sub(window);
+            let w = &samples[start..i + 1];
+            let expected_max = w.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
+            let expected_min = w.iter().cloned().fold(std::f64::INFINITY, f64::min);
+            assert_eq!(max.max(), expected_max);
+            assert_eq!(min.min(), expected_min);
+            assert_eq!(max.len(), w.len() as u64);
+        }
+    }
+}
+
+#[test]
+fn nan() {
+    let mut a = SlidingMax::new(2);
+    a.add(1.);
+    a.add(std::f64::NAN);
+    assert_eq!(a.max(), 1.);
+    a.add(std::f64::NAN);
+    assert_eq!(a.max(), std::f64::NEG_INFINITY);
+    a.add(0.);
+    assert_eq!(a.max(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn zero_window() {
+    SlidingMax::new(0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = SlidingMax::new(3);
+    for &x in &[1., 5., 2., 3.] {
+        a.add(x);
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let mut c: SlidingMax = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.max(), 5.);
+    c.add(4.);
+    assert_eq!(c.max(), 4.);
+}
@@ -0,0 +1,77 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{Estimate, Mean, Merge, Sum};
+
+#[test]
+fn trivial() {
+    let mut a = Sum::new();
+    assert!(a.is_empty());
+    assert_eq!(a.sum(), 0.0);
+    a.add(1.0);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.sum(), 1.0);
+    a.add(2.0);
+    assert_eq!(a.sum(), 3.0);
+    assert_eq!(a.estimate(), 3.0);
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn merge() {
+    let sequence: Vec<f64> = (0..100).map(|i| f64::from(i) * 0.1).collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Sum = sequence.iter().collect();
+        let mut a: Sum = left.iter().collect();
+        let b: Sum = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_almost_eq!(a.sum(), total.sum(), 1e-14);
+    }
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: Sum = (1..6).map(f64::from).collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: Sum = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.sum(), 15.0);
+    assert_eq!(c.len(), 5);
+}
+
+#[test]
+fn compensated() {
+    let n = 10_000_000;
+    let values = (0..n).map(|i| 0.1 + f64::from(i % 7) * 1e-3);
+    let mut a = Sum::new();
+    let mut naive = 0.;
+    // Reference using classic Kahan summation.
+    let (mut kahan, mut c) = (0., 0.);
+    for x in values {
+        a.add(x);
+        naive += x;
+        let y = x - c;
+        let t = kahan + y;
+        c = (t - kahan) - y;
+        kahan = t;
+    }
+    assert_eq!(a.len(), n as u64);
+    assert_almost_eq!(a.sum(), kahan, 1e-15);
+    assert!((a.sum() - kahan).abs() < (naive - kahan).abs());
+}
+
+#[test]
+fn concatenate() {
+    concatenate!(SumMean, [Sum, sum], [Mean, mean]);
+    let a: SumMean = (1..6).map(f64::from).collect();
+    assert_eq!(a.sum(), 15.0);
+    assert_eq!(a.mean(), 3.0);
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate rand;
+
+use rand::distributions::{Distribution, Exp};
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use average::{Estimate, Merge, Quantile, TDigest};
+
+/// Calculate the exact quantile of sorted samples, interpolating linearly.
+fn exact_quantile(sorted: &[f64], p: f64) -> f64 {
+    let h = (sorted.len() - 1) as f64 * p;
+    let i = h.floor() as usize;
+    if i + 1 == sorted.len() {
+        return sorted[i];
+    }
+    sorted[i] + (h - h.floor()) * (sorted[i + 1] - sorted[i])
+}
+
+/// Calculate the fraction of sorted samples smaller than or equal to `x`.
+fn rank(sorted: &[f64], x: f64) -> f64 {
+    let i = sorted.iter().position(|&y| y > x).unwrap_or(sorted.len());
+    i as f64 / sorted.len() as f64
+}
+
+fn skewed_sample(n: usize) -> Vec<f64> {
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let exp = Exp::new(1.);
+    // The square of an exponential distribution has a heavier tail.
+    (0..n).map(|_| { let x = exp.sample(&mut rng); x * x }).collect()
+}
+
+#[test]
+fn trivial() {
+    let mut a = TDigest::new(100.);
+    assert!(a.is_empty());
+    assert_eq!(a.quantile(0.5), 0.);
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.quantile(0.), 1.);
+    assert_eq!(a.quantile(0.5), 1.);
+    assert_eq!(a.quantile(1.), 1.);
+    a.add(3.);
+    assert_eq!(a.quantile(0.), 1.);
+    assert_eq!(a.quantile(1.), 3.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.compression(), 100.);
+}
+
+#[test]
+fn small_exact() {
+    let a: TDigest = (1..11).map(f64::from).collect();
+    assert_eq!(a.centroids(), 10);
+    assert_eq!(a.quantile(0.), 1.);
+    assert_eq!(a.quantile(0.55), 6.);
+    assert_eq!(a.quantile(1.), 10.);
+}
+
+#[test]
+fn uniform() {
+    let a: TDigest = (0..100_001).map(f64::from).collect();
+    assert!(a.centroids() <= 100);
+    for &p in &[0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+        assert_almost_eq!(a.quantile(p), p * 100_000., 100_000. * 2e-3);
+    }
+}
+
+#[test]
+fn tails() {
+    let sample = skewed_sample(1_000_000);
+    let mut sorted = sample.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let digest: TDigest = sample.iter().collect();
+    assert_eq!(digest.quantile(0.), sorted[0]);
+    assert_eq!(digest.quantile(1.), sorted[sorted.len() - 1]);
+    for &p in &[0.5, 0.9, 0.99, 0.999, 0.9999] {
+        let exact = exact_quantile(&sorted, p);
+        assert_almost_eq!(digest.quantile(p), exact, exact * 1e-2);
+    }
+}
+
+#[test]
+fn tails_sorted() {
+    // P² degrades if the samples are not in random order, the t-digest does
+    // not.
+    let mut sorted = skewed_sample(1_000_000);
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let digest: TDigest = sorted.iter().collect();
+    for &p in &[0.99, 0.999, 0.9999] {
+        let mut p2 = Quantile::new(p);
+        for &x in &sorted {
+            p2.add(x);
+        }
+        let exact = exact_quantile(&sorted, p);
+        let digest_error = ((digest.quantile(p) - exact) / exact).abs();
+        let p2_error = ((p2.quantile() - exact) / exact).abs();
+        assert!(digest_error < 2e-2, "error {} for p = {}", digest_error, p);
+        assert!(digest_error < p2_error,
+                "error {} not smaller than {} for p = {}", digest_error, p2_error, p);
+    }
+}
+
+#[test]
+fn merge() {
+    let sample = skewed_sample(100_000);
+    let mut sorted = sample.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut parts: Vec<TDigest> = sample.chunks(10_000).map(|c| c.iter().collect()).collect();
+    let mut merged = parts.pop().unwrap();
+    for d in &parts {
+        merged.merge(d);
+    }
+    assert_eq!(merged.len(), 100_000);
+    assert!(merged.centroids() <= 100);
+    assert_eq!(merged.quantile(0.), sorted[0]);
+    assert_eq!(merged.quantile(1.), sorted[sorted.len() - 1]);
+    // Compare the ranks, because the quantiles close to zero are very dense.
+    // The tails are more accurate than the center.
+    for &p in &[0.01, 0.1, 0.5, 0.9] {
+        assert_almost_eq!(rank(&sorted, merged.quantile(p)), p, 5e-3);
+    }
+    for &p in &[0.99, 0.999] {
+        assert_almost_eq!(rank(&sorted, merged.quantile(p)), p, 1e-4);
+    }
+}
+
+#[test]
+#[should_panic(expected = "compression must be positive")]
+fn zero_compression() {
+    TDigest::new(0.);
+}
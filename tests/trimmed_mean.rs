@@ -0,0 +1,108 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+use average::{Estimate, Mean, Merge, TrimmedMean, WinsorizedMean};
+
+#[test]
+fn trivial() {
+    let mut a = TrimmedMean::new(0.1);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(2.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.trimmed_len(), 0);
+    assert_eq!(a.mean(), 2.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.trim(), 0.1);
+}
+
+#[test]
+fn untrimmed() {
+    let mut a = TrimmedMean::new(0.);
+    for i in 1..6 {
+        a.add(f64::from(i));
+    }
+    assert_eq!(a.mean(), 3.);
+}
+
+#[test]
+fn outliers() {
+    let clean: Vec<f64> = (0..90).map(|i| 10. + (f64::from(i) * 0.37).sin()).collect();
+    let mut data = clean.clone();
+    data.extend_from_slice(&[1e6, 2e6, 5e5, 1e7, 3e6]);
+    data.extend_from_slice(&[-1e6, -4e5, -2e6, -1e7, -3e6]);
+
+    let mut a = TrimmedMean::new(0.05);
+    for &x in &data {
+        a.add(x);
+    }
+    assert_eq!(a.trimmed_len(), 5);
+    let expected: Mean = clean.iter().collect();
+    assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+
+    let plain: Mean = data.iter().collect();
+    assert!((plain.mean() - expected.mean()).abs() > 100.);
+}
+
+#[test]
+fn merge() {
+    let data: Vec<f64> = (0..100).map(|i| f64::from((i * 37) % 100)).collect();
+    let mut total = TrimmedMean::new(0.25);
+    let mut left = TrimmedMean::new(0.25);
+    let mut right = TrimmedMean::new(0.25);
+    for (i, &x) in data.iter().enumerate() {
+        total.add(x);
+        if i < 30 { left.add(x) } else { right.add(x) }
+    }
+    left.merge(&right);
+    assert_eq!(left.len(), total.len());
+    assert_eq!(left.mean(), total.mean());
+    assert_eq!(total.mean(), 49.5);
+}
+
+#[test]
+#[should_panic(expected = "trim must be")]
+fn trim_too_large() {
+    TrimmedMean::new(0.5);
+}
+
+#[test]
+fn winsorized_trivial() {
+    let mut a = WinsorizedMean::new(0.1);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(2.);
+    assert_eq!(a.mean(), 2.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.trim(), 0.1);
+}
+
+#[test]
+fn winsorized() {
+    let data = [3., 1., 100., 2., 5., 4., -50., 6., 7., 8.];
+    let mut winsorized = WinsorizedMean::new(0.1);
+    let mut trimmed = TrimmedMean::new(0.1);
+    for &x in &data {
+        winsorized.add(x);
+        trimmed.add(x);
+    }
+    let plain: Mean = data.iter().collect();
+    assert_eq!(winsorized.len(), 10);
+    assert_eq!(winsorized.trimmed_len(), 1);
+    // -50 is replaced by 1 and 100 by 8.
+    assert_eq!(winsorized.mean(), (1. + 1. + 2. + 3. + 4. + 5. + 6. + 7. + 8. + 8.) / 10.);
+    assert_eq!(trimmed.mean(), (1. + 2. + 3. + 4. + 5. + 6. + 7. + 8.) / 8.);
+    assert_eq!(plain.mean(), 8.6);
+
+    let mut left = WinsorizedMean::new(0.1);
+    let mut right = WinsorizedMean::new(0.1);
+    for (i, &x) in data.iter().enumerate() {
+        if i < 4 { left.add(x) } else { right.add(x) }
+    }
+    left.merge(&right);
+    assert_eq!(left.mean(), winsorized.mean());
+}
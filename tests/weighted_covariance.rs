@@ -0,0 +1,192 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use average::{CoVariance, Merge, WeightedCoVariance, WeightKind};
+
+#[test]
+fn trivial() {
+    let mut a = WeightedCoVariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.weighted_covariance(), 0.0);
+    a.add(1.0, 2.0, 1.0);
+    assert!(!a.is_empty());
+    assert_eq!(a.weighted_mean_x(), 1.0);
+    assert_eq!(a.weighted_mean_y(), 2.0);
+    assert_eq!(a.sum_weights(), 1.0);
+    assert_eq!(a.sum_weights_sq(), 1.0);
+    assert_eq!(a.weighted_covariance(), 0.0);
+    assert_eq!(a.weighted_variance_x(), 0.0);
+    assert_eq!(a.weighted_variance_y(), 0.0);
+}
+
+#[test]
+fn unit_weights() {
+    let sequence: Vec<(f64, f64)> = (0..100)
+        .map(|i| {
+            let x = f64::from(i) * 0.1;
+            (x, x.sin() + 0.3 * x)
+        })
+        .collect();
+    let mut weighted = WeightedCoVariance::new();
+    let mut unweighted = CoVariance::new();
+    for &(x, y) in &sequence {
+        weighted.add(x, y, 1.0);
+        unweighted.add(x, y);
+    }
+    assert_almost_eq!(weighted.weighted_mean_x(), unweighted.mean_x(), 1e-14);
+    assert_almost_eq!(weighted.weighted_mean_y(), unweighted.mean_y(), 1e-14);
+    assert_almost_eq!(weighted.weighted_covariance(), unweighted.sample_covariance(), 1e-14);
+    assert_almost_eq!(weighted.weighted_variance_x(), unweighted.sample_variance_x(), 1e-14);
+    assert_almost_eq!(weighted.weighted_variance_y(), unweighted.sample_variance_y(), 1e-14);
+}
+
+#[test]
+fn simple() {
+    let mut a = WeightedCoVariance::new();
+    for &(x, y, w) in &[(1., 2., 1.), (2., 1., 2.), (3., 5., 1.)] {
+        a.add(x, y, w);
+    }
+    // Weighted means: x = 2, y = 2.25.
+    assert_almost_eq!(a.weighted_mean_x(), 2.0, 1e-15);
+    assert_almost_eq!(a.weighted_mean_y(), 2.25, 1e-15);
+    // Normalization: V_1 - V_2 / V_1 = 4 - 6 / 4 = 2.5.
+    assert_almost_eq!(a.weighted_variance_x(), 2. / 2.5, 1e-15);
+    assert_almost_eq!(a.weighted_covariance(), 3. / 2.5, 1e-15);
+}
+
+#[test]
+fn correlation() {
+    let mut weighted = WeightedCoVariance::new();
+    let mut unweighted = CoVariance::new();
+    for i in 0..50 {
+        let x = f64::from(i);
+        let y = (0.3 * x).sin() + 0.05 * x;
+        weighted.add(x, y, 1.);
+        unweighted.add(x, y);
+    }
+    assert_almost_eq!(weighted.weighted_correlation(), unweighted.pearson(), 1e-14);
+
+    let mut a = WeightedCoVariance::new();
+    for &(x, w) in &[(1., 1.), (2., 0.5), (3., 2.)] {
+        a.add(x, -2. * x + 1., w);
+    }
+    assert_almost_eq!(a.weighted_correlation(), -1., 1e-15);
+    a.reset();
+    assert_eq!(a.weighted_correlation(), 0.);
+}
+
+#[test]
+fn merge() {
+    let sequence: Vec<(f64, f64, f64)> = (0..20)
+        .map(|i| {
+            let x = f64::from(i) * 0.3;
+            (x, x.cos() + 0.5 * x, 0.1 + f64::from(i % 4))
+        })
+        .collect();
+    let mut total = WeightedCoVariance::new();
+    for &(x, y, w) in &sequence {
+        total.add(x, y, w);
+    }
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut a = WeightedCoVariance::new();
+        for &(x, y, w) in left {
+            a.add(x, y, w);
+        }
+        let mut b = WeightedCoVariance::new();
+        for &(x, y, w) in right {
+            b.add(x, y, w);
+        }
+        a.merge(&b);
+        assert_almost_eq!(a.sum_weights(), total.sum_weights(), 1e-13);
+        assert_almost_eq!(a.sum_weights_sq(), total.sum_weights_sq(), 1e-13);
+        assert_almost_eq!(a.weighted_mean_x(), total.weighted_mean_x(), 1e-14);
+        assert_almost_eq!(a.weighted_mean_y(), total.weighted_mean_y(), 1e-14);
+        assert_almost_eq!(a.weighted_covariance(), total.weighted_covariance(), 1e-13);
+        assert_almost_eq!(a.weighted_variance_x(), total.weighted_variance_x(), 1e-13);
+        assert_almost_eq!(a.weighted_variance_y(), total.weighted_variance_y(), 1e-13);
+        assert!(a.weighted_correlation().is_finite());
+        assert_almost_eq!(a.weighted_correlation(), total.weighted_correlation(), 1e-14);
+    }
+}
+
+#[test]
+fn merge_zero_weights() {
+    let mut a = WeightedCoVariance::new();
+    a.add(1., 2., 0.);
+    let mut b = WeightedCoVariance::new();
+    b.add(3., 4., 2.);
+    a.merge(&b);
+    assert_eq!(a.sum_weights(), 2.);
+    assert_eq!(a.weighted_mean_x(), 3.);
+    assert_eq!(a.weighted_mean_y(), 4.);
+}
+
+#[test]
+fn effective_len() {
+    let mut a = WeightedCoVariance::new();
+    assert_eq!(a.effective_len(), 0.);
+    for i in 0..10 {
+        a.add(f64::from(i), f64::from(i * i), 1.);
+    }
+    assert_eq!(a.effective_len(), 10.);
+    let mut b = WeightedCoVariance::new();
+    b.add(1., 1., 3.);
+    b.add(2., 1., 1.);
+    assert_eq!(b.effective_len(), 16. / 10.);
+    a.merge(&b);
+    assert_eq!(a.effective_len(), 196. / 20.);
+}
+
+#[test]
+fn frequency_weights() {
+    let data = [(1., 2., 3u32), (2., 1., 1), (4., 5., 2), (-1., 0., 4)];
+    let mut frequency = WeightedCoVariance::with_weight_kind(WeightKind::Frequency);
+    let mut reliability = WeightedCoVariance::new();
+    let mut expanded = CoVariance::new();
+    for &(x, y, w) in &data {
+        frequency.add(x, y, f64::from(w));
+        reliability.add(x, y, f64::from(w));
+        for _ in 0..w {
+            expanded.add(x, y);
+        }
+    }
+    assert_eq!(frequency.weight_kind(), WeightKind::Frequency);
+    assert_eq!(reliability.weight_kind(), WeightKind::Reliability);
+    assert_almost_eq!(frequency.weighted_covariance(), expanded.sample_covariance(), 1e-14);
+    assert_almost_eq!(frequency.weighted_variance_x(), expanded.sample_variance_x(), 1e-14);
+    assert_almost_eq!(frequency.weighted_variance_y(), expanded.sample_variance_y(), 1e-14);
+    assert_almost_eq!(frequency.weighted_mean_x(), reliability.weighted_mean_x(), 1e-14);
+    assert!((frequency.weighted_variance_x() - reliability.weighted_variance_x()).abs() > 0.1);
+
+    frequency.reset();
+    assert!(frequency.is_empty());
+    assert_eq!(frequency.weight_kind(), WeightKind::Frequency);
+}
+
+#[test]
+#[should_panic]
+fn merge_different_weight_kinds() {
+    let mut a = WeightedCoVariance::new();
+    let b = WeightedCoVariance::with_weight_kind(WeightKind::Frequency);
+    a.merge(&b);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = WeightedCoVariance::with_weight_kind(WeightKind::Frequency);
+    for &(x, y, w) in &[(1., 2., 3.), (2., 1., 1.), (4., 5., 2.)] {
+        a.add(x, y, w);
+    }
+    let b = serde_json::to_string(&a).unwrap();
+    let c: WeightedCoVariance = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.weight_kind(), WeightKind::Frequency);
+    assert_eq!(c.weighted_covariance(), a.weighted_covariance());
+    assert_eq!(c.sum_weights(), a.sum_weights());
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -8,7 +8,7 @@ extern crate serde_json;
 
 use core::iter::Iterator;
 
-use average::{WeightedMeanWithError, Merge};
+use average::{WeightedMean, WeightedMeanWithError, Merge};
 
 #[test]
 fn trivial() {
@@ -123,3 +123,77 @@ fn merge_weighted() {
         assert_almost_eq!(avg_total.sample_variance(), avg_left.sample_variance(), 1e-14);
     }
 }
+
+#[test]
+fn merge_weighted_mean() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.)];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: WeightedMean = sequence.iter().collect();
+        let mut avg_left: WeightedMean = left.iter().collect();
+        let avg_right: WeightedMean = right.iter().collect();
+        avg_left.merge(&avg_right);
+        assert_almost_eq!(avg_total.sum_weights(), avg_left.sum_weights(), 1e-15);
+        assert!(avg_left.mean().is_finite());
+        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-15);
+    }
+}
+
+#[test]
+fn merge_weighted_error() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.)];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: WeightedMeanWithError = sequence.iter().collect();
+        let mut avg_left: WeightedMeanWithError = left.iter().collect();
+        let avg_right: WeightedMeanWithError = right.iter().collect();
+        avg_left.merge(&avg_right);
+        assert!(avg_left.weighted_mean().is_finite());
+        assert!(avg_left.error().is_finite());
+        assert_almost_eq!(avg_total.effective_len(), avg_left.effective_len(), 1e-14);
+        assert_almost_eq!(avg_total.weighted_mean(), avg_left.weighted_mean(), 1e-15);
+        assert_almost_eq!(avg_total.error(), avg_left.error(), 1e-14);
+    }
+}
+
+#[test]
+fn merge_zero_weights() {
+    let mut a = WeightedMean::new();
+    a.add(1., 0.);
+    let mut b = WeightedMean::new();
+    b.add(2., 0.);
+    a.merge(&b);
+    assert_eq!(a.sum_weights(), 0.);
+    assert_eq!(a.mean(), 0.);
+}
+
+#[test]
+fn effective_len() {
+    let a: WeightedMeanWithError = (1..11).map(|x| (f64::from(x), 1.)).collect();
+    assert_eq!(a.effective_len(), 10.);
+    let a: WeightedMeanWithError = (1..11).map(|x| (f64::from(x), 0.25)).collect();
+    assert_eq!(a.effective_len(), 10.);
+    // One dominating weight reduces the effective sample size.
+    let a: WeightedMeanWithError = [(1., 1.), (2., 1.), (3., 10.)].iter().collect();
+    assert_eq!(a.effective_len(), 144. / 102.);
+    assert_eq!(WeightedMeanWithError::new().effective_len(), 0.);
+}
+
+#[test]
+fn weighted_mean_effective_len() {
+    let a: WeightedMean = (1..11).map(|x| (f64::from(x), 1.)).collect();
+    assert_eq!(a.effective_len(), 10.);
+    let data = [(1., 1.), (2., 1.), (3., 10.)];
+    let a: WeightedMean = data.iter().collect();
+    let b: WeightedMeanWithError = data.iter().collect();
+    assert_eq!(a.effective_len(), 144. / 102.);
+    assert_eq!(a.effective_len(), b.effective_len());
+    let mut c: WeightedMean = data[..1].iter().collect();
+    c.merge(&data[1..].iter().collect());
+    assert_eq!(c.effective_len(), a.effective_len());
+    assert_eq!(WeightedMean::new().effective_len(), 0.);
+}
@@ -0,0 +1,58 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+
+use average::{Estimate, Kurtosis, Skewness, WeightedKurtosis, WeightedSkewness};
+
+#[test]
+fn trivial() {
+    let mut a = WeightedKurtosis::new();
+    assert!(a.is_empty());
+    assert_eq!(a.weighted_skewness(), 0.);
+    assert_eq!(a.weighted_kurtosis(), 0.);
+    a.add(1., 2.);
+    assert_eq!(a.sum_weights(), 2.);
+    assert_eq!(a.weighted_mean(), 1.);
+    assert_eq!(a.weighted_population_variance(), 0.);
+    assert_eq!(a.weighted_skewness(), 0.);
+    assert_eq!(a.weighted_kurtosis(), 0.);
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn unit_weights() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let expected_skewness: Skewness = sequence.iter().collect();
+    let expected_kurtosis: Kurtosis = sequence.iter().collect();
+    let a: WeightedSkewness = sequence.iter().map(|&x| (x, 1.)).collect();
+    let b: WeightedKurtosis = sequence.iter().map(|&x| (x, 1.)).collect();
+    assert_almost_eq!(a.weighted_mean(), expected_skewness.mean(), 1e-15);
+    assert_almost_eq!(a.weighted_population_variance(),
+                      expected_skewness.population_variance(), 1e-14);
+    assert_almost_eq!(a.weighted_skewness(), expected_skewness.skewness(), 1e-14);
+    assert_almost_eq!(b.weighted_skewness(), expected_kurtosis.skewness(), 1e-14);
+    assert_almost_eq!(b.weighted_kurtosis(), expected_kurtosis.kurtosis(), 1e-14);
+}
+
+#[test]
+fn integer_weights() {
+    let weighted: &[(f64, f64)] = &[(1., 1.), (2., 3.), (-4., 2.), (7.5, 1.), (3., 4.)];
+    let mut expected = Kurtosis::new();
+    for &(x, w) in weighted {
+        for _ in 0..(w as u32) {
+            expected.add(x);
+        }
+    }
+    let a: WeightedKurtosis = weighted.iter().collect();
+    assert_almost_eq!(a.weighted_mean(), expected.mean(), 1e-14);
+    assert_almost_eq!(a.weighted_skewness(), expected.skewness(), 1e-14);
+    assert_almost_eq!(a.weighted_kurtosis(), expected.kurtosis(), 1e-14);
+
+    // Scaling the weights does not change the results.
+    let b: WeightedKurtosis = weighted.iter().map(|&(x, w)| (x, 0.1 * w)).collect();
+    assert_almost_eq!(b.weighted_skewness(), a.weighted_skewness(), 1e-14);
+    assert_almost_eq!(b.weighted_kurtosis(), a.weighted_kurtosis(), 1e-14);
+}
@@ -0,0 +1,88 @@
+#![allow(clippy::float_cmp)]
+
+#[macro_use] extern crate average;
+
+extern crate core;
+extern crate rand;
+
+use average::{Estimate, Quantile, WeightedQuantile};
+
+#[test]
+fn few_observations() {
+    let mut q = WeightedQuantile::new(0.5);
+    assert!(q.is_empty());
+    assert_eq!(q.quantile(), 0.);
+    q.add(3., 1.);
+    assert_eq!(q.quantile(), 3.);
+    q.add(1., 1.);
+    assert_eq!(q.quantile(), 2.);
+    q.add(2., 2.);
+    assert_eq!(q.len(), 3);
+    assert_eq!(q.sum_weights(), 4.);
+    assert_eq!(q.quantile(), 2.);
+    q.add(4., 4.);
+    assert_eq!(q.quantile(), 3.5);
+    q.add(5., 0.);
+    assert_eq!(q.len(), 4);
+    q.reset();
+    assert!(q.is_empty());
+    assert_eq!(q.p(), 0.5);
+}
+
+#[test]
+fn unit_weights() {
+    use rand::distributions::{Distribution, Normal};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let normal = Normal::new(2.0, 3.0);
+    for &p in &[0.1, 0.5, 0.99] {
+        let mut expected = Quantile::new(p);
+        let mut q = WeightedQuantile::new(p);
+        for _ in 0..10_000 {
+            let x = normal.sample(&mut rng);
+            expected.add(x);
+            q.add(x, 1.);
+        }
+        assert_almost_eq!(q.quantile(), expected.quantile(), 1e-3);
+    }
+}
+
+#[test]
+fn integer_weights() {
+    use rand::distributions::{Distribution, Uniform};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    let mut rng = SmallRng::from_seed(
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let values = Uniform::new(0., 100.);
+    let weights = Uniform::new(1u32, 6);
+    let sample: Vec<(f64, u32)> = (0..10_000)
+        .map(|_| (values.sample(&mut rng), weights.sample(&mut rng)))
+        .collect();
+    let mut expanded: Vec<f64> = sample.iter()
+        .flat_map(|&(x, w)| (0..w).map(move |_| x))
+        .collect();
+    expanded.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for &p in &[0.1, 0.25, 0.5, 0.75, 0.9] {
+        let mut q = WeightedQuantile::new(p);
+        for &(x, w) in &sample {
+            q.add(x, f64::from(w));
+        }
+        let index = (p * expanded.len() as f64).ceil() as usize - 1;
+        assert_almost_eq!(q.quantile(), expanded[index], 1.);
+    }
+}
+
+#[test]
+fn from_iter() {
+    let sample = [(1., 1.), (2., 1.), (3., 3.)];
+    let a: WeightedQuantile = sample.iter().collect();
+    assert_eq!(a.p(), 0.5);
+    assert_eq!(a.quantile(), 3.);
+    let b: WeightedQuantile = sample.iter().cloned().collect();
+    assert_eq!(b.quantile(), 3.);
+}